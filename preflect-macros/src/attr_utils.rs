@@ -1,9 +1,19 @@
 use crate::errors::GetHelperAttrError;
-use attribution::AttrArgs;
 use proc_macro2::Ident;
 use proc_macro2::Span;
+use syn::parenthesized;
+use syn::parse::Error as ParseError;
+use syn::parse::Parse;
+use syn::parse::ParseStream;
+use syn::parse::Result as ParseResult;
+use syn::punctuated::Punctuated;
 use syn::Attribute;
+use syn::Expr;
+use syn::ExprLit;
 use syn::Field;
+use syn::Lit;
+use syn::LitStr;
+use syn::Token;
 
 pub fn get_preflect_attr(field: &Field) -> Result<HelperAttr, GetHelperAttrError> {
     // Parse all the preflect helper attributes
@@ -18,15 +28,35 @@ pub fn get_preflect_attr(field: &Field) -> Result<HelperAttr, GetHelperAttrError
     // Ensure that there are not multiple helper attributes defined
     let second_preflect_attr_exists = helper_attrs.len() > 1;
     if second_preflect_attr_exists {
-        let span = field
-            .ident
-            .as_ref()
-            .map(Ident::span)
-            .unwrap_or_else(Span::call_site);
+        let span = field_span(field);
         return Err(GetHelperAttrError::MultipleAttributes { span });
     }
 
-    Ok(helper_attrs.pop().unwrap_or_default())
+    let helper_attr = helper_attrs.pop().unwrap_or_default();
+    let alloc_attr = if helper_attr.collection {
+        Some("collection")
+    } else if helper_attr.pointer {
+        Some("pointer")
+    } else {
+        None
+    };
+    if let (Some(attr), false) = (alloc_attr, cfg!(feature = "alloc")) {
+        return Err(GetHelperAttrError::MissingFeature {
+            attr,
+            feature: "alloc",
+            span: field_span(field),
+        });
+    }
+
+    Ok(helper_attr)
+}
+
+fn field_span(field: &Field) -> Span {
+    field
+        .ident
+        .as_ref()
+        .map(Ident::span)
+        .unwrap_or_else(Span::call_site)
 }
 
 fn is_preflect_attr(attr: &Attribute) -> bool {
@@ -36,24 +66,259 @@ fn is_preflect_attr(attr: &Attribute) -> bool {
         .unwrap_or(false)
 }
 
-#[derive(AttrArgs, Debug)]
-pub enum HelperAttr {
-    Ignore { ignore: bool },
-    Alias { alias: Vec<String> },
-    Default,
+/// The arguments of a `#[preflect(...)]` attribute. Several arguments can be
+/// combined in one attribute (e.g. `#[preflect(nested, alias = "addr")]`).
+#[derive(Debug, Default, Eq, PartialEq)]
+pub struct HelperAttr {
+    ignore: bool,
+    nested: bool,
+    collection: bool,
+    pointer: bool,
+    redact: bool,
+    alias: Vec<Alias>,
 }
 
 impl HelperAttr {
     pub fn ignore(&self) -> bool {
-        match self {
-            HelperAttr::Ignore { ignore } => *ignore,
-            _ => false,
+        self.ignore
+    }
+
+    pub fn nested(&self) -> bool {
+        self.nested
+    }
+
+    pub fn collection(&self) -> bool {
+        self.collection
+    }
+
+    pub fn pointer(&self) -> bool {
+        self.pointer
+    }
+
+    pub fn redacted(&self) -> bool {
+        self.redact
+    }
+
+    pub fn aliases(&self) -> impl Iterator<Item = &str> {
+        self.alias.iter().map(|alias| alias.name.as_str())
+    }
+
+    /// The aliases as string literals spanning the place they were declared.
+    pub fn alias_lits(&self) -> impl Iterator<Item = LitStr> + '_ {
+        self.alias
+            .iter()
+            .map(|alias| LitStr::new(&alias.name, alias.span))
+    }
+}
+
+/// An alias of a field, along with where it was declared so that errors can
+/// point at it. Aliases are compared by their names.
+#[derive(Debug)]
+struct Alias {
+    name: String,
+    span: Span,
+}
+
+impl PartialEq for Alias {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+    }
+}
+
+impl Eq for Alias {}
+
+#[cfg(test)]
+impl From<&str> for Alias {
+    fn from(name: &str) -> Self {
+        Self {
+            name: name.into(),
+            span: Span::call_site(),
         }
     }
 }
 
-impl Default for HelperAttr {
-    fn default() -> Self {
-        Self::Default
+impl Parse for HelperAttr {
+    fn parse(input: ParseStream) -> ParseResult<Self> {
+        let mut helper_attr = HelperAttr::default();
+        if input.is_empty() {
+            return Ok(helper_attr);
+        }
+
+        let content;
+        parenthesized!(content in input);
+        let args = Punctuated::<HelperArg, Token![,]>::parse_terminated(&content)?;
+
+        // A field can only be traversed in one way.
+        let mut traversal: Option<&Ident> = None;
+        for arg in args.iter() {
+            let flag = match arg.key.to_string().as_str() {
+                "ignore" => &mut helper_attr.ignore,
+                "nested" => &mut helper_attr.nested,
+                "collection" => &mut helper_attr.collection,
+                "pointer" => &mut helper_attr.pointer,
+                "redact" => &mut helper_attr.redact,
+                "alias" => {
+                    helper_attr.alias.extend(parse_alias(arg)?);
+                    continue;
+                }
+                _ => {
+                    let message = format!("Unknown preflect argument '{}'.", arg.key);
+                    return Err(ParseError::new(arg.key.span(), message));
+                }
+            };
+
+            *flag = parse_flag(arg)?;
+            if *flag && arg.key != "ignore" && arg.key != "redact" {
+                if let Some(previous) = traversal {
+                    let message = format!(
+                        "#[preflect({})] can not be combined with #[preflect({})].",
+                        arg.key, previous
+                    );
+                    return Err(ParseError::new(arg.key.span(), message));
+                }
+
+                traversal = Some(&arg.key);
+            }
+        }
+
+        Ok(helper_attr)
+    }
+}
+
+/// A single argument of a `#[preflect(...)]` attribute, which is either a
+/// flag (e.g. `nested`) or a key and a value (e.g. `alias = "addr"`).
+struct HelperArg {
+    key: Ident,
+    value: Option<Expr>,
+}
+
+impl Parse for HelperArg {
+    fn parse(input: ParseStream) -> ParseResult<Self> {
+        let key = input.parse()?;
+        let value = if input.peek(Token![=]) {
+            input.parse::<Token![=]>()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+
+        Ok(Self { key, value })
+    }
+}
+
+/// Parses a flag which is either present (e.g. `nested`) or set explicitly
+/// (e.g. `nested = false`).
+fn parse_flag(arg: &HelperArg) -> ParseResult<bool> {
+    match &arg.value {
+        None => Ok(true),
+        Some(Expr::Lit(ExprLit {
+            lit: Lit::Bool(value),
+            ..
+        })) => Ok(value.value),
+        Some(value) => {
+            let message = format!("Expected '{}' to be set to a bool.", arg.key);
+            Err(ParseError::new_spanned(value, message))
+        }
+    }
+}
+
+/// Parses the aliases of a field, which are either a single string (e.g.
+/// `alias = "addr"`) or an array of strings (e.g. `alias = ["addr", "a"]`).
+fn parse_alias(arg: &HelperArg) -> ParseResult<Vec<Alias>> {
+    let message = "Expected 'alias' to be set to a string or an array of strings.";
+    let make_alias = |alias: &LitStr| Alias {
+        name: alias.value(),
+        span: alias.span(),
+    };
+    match &arg.value {
+        Some(Expr::Lit(ExprLit {
+            lit: Lit::Str(alias),
+            ..
+        })) => Ok(vec![make_alias(alias)]),
+        Some(Expr::Array(aliases)) => aliases
+            .elems
+            .iter()
+            .map(|alias| match alias {
+                Expr::Lit(ExprLit {
+                    lit: Lit::Str(alias),
+                    ..
+                }) => Ok(make_alias(alias)),
+                _ => Err(ParseError::new_spanned(alias, message)),
+            })
+            .collect(),
+        Some(value) => Err(ParseError::new_spanned(value, message)),
+        None => Err(ParseError::new(arg.key.span(), message)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use syn::parse_quote;
+    use syn::FieldsNamed;
+
+    #[test]
+    fn get_preflect_attr_combined_test() {
+        let fields: FieldsNamed = parse_quote!({
+            #[preflect(nested, redact, alias = ["addr", "a"], alias = "home")]
+            address: Address
+        });
+        let field = &fields.named[0];
+
+        let actual = get_preflect_attr(field).unwrap();
+        let expected = HelperAttr {
+            nested: true,
+            redact: true,
+            alias: vec!["addr".into(), "a".into(), "home".into()],
+            ..HelperAttr::default()
+        };
+
+        assert_eq!(actual, expected)
+    }
+
+    #[test]
+    fn get_preflect_attr_explicit_flag_test() {
+        let fields: FieldsNamed = parse_quote!({
+            #[preflect(ignore = false)]
+            id: u32
+        });
+        let field = &fields.named[0];
+
+        let actual = get_preflect_attr(field).unwrap();
+
+        assert_eq!(actual, HelperAttr::default())
+    }
+
+    #[test]
+    fn get_preflect_attr_conflicting_traversal_test() {
+        let fields: FieldsNamed = parse_quote!({
+            #[preflect(nested, collection)]
+            items: Vec<Item>
+        });
+        let field = &fields.named[0];
+
+        let actual = get_preflect_attr(field).unwrap_err();
+
+        assert_eq!(
+            "There was a problem parsing the attribute body: #[preflect(collection)] can not be combined with #[preflect(nested)].",
+            actual.to_string()
+        )
+    }
+
+    #[test]
+    fn get_preflect_attr_unknown_argument_test() {
+        let fields: FieldsNamed = parse_quote!({
+            #[preflect(flatten)]
+            address: Address
+        });
+        let field = &fields.named[0];
+
+        let actual = get_preflect_attr(field).unwrap_err();
+
+        assert_eq!(
+            "There was a problem parsing the attribute body: Unknown preflect argument 'flatten'.",
+            actual.to_string()
+        )
     }
 }
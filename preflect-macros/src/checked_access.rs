@@ -0,0 +1,255 @@
+use crate::field_name::make_field_name_type;
+use crate::field_name::FieldNameInput;
+use proc_macro2::TokenStream;
+use quote::quote_spanned;
+use syn::ext::IdentExt;
+use syn::parse::Parse;
+use syn::parse::ParseStream;
+use syn::parse::Result as ParseResult;
+use syn::parse_quote;
+use syn::Expr;
+use syn::LitStr;
+use syn::Member;
+use syn::Path;
+use syn::Token;
+
+/// The input of the `field!` macro. A field can either be named on its own
+/// (e.g. `field!(name)`) or accessed through a value (e.g.
+/// `field!(user.name)`).
+pub enum FieldInput {
+    Name(FieldNameInput),
+    Access(Box<FieldAccessInput>),
+}
+
+impl Parse for FieldInput {
+    fn parse(input: ParseStream) -> ParseResult<Self> {
+        let fork = input.fork();
+        let is_access = fork.peek(Token![mut])
+            || matches!(fork.parse::<Expr>(), Ok(Expr::Field(_)) if fork.is_empty());
+
+        if is_access {
+            input.parse().map(FieldInput::Access)
+        } else {
+            input.parse().map(FieldInput::Name)
+        }
+    }
+}
+
+/// An access to a field of a value whose name is checked at compile time
+/// (e.g. `field!(user.name)` or `field!(mut user.name)`).
+pub struct FieldAccessInput {
+    mut_token: Option<Token![mut]>,
+    base: Expr,
+    name: LitStr,
+}
+
+impl Parse for FieldAccessInput {
+    fn parse(input: ParseStream) -> ParseResult<Self> {
+        let mut_token = input.parse()?;
+        let (base, member) = match input.parse()? {
+            Expr::Field(field) => (*field.base, field.member),
+            other => {
+                let message = "expected a field of a value (e.g. `user.name`)";
+                return Err(syn::Error::new_spanned(other, message));
+            }
+        };
+
+        let name = match member {
+            Member::Named(ident) => LitStr::new(&ident.unraw().to_string(), ident.span()),
+            Member::Unnamed(index) => LitStr::new(&index.index.to_string(), index.span),
+        };
+
+        Ok(Self {
+            mut_token,
+            base,
+            name,
+        })
+    }
+}
+
+/// The input of the `partial_drop!` macro: the value to partially drop
+/// followed by the names of the fields to preserve.
+#[cfg(feature = "partial-drop")]
+pub struct PartialDropInput {
+    value: Expr,
+    names: Vec<LitStr>,
+}
+
+#[cfg(feature = "partial-drop")]
+impl Parse for PartialDropInput {
+    fn parse(input: ParseStream) -> ParseResult<Self> {
+        let value = input.parse()?;
+        let mut names = vec![];
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+
+            let span = input.span();
+            let name = input.parse::<FieldNameInput>()?;
+            names.push(LitStr::new(name.name(), span));
+        }
+
+        Ok(Self { value, names })
+    }
+}
+
+/// Builds the expression which accesses a field after checking that the type
+/// of the value has a field with that name. The check is a trait bound, so a
+/// misspelled name is reported while the code is type checked.
+pub fn make_checked_field_access(input: &FieldAccessInput) -> Expr {
+    let FieldAccessInput {
+        mut_token,
+        base,
+        name,
+    } = input;
+    let name_check = make_name_check(&parse_quote!(::preflect::fields::check_field_name), name);
+    let get_field = if mut_token.is_some() {
+        quote_spanned!(name.span()=> get_field_mut)
+    } else {
+        quote_spanned!(name.span()=> get_field)
+    };
+
+    parse_quote! {
+        {
+            trait PreflectCheckedField {
+                fn preflect_checked_field(& #mut_token self) -> & #mut_token Self;
+            }
+
+            impl<T: ::preflect::fields::HasFieldInfo + ?Sized> PreflectCheckedField for T {
+                fn preflect_checked_field(& #mut_token self) -> & #mut_token Self {
+                    self
+                }
+            }
+
+            let preflect_value = (#base).preflect_checked_field();
+            #name_check
+            ::preflect::fields::HasFields::#get_field(preflect_value, #name)
+        }
+    }
+}
+
+/// Builds the expression which partially drops a value after checking that
+/// the type of the value can preserve each of the named fields.
+#[cfg(feature = "partial-drop")]
+pub fn make_checked_partial_drop(input: &PartialDropInput) -> Expr {
+    let PartialDropInput { value, names } = input;
+    let check = parse_quote!(::preflect::drop::check_droppable_field);
+    let name_checks = names.iter().map(|name| make_name_check(&check, name));
+
+    parse_quote! {
+        {
+            trait PreflectCheckedDrop {
+                fn preflect_checked_drop(&mut self) -> &mut Self;
+            }
+
+            impl<T: ::preflect::drop::PartialDrop + ?Sized> PreflectCheckedDrop for T {
+                fn preflect_checked_drop(&mut self) -> &mut Self {
+                    self
+                }
+            }
+
+            let preflect_value = (#value).preflect_checked_drop();
+            #(#name_checks)*
+            ::preflect::drop::PartialDrop::drop_all_fields_except(preflect_value, &[#(#names),*])
+        }
+    }
+}
+
+/// Builds the statement which checks a name by calling `check` with the name
+/// encoded as a type. The statement is spanned to the name so that the
+/// compiler points at it when the type has no such field.
+fn make_name_check(check: &Path, name: &LitStr) -> TokenStream {
+    let name_ty = make_field_name_type(&name.value());
+    quote_spanned! {name.span()=>
+        #check::<#name_ty, _>(&*preflect_value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn field_input_test() {
+        let inputs = [
+            syn::parse_str::<FieldInput>("name").unwrap(),
+            syn::parse_str::<FieldInput>("0").unwrap(),
+            syn::parse_str::<FieldInput>("user.name").unwrap(),
+            syn::parse_str::<FieldInput>("mut user.0").unwrap(),
+        ];
+
+        let actual = inputs
+            .iter()
+            .map(|input| match input {
+                FieldInput::Name(name) => name.name().to_string(),
+                FieldInput::Access(access) => access.name.value(),
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(vec!["name", "0", "name", "0"], actual)
+    }
+
+    #[test]
+    fn make_checked_field_access_test() {
+        let input = syn::parse_str::<FieldAccessInput>("mut user.r#type").unwrap();
+        let actual = make_checked_field_access(&input);
+        let expected: Expr = parse_quote! {
+            {
+                trait PreflectCheckedField {
+                    fn preflect_checked_field(&mut self) -> &mut Self;
+                }
+
+                impl<T: ::preflect::fields::HasFieldInfo + ?Sized> PreflectCheckedField for T {
+                    fn preflect_checked_field(&mut self) -> &mut Self {
+                        self
+                    }
+                }
+
+                let preflect_value = (user).preflect_checked_field();
+                ::preflect::fields::check_field_name::<
+                    ::preflect::fields::NameChar<'t', ::preflect::fields::NameChar<'y', ::preflect::fields::NameChar<'p', ::preflect::fields::NameChar<'e', ::preflect::fields::NameEnd>>>>,
+                    _
+                >(&*preflect_value);
+                ::preflect::fields::HasFields::get_field_mut(preflect_value, "type")
+            }
+        };
+
+        assert_eq!(actual, expected)
+    }
+
+    #[cfg(feature = "partial-drop")]
+    #[test]
+    fn make_checked_partial_drop_test() {
+        let input = syn::parse_str::<PartialDropInput>("user, id, \"name\"").unwrap();
+        let actual = make_checked_partial_drop(&input);
+        let expected: Expr = parse_quote! {
+            {
+                trait PreflectCheckedDrop {
+                    fn preflect_checked_drop(&mut self) -> &mut Self;
+                }
+
+                impl<T: ::preflect::drop::PartialDrop + ?Sized> PreflectCheckedDrop for T {
+                    fn preflect_checked_drop(&mut self) -> &mut Self {
+                        self
+                    }
+                }
+
+                let preflect_value = (user).preflect_checked_drop();
+                ::preflect::drop::check_droppable_field::<
+                    ::preflect::fields::NameChar<'i', ::preflect::fields::NameChar<'d', ::preflect::fields::NameEnd>>,
+                    _
+                >(&*preflect_value);
+                ::preflect::drop::check_droppable_field::<
+                    ::preflect::fields::NameChar<'n', ::preflect::fields::NameChar<'a', ::preflect::fields::NameChar<'m', ::preflect::fields::NameChar<'e', ::preflect::fields::NameEnd>>>>,
+                    _
+                >(&*preflect_value);
+                ::preflect::drop::PartialDrop::drop_all_fields_except(preflect_value, &["id", "name"])
+            }
+        };
+
+        assert_eq!(actual, expected)
+    }
+}
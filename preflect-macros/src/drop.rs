@@ -1,61 +1,605 @@
 #![cfg(feature = "partial-drop")]
 
+use crate::attr_utils::get_preflect_attr;
+use crate::errors::GetHelperAttrError;
+use crate::errors::PreflectMacroError;
+use crate::field_name::impl_field_name_markers;
+use crate::field_utils::check_unique_aliases;
+use crate::field_utils::get_field_member;
+use crate::field_utils::get_field_name;
+use crate::field_utils::get_unique_names;
 use proc_macro2::Ident;
-use proc_macro2::Span as Span2;
+use proc_macro2::TokenStream;
+use quote::format_ident;
+use quote::quote;
+use quote::ToTokens;
 use syn::parse_quote;
+use syn::Arm;
 use syn::Data;
-use syn::DataStruct;
+use syn::DataEnum;
 use syn::DeriveInput;
+use syn::Expr;
+use syn::ExprIf;
 use syn::Field;
+use syn::Fields;
+use syn::Generics;
+use syn::ImplItemMethod;
 use syn::ItemImpl;
 use syn::LitStr;
+use syn::Member;
 
-pub fn partial_drop_derive_impl(tagged: &DeriveInput) -> Result<ItemImpl, syn::Error> {
+/// A field which is dropped by `PartialDrop`.
+struct DropField<'a> {
+    field: &'a Field,
+    member: Member,
+    /// The name of the field followed by its aliases.
+    names: Vec<LitStr>,
+    /// An expression which evaluates to a mutable reference to the field.
+    field_ref: Expr,
+}
+
+/// A variant of an enum along with its fields which are dropped.
+struct DropVariant<'a> {
+    ident: &'a Ident,
+    fields: Vec<DropField<'a>>,
+    /// The fields marked with `#[preflect(ignore)]`.
+    ignored: Vec<DropField<'a>>,
+}
+
+pub fn partial_drop_derive_impl(tagged: &DeriveInput) -> Result<TokenStream, PreflectMacroError> {
     let type_name = &tagged.ident;
-    if let Data::Struct(tagged_struct) = &tagged.data {
-        Ok(impl_partial_drop_for_struct(type_name, tagged_struct))
+    let (field_drops, ignored_drops, accepted_names, checked_methods) = match &tagged.data {
+        Data::Struct(tagged_struct) => {
+            check_unique_aliases(&tagged_struct.fields)?;
+            let make_ref = |member: &Member, _| parse_quote!(&mut self.#member);
+            let fields = get_drop_fields(&tagged_struct.fields, false, make_ref)?;
+            let ignored = get_drop_fields(&tagged_struct.fields, true, make_ref)?;
+            (
+                build_struct_drops(&fields),
+                build_struct_ignored_drops(&ignored),
+                get_unique_names(fields.iter().flat_map(|field| &field.names)),
+                make_struct_checked_methods(&fields),
+            )
+        }
+        Data::Enum(tagged_enum) => {
+            let variants = get_drop_variants(tagged_enum)?;
+            let names = variants
+                .iter()
+                .flat_map(|variant| variant.fields.iter().flat_map(|field| &field.names));
+            (
+                build_enum_drops(&variants),
+                build_enum_ignored_drops(&variants),
+                get_unique_names(names),
+                make_enum_checked_methods(&variants),
+            )
+        }
+        Data::Union(_) => {
+            let message = "PartialDrop can only be derived for structs and enums".into();
+            let span = type_name.span();
+            return Err(PreflectMacroError::new(message, span));
+        }
+    };
+
+    let checked_methods = if cfg!(feature = "checked-drop") {
+        checked_methods
     } else {
-        let span = Span2::call_site();
-        let message = "'PartialDrop' can only be implemented for structs".to_string();
-        let error = syn::Error::new(span, message);
-        Err(error)
-    }
+        vec![]
+    };
+
+    let name_markers = impl_field_name_markers(
+        &parse_quote!(::preflect::drop::HasDroppableField),
+        "droppable",
+        type_name,
+        &tagged.generics,
+        &accepted_names,
+    );
+    let mut token_stream = impl_partial_drop(
+        type_name,
+        &tagged.generics,
+        field_drops,
+        ignored_drops,
+        accepted_names,
+        checked_methods,
+    )
+    .into_token_stream();
+    name_markers.to_tokens(&mut token_stream);
+
+    Ok(token_stream)
 }
 
-fn impl_partial_drop_for_struct(type_name: &Ident, tagged_struct: &DataStruct) -> ItemImpl {
-    let field_checks = build_field_checks(tagged_struct);
+fn impl_partial_drop(
+    type_name: &Ident,
+    generics: &Generics,
+    field_drops: Expr,
+    ignored_drops: Expr,
+    accepted_names: Vec<LitStr>,
+    checked_methods: Vec<ImplItemMethod>,
+) -> ItemImpl {
+    // In checked mode, the names are validated before anything is dropped.
+    let name_check: Option<Expr> = if checked_methods.is_empty() {
+        None
+    } else {
+        Some(parse_quote!(::preflect::drop::check_field_names(
+            self,
+            field_names
+        )))
+    };
+    let name_check = name_check.iter();
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
     parse_quote! {
-        impl ::preflect::drop::PartialDrop for #type_name {
+        unsafe impl #impl_generics ::preflect::drop::PartialDrop for #type_name #ty_generics #where_clause {
+            const ACCEPTED_NAMES: &'static [&'static str] = &[#(#accepted_names),*];
+
             unsafe fn drop_all_fields_except(&mut self, field_names: &[&'static str]) {
+                #(#name_check;)*
+                #field_drops
+            }
+
+            unsafe fn drop_ignored_fields(&mut self) {
+                #ignored_drops
+            }
+
+            #(#checked_methods)*
+        }
+    }
+}
+
+/// Gets the fields which are dropped by name, skipping those which are
+/// ignored, or only the ignored fields if `ignored` is set. `make_ref` builds
+/// the reference to a field from its member and position.
+fn get_drop_fields(
+    fields: &Fields,
+    ignored: bool,
+    make_ref: impl Fn(&Member, usize) -> Expr,
+) -> Result<Vec<DropField<'_>>, GetHelperAttrError> {
+    let mut drop_fields = vec![];
+    for (index, field) in fields.iter().enumerate() {
+        let attr = get_preflect_attr(field)?;
+        if attr.ignore() != ignored {
+            continue;
+        }
+
+        let field_name = get_field_name(field, index);
+        let alias_lits = attr
+            .aliases()
+            .map(|alias| LitStr::new(alias, field_name.span()));
+        let names = std::iter::once(field_name.clone())
+            .chain(alias_lits)
+            .collect();
+        let member = get_field_member(field, index);
+        let field_ref = make_ref(&member, index);
+        drop_fields.push(DropField {
+            field,
+            member,
+            names,
+            field_ref,
+        });
+    }
+
+    Ok(drop_fields)
+}
+
+fn get_drop_variants(tagged_enum: &DataEnum) -> Result<Vec<DropVariant<'_>>, GetHelperAttrError> {
+    let mut variants = vec![];
+    for variant in tagged_enum.variants.iter() {
+        check_unique_aliases(&variant.fields)?;
+        let make_ref = |_: &Member, index| {
+            let field_binding = format_ident!("field_{}", index);
+            parse_quote!(#field_binding)
+        };
+        variants.push(DropVariant {
+            ident: &variant.ident,
+            fields: get_drop_fields(&variant.fields, false, make_ref)?,
+            ignored: get_drop_fields(&variant.fields, true, make_ref)?,
+        });
+    }
+
+    Ok(variants)
+}
+
+fn build_struct_drops(fields: &[DropField]) -> Expr {
+    let field_checks = fields.iter().map(build_field_check);
+    parse_quote!({ #(#field_checks)* })
+}
+
+/// Drops the ignored fields of a struct unconditionally.
+fn build_struct_ignored_drops(ignored: &[DropField]) -> Expr {
+    let ignored_drops = ignored.iter().map(build_field_drop);
+    parse_quote!({ #(#ignored_drops)* })
+}
+
+/// Builds a match which drops the fields of the active variant.
+fn build_enum_drops(variants: &[DropVariant]) -> Expr {
+    let variant_arms = variants.iter().map(|variant| {
+        let pattern = make_variant_pattern(variant.ident, &variant.fields);
+        let field_checks = variant.fields.iter().map(build_field_check);
+        let arm: Arm = parse_quote! {
+            #pattern => {
                 #(#field_checks)*
             }
+        };
+
+        arm
+    });
+
+    let match_target = make_match_target(variants);
+    parse_quote! {
+        match #match_target {
+            #(#variant_arms,)*
         }
     }
 }
 
-fn build_field_checks(tagged_struct: &DataStruct) -> impl Iterator<Item = syn::ExprIf> + '_ {
-    tagged_struct.fields.iter().map(build_field_check)
+/// Builds a match which drops the ignored fields of the active variant.
+fn build_enum_ignored_drops(variants: &[DropVariant]) -> Expr {
+    let variant_arms = variants.iter().map(|variant| {
+        let pattern = make_variant_pattern(variant.ident, &variant.ignored);
+        let ignored_drops = variant.ignored.iter().map(build_field_drop);
+        let arm: Arm = parse_quote! {
+            #pattern => {
+                #(#ignored_drops)*
+            }
+        };
+
+        arm
+    });
+
+    let match_target = make_match_target(variants);
+    parse_quote! {
+        match #match_target {
+            #(#variant_arms,)*
+        }
+    }
+}
+
+/// Builds the pattern which binds the given fields of a variant.
+fn make_variant_pattern(variant_ident: &Ident, fields: &[DropField]) -> TokenStream {
+    let field_members = fields.iter().map(|field| &field.member);
+    let field_bindings = fields.iter().map(|field| &field.field_ref);
+    quote!(Self::#variant_ident { #(#field_members: #field_bindings,)* .. })
+}
+
+/// An enum without variants can only be matched by dereferencing it.
+fn make_match_target(variants: &[DropVariant]) -> Expr {
+    if variants.is_empty() {
+        parse_quote!(*self)
+    } else {
+        parse_quote!(self)
+    }
 }
 
-fn build_field_check(field: &Field) -> syn::ExprIf {
-    let field_ident = get_ident_from_field(field);
-    let field_lit = get_field_ident_literal(field);
-    let field_ty = &field.ty;
+/// Builds the check which drops a field unless it is preserved by one of its
+/// names. Ignored fields are never dropped.
+fn build_field_check(field: &DropField) -> ExprIf {
+    let names = &field.names;
+    let field_ty = &field.field.ty;
+    let field_ref = &field.field_ref;
     parse_quote! {
-        if !field_names.contains(&#field_lit) {
-            let #field_ident = &mut self.#field_ident;
+        if #(!field_names.contains(&#names))&&* {
+            let field: *mut #field_ty = #field_ref;
             if ::core::mem::needs_drop::<#field_ty>() {
-                ::core::ptr::drop_in_place(#field_ident);
+                ::core::ptr::drop_in_place(field);
             }
         }
     }
 }
 
-fn get_ident_from_field(field: &Field) -> Ident {
-    field.ident.as_ref().unwrap().clone()
+/// Builds the statement which drops a field in place.
+fn build_field_drop(field: &DropField) -> TokenStream {
+    let field_ty = &field.field.ty;
+    let field_ref = &field.field_ref;
+    quote! {
+        {
+            let field: *mut #field_ty = #field_ref;
+            ::core::ptr::drop_in_place(field);
+        }
+    }
+}
+
+/// Builds the methods used by the checked mode for a struct.
+fn make_struct_checked_methods(fields: &[DropField]) -> Vec<ImplItemMethod> {
+    let field_names = make_field_names(fields);
+    let index_match = make_index_match(fields);
+    let drop_match = make_drop_match(fields);
+    let field_match = make_field_mut_match(fields);
+    make_checked_methods(field_names, index_match, drop_match, field_match)
 }
 
-fn get_field_ident_literal(field: &Field) -> LitStr {
-    let field_ident = field.ident.as_ref().unwrap();
-    LitStr::new(&field_ident.to_string(), field_ident.span())
+/// Builds the methods used by the checked mode for an enum. Each method
+/// refers to the fields of the active variant.
+fn make_enum_checked_methods(variants: &[DropVariant]) -> Vec<ImplItemMethod> {
+    let match_target = make_match_target(variants);
+    let variant_idents = variants
+        .iter()
+        .map(|variant| variant.ident)
+        .collect::<Vec<_>>();
+    let field_names = variants
+        .iter()
+        .map(|variant| make_field_names(&variant.fields));
+    let index_matches = variants
+        .iter()
+        .map(|variant| make_index_match(&variant.fields));
+    let variant_patterns = variants
+        .iter()
+        .map(|variant| make_variant_pattern(variant.ident, &variant.fields))
+        .collect::<Vec<_>>();
+    let drop_matches = variants
+        .iter()
+        .map(|variant| make_drop_match(&variant.fields))
+        .collect::<Vec<_>>();
+    let field_matches = variants
+        .iter()
+        .map(|variant| make_field_mut_match(&variant.fields));
+
+    make_checked_methods(
+        parse_quote! {
+            match *self {
+                #(Self::#variant_idents { .. } => #field_names,)*
+            }
+        },
+        parse_quote! {
+            match *self {
+                #(Self::#variant_idents { .. } => #index_matches,)*
+            }
+        },
+        parse_quote! {
+            match #match_target {
+                #(#variant_patterns => #drop_matches,)*
+            }
+        },
+        parse_quote! {
+            match #match_target {
+                #(#variant_patterns => #field_matches,)*
+            }
+        },
+    )
+}
+
+fn make_checked_methods(
+    field_names: Expr,
+    index_match: Expr,
+    drop_match: Expr,
+    field_match: Expr,
+) -> Vec<ImplItemMethod> {
+    vec![
+        parse_quote! {
+            fn droppable_field_names(&self) -> &'static [&'static str] {
+                #field_names
+            }
+        },
+        parse_quote! {
+            fn droppable_field_index(&self, name: &str) -> core::option::Option<usize> {
+                #index_match
+            }
+        },
+        parse_quote! {
+            unsafe fn drop_field(&mut self, index: usize) {
+                #drop_match
+            }
+        },
+        parse_quote! {
+            fn droppable_field_mut(&mut self, index: usize) -> core::option::Option<&mut dyn core::any::Any>
+            where
+                Self: 'static,
+            {
+                #field_match
+            }
+        },
+    ]
+}
+
+fn make_field_names(fields: &[DropField]) -> Expr {
+    let field_names = fields.iter().map(|field| &field.names[0]);
+    parse_quote!(&[#(#field_names),*])
+}
+
+fn make_index_match(fields: &[DropField]) -> Expr {
+    let field_patterns = fields.iter().map(|field| {
+        let names = &field.names;
+        quote!(#(#names)|*)
+    });
+    let positions = 0..fields.len();
+    parse_quote! {
+        match name {
+            #(#field_patterns => core::option::Option::Some(#positions),)*
+            _ => core::option::Option::None
+        }
+    }
+}
+
+fn make_drop_match(fields: &[DropField]) -> Expr {
+    let positions = 0..fields.len();
+    let field_tys = fields.iter().map(|field| &field.field.ty);
+    let field_refs = fields.iter().map(|field| &field.field_ref);
+    parse_quote! {
+        match index {
+            #(#positions => {
+                let field: *mut #field_tys = #field_refs;
+                ::core::ptr::drop_in_place(field);
+            })*
+            _ => {}
+        }
+    }
+}
+
+/// Builds the match which finds a droppable field by its position, so that
+/// `CheckedPartialDrop` can move it out.
+fn make_field_mut_match(fields: &[DropField]) -> Expr {
+    let positions = 0..fields.len();
+    let field_refs = fields.iter().map(|field| &field.field_ref);
+    parse_quote! {
+        match index {
+            #(#positions => core::option::Option::Some(#field_refs as &mut dyn core::any::Any),)*
+            _ => core::option::Option::None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use syn::FieldsNamed;
+
+    #[test]
+    fn build_field_check_test() {
+        let fields: FieldsNamed = parse_quote!({ #[preflect(alias = ["url"])] path: String });
+        let fields = Fields::Named(fields);
+        let drop_fields =
+            get_drop_fields(&fields, false, |member, _| parse_quote!(&mut self.#member)).unwrap();
+
+        let actual = build_field_check(&drop_fields[0]);
+        let expected: ExprIf = parse_quote! {
+            if !field_names.contains(&"path") && !field_names.contains(&"url") {
+                let field: *mut String = &mut self.path;
+                if ::core::mem::needs_drop::<String>() {
+                    ::core::ptr::drop_in_place(field);
+                }
+            }
+        };
+
+        assert_eq!(actual, expected)
+    }
+
+    #[test]
+    fn get_drop_fields_ignore_test() {
+        let fields: FieldsNamed = parse_quote!({ #[preflect(ignore)] path: String, id: u32 });
+        let fields = Fields::Named(fields);
+
+        let actual =
+            get_drop_fields(&fields, false, |member, _| parse_quote!(&mut self.#member)).unwrap();
+
+        assert_eq!(1, actual.len());
+        assert_eq!("id", actual[0].names[0].value());
+
+        let actual =
+            get_drop_fields(&fields, true, |member, _| parse_quote!(&mut self.#member)).unwrap();
+
+        assert_eq!(1, actual.len());
+        assert_eq!("path", actual[0].names[0].value())
+    }
+
+    #[test]
+    fn build_enum_drops_test() {
+        let tagged_enum = make_data_enum();
+        let variants = get_drop_variants(&tagged_enum).unwrap();
+
+        let actual = build_enum_drops(&variants);
+        let expected: Expr = parse_quote! {
+            match self {
+                Self::Text { 0: field_0, .. } => {
+                    if !field_names.contains(&"0") {
+                        let field: *mut String = field_0;
+                        if ::core::mem::needs_drop::<String>() {
+                            ::core::ptr::drop_in_place(field);
+                        }
+                    }
+                },
+                Self::Empty { .. } => {},
+            }
+        };
+
+        assert_eq!(actual, expected)
+    }
+
+    #[test]
+    fn build_enum_ignored_drops_test() {
+        let tagged_enum = make_data_enum();
+        let variants = get_drop_variants(&tagged_enum).unwrap();
+
+        let actual = build_enum_ignored_drops(&variants);
+        let expected: Expr = parse_quote! {
+            match self {
+                Self::Text { 1: field_1, .. } => {
+                    {
+                        let field: *mut u32 = field_1;
+                        ::core::ptr::drop_in_place(field);
+                    }
+                },
+                Self::Empty { .. } => {},
+            }
+        };
+
+        assert_eq!(actual, expected)
+    }
+
+    #[test]
+    fn make_enum_checked_methods_test() {
+        let tagged_enum = make_data_enum();
+        let variants = get_drop_variants(&tagged_enum).unwrap();
+
+        let actual = make_enum_checked_methods(&variants);
+        let expected: Vec<ImplItemMethod> = vec![
+            parse_quote! {
+                fn droppable_field_names(&self) -> &'static [&'static str] {
+                    match *self {
+                        Self::Text { .. } => &["0"],
+                        Self::Empty { .. } => &[],
+                    }
+                }
+            },
+            parse_quote! {
+                fn droppable_field_index(&self, name: &str) -> core::option::Option<usize> {
+                    match *self {
+                        Self::Text { .. } => match name {
+                            "0" => core::option::Option::Some(0usize),
+                            _ => core::option::Option::None
+                        },
+                        Self::Empty { .. } => match name {
+                            _ => core::option::Option::None
+                        },
+                    }
+                }
+            },
+            parse_quote! {
+                unsafe fn drop_field(&mut self, index: usize) {
+                    match self {
+                        Self::Text { 0: field_0, .. } => match index {
+                            0usize => {
+                                let field: *mut String = field_0;
+                                ::core::ptr::drop_in_place(field);
+                            }
+                            _ => {}
+                        },
+                        Self::Empty { .. } => match index {
+                            _ => {}
+                        },
+                    }
+                }
+            },
+            parse_quote! {
+                fn droppable_field_mut(&mut self, index: usize) -> core::option::Option<&mut dyn core::any::Any>
+                where
+                    Self: 'static,
+                {
+                    match self {
+                        Self::Text { 0: field_0, .. } => match index {
+                            0usize => core::option::Option::Some(field_0 as &mut dyn core::any::Any),
+                            _ => core::option::Option::None
+                        },
+                        Self::Empty { .. } => match index {
+                            _ => core::option::Option::None
+                        },
+                    }
+                }
+            },
+        ];
+
+        assert_eq!(actual, expected)
+    }
+
+    fn make_data_enum() -> DataEnum {
+        let derive_input: DeriveInput = parse_quote! {
+            enum Message {
+                Text(String, #[preflect(ignore)] u32),
+                Empty,
+            }
+        };
+
+        match derive_input.data {
+            Data::Enum(tagged_enum) => tagged_enum,
+            _ => unreachable!(),
+        }
+    }
 }
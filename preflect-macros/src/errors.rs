@@ -25,28 +25,38 @@ impl From<GetHelperAttrError> for PreflectMacroError {
         let span = match src {
             GetHelperAttrError::MultipleAttributes { span } => span,
             GetHelperAttrError::ParseError { parse_error } => parse_error.span(),
+            GetHelperAttrError::MissingFeature { span, .. } => span,
         };
 
         PreflectMacroError::new(message, span)
     }
 }
 
-impl Into<TokenStream> for PreflectMacroError {
-    fn into(self) -> TokenStream {
-        Into::<TokenStream2>::into(self).into()
+impl From<PreflectMacroError> for TokenStream {
+    fn from(error: PreflectMacroError) -> Self {
+        TokenStream2::from(error).into()
     }
 }
 
-impl Into<TokenStream2> for PreflectMacroError {
-    fn into(self) -> TokenStream2 {
-        SynError::new(self.span, self.message).to_compile_error()
+impl From<PreflectMacroError> for TokenStream2 {
+    fn from(error: PreflectMacroError) -> Self {
+        SynError::new(error.span, error.message).to_compile_error()
     }
 }
 
 #[derive(Debug)]
 pub enum GetHelperAttrError {
-    MultipleAttributes { span: Span },
-    ParseError { parse_error: syn::parse::Error },
+    MultipleAttributes {
+        span: Span,
+    },
+    ParseError {
+        parse_error: syn::parse::Error,
+    },
+    MissingFeature {
+        attr: &'static str,
+        feature: &'static str,
+        span: Span,
+    },
 }
 
 impl Display for GetHelperAttrError {
@@ -61,6 +71,11 @@ impl Display for GetHelperAttrError {
                 "There was a problem parsing the attribute body: {}",
                 parse_error
             ),
+            GetHelperAttrError::MissingFeature { attr, feature, .. } => write!(
+                f,
+                "The attribute #[preflect({})] requires the '{}' feature of preflect.",
+                attr, feature
+            ),
         }
     }
 }
@@ -71,10 +86,10 @@ impl From<ParseError> for GetHelperAttrError {
     }
 }
 
-impl Into<syn::Error> for GetHelperAttrError {
-    fn into(self) -> syn::Error {
+impl From<GetHelperAttrError> for syn::Error {
+    fn from(error: GetHelperAttrError) -> Self {
         let span = proc_macro2::Span::call_site();
-        let message = self.to_string();
+        let message = error.to_string();
         syn::Error::new(span, message)
     }
 }
@@ -0,0 +1,139 @@
+use crate::field_utils::get_field_member;
+use crate::field_utils::get_field_name;
+use syn::parse_quote;
+use syn::Expr;
+use syn::Field;
+use syn::LitStr;
+use syn::Path;
+use syn::Visibility;
+
+/// Builds an expression that constructs the `FieldInfo` of a field.
+pub fn make_field_info(field: &Field, index: usize, redacted: bool) -> Expr {
+    let field_ty = &field.ty;
+    let field_member = get_field_member(field, index);
+    let field_name = get_field_name(field, index);
+    let visibility = make_visibility(&field.vis);
+    let field_info = parse_quote! {
+        ::preflect::fields::FieldInfo::new::<#field_ty>(
+            #field_name,
+            #index,
+            #visibility,
+            || ::preflect::memoffset::offset_of!(Self, #field_member)
+        )
+    };
+
+    redact_field_info(field_info, redacted)
+}
+
+/// Builds an expression that constructs the `FieldInfo` of a field of an enum
+/// variant.
+pub fn make_variant_field_info(
+    variant_name: &LitStr,
+    field: &Field,
+    index: usize,
+    redacted: bool,
+) -> Expr {
+    let field_ty = &field.ty;
+    let field_name = get_field_name(field, index);
+    let field_info = parse_quote! {
+        ::preflect::fields::FieldInfo::new_variant_field::<#field_ty>(#variant_name, #field_name, #index)
+    };
+
+    redact_field_info(field_info, redacted)
+}
+
+/// Marks the field described by `field_info` as redacted if it was declared
+/// with `#[preflect(redact)]`.
+fn redact_field_info(field_info: Expr, redacted: bool) -> Expr {
+    if redacted {
+        parse_quote!(#field_info.redact())
+    } else {
+        field_info
+    }
+}
+
+fn make_visibility(vis: &Visibility) -> Path {
+    match vis {
+        Visibility::Public(_) => parse_quote!(::preflect::fields::Visibility::Public),
+        Visibility::Crate(_) => parse_quote!(::preflect::fields::Visibility::Crate),
+        Visibility::Restricted(restricted) if restricted.path.is_ident("crate") => {
+            parse_quote!(::preflect::fields::Visibility::Crate)
+        }
+        Visibility::Restricted(_) => parse_quote!(::preflect::fields::Visibility::Restricted),
+        Visibility::Inherited => parse_quote!(::preflect::fields::Visibility::Inherited),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn make_field_info_test() {
+        let field = Field {
+            attrs: vec![],
+            colon_token: Some(syn::token::Colon::default()),
+            ident: parse_quote!(id),
+            ty: parse_quote!(u32),
+            vis: parse_quote!(pub),
+        };
+
+        let actual = make_field_info(&field, 0, false);
+        let expected: Expr = parse_quote! {
+            ::preflect::fields::FieldInfo::new::<u32>(
+                "id",
+                0usize,
+                ::preflect::fields::Visibility::Public,
+                || ::preflect::memoffset::offset_of!(Self, id)
+            )
+        };
+
+        assert_eq!(actual, expected)
+    }
+
+    #[test]
+    fn make_variant_field_info_test() {
+        let variant_name = parse_quote!("Circle");
+        let field = Field {
+            attrs: vec![],
+            colon_token: None,
+            ident: None,
+            ty: parse_quote!(f32),
+            vis: Visibility::Inherited,
+        };
+
+        let actual = make_variant_field_info(&variant_name, &field, 0, true);
+        let expected: Expr = parse_quote! {
+            ::preflect::fields::FieldInfo::new_variant_field::<f32>("Circle", "0", 0usize).redact()
+        };
+
+        assert_eq!(actual, expected)
+    }
+
+    #[test]
+    fn make_visibility_test() {
+        let cases: [(Visibility, Path); 4] = [
+            (
+                parse_quote!(pub),
+                parse_quote!(::preflect::fields::Visibility::Public),
+            ),
+            (
+                parse_quote!(pub(crate)),
+                parse_quote!(::preflect::fields::Visibility::Crate),
+            ),
+            (
+                parse_quote!(pub(super)),
+                parse_quote!(::preflect::fields::Visibility::Restricted),
+            ),
+            (
+                Visibility::Inherited,
+                parse_quote!(::preflect::fields::Visibility::Inherited),
+            ),
+        ];
+
+        for (vis, expected) in cases.iter() {
+            assert_eq!(&make_visibility(vis), expected);
+        }
+    }
+}
@@ -0,0 +1,186 @@
+use syn::ext::IdentExt;
+use syn::parse::Parse;
+use syn::parse::ParseStream;
+use syn::parse::Result as ParseResult;
+use syn::parse_quote;
+use syn::Generics;
+use syn::Ident;
+use syn::ItemConst;
+use syn::LitInt;
+use syn::LitStr;
+use syn::Path;
+use syn::Type;
+
+/// The name of a field as written in the `field!` macro. Fields can be named
+/// with an identifier (`field!(name)`), a string (`field!("name")`) or the
+/// position of a tuple struct field (`field!(0)`).
+pub struct FieldNameInput {
+    name: String,
+}
+
+impl FieldNameInput {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl Parse for FieldNameInput {
+    fn parse(input: ParseStream) -> ParseResult<Self> {
+        let lookahead = input.lookahead1();
+        let name = if lookahead.peek(LitStr) {
+            input.parse::<LitStr>()?.value()
+        } else if lookahead.peek(LitInt) {
+            input.parse::<LitInt>()?.base10_digits().into()
+        } else if lookahead.peek(Ident) {
+            input.parse::<Ident>()?.unraw().to_string()
+        } else {
+            return Err(lookahead.error());
+        };
+
+        Ok(Self { name })
+    }
+}
+
+/// Builds the type which encodes a field name as a list of characters.
+pub fn make_field_name_type(name: &str) -> Type {
+    name.chars().rev().fold(
+        parse_quote!(::preflect::fields::NameEnd),
+        |rest, c| parse_quote!(::preflect::fields::NameChar<#c, #rest>),
+    )
+}
+
+/// Implements a marker trait (e.g. `HasFieldName`) for each of the names a
+/// type accepts, with the name encoded as a type. This lets the `field!` and
+/// `partial_drop!` macros check names while the code is type checked.
+///
+/// The names are accepted through a trait which is generated for the type, so
+/// that an unknown name is reported along with the names the type accepts.
+/// `kind` describes the fields in that report (e.g. "reflected").
+pub fn impl_field_name_markers(
+    marker: &Path,
+    kind: &str,
+    ty_ident: &Ident,
+    generics: &Generics,
+    names: &[LitStr],
+) -> ItemConst {
+    let message = format!("`{}` has no {} field with this name", ty_ident, kind);
+    let note = if names.is_empty() {
+        format!("`{}` has no {} fields", ty_ident, kind)
+    } else {
+        let names = names
+            .iter()
+            .map(|name| format!("`{}`", name.value()))
+            .collect::<Vec<_>>();
+        format!("the accepted names are {}", names.join(", "))
+    };
+    let name_tys = names.iter().map(|name| make_field_name_type(&name.value()));
+
+    let mut marker_generics = generics.clone();
+    marker_generics.params.push(parse_quote! {
+        PreflectName: ::preflect::fields::FieldName + PreflectAcceptedName
+    });
+    let (impl_generics, _, _) = marker_generics.split_for_impl();
+    let (_, ty_generics, where_clause) = generics.split_for_impl();
+    parse_quote! {
+        const _: () = {
+            #[diagnostic::on_unimplemented(
+                message = #message,
+                label = "unknown field name",
+                note = #note
+            )]
+            pub trait PreflectAcceptedName {}
+
+            #(impl PreflectAcceptedName for #name_tys {})*
+
+            impl #impl_generics #marker<PreflectName> for #ty_ident #ty_generics #where_clause {}
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn make_field_name_type_test() {
+        let actual = make_field_name_type("id");
+        let expected: Type = parse_quote! {
+            ::preflect::fields::NameChar<'i', ::preflect::fields::NameChar<'d', ::preflect::fields::NameEnd>>
+        };
+
+        assert_eq!(actual, expected)
+    }
+
+    #[test]
+    fn impl_field_name_markers_test() {
+        let ty_ident: Ident = parse_quote!(Wrapper);
+        let generics: Generics = parse_quote!(<T>);
+        let names: [LitStr; 2] = [parse_quote!("id"), parse_quote!("key")];
+
+        let actual = impl_field_name_markers(
+            &parse_quote!(::preflect::fields::HasFieldName),
+            "reflected",
+            &ty_ident,
+            &generics,
+            &names,
+        );
+        let expected: ItemConst = parse_quote! {
+            const _: () = {
+                #[diagnostic::on_unimplemented(
+                    message = "`Wrapper` has no reflected field with this name",
+                    label = "unknown field name",
+                    note = "the accepted names are `id`, `key`"
+                )]
+                pub trait PreflectAcceptedName {}
+
+                impl PreflectAcceptedName for ::preflect::fields::NameChar<'i', ::preflect::fields::NameChar<'d', ::preflect::fields::NameEnd>> {}
+                impl PreflectAcceptedName for ::preflect::fields::NameChar<'k', ::preflect::fields::NameChar<'e', ::preflect::fields::NameChar<'y', ::preflect::fields::NameEnd>>> {}
+
+                impl<T, PreflectName: ::preflect::fields::FieldName + PreflectAcceptedName> ::preflect::fields::HasFieldName<PreflectName> for Wrapper<T> {}
+            };
+        };
+
+        assert_eq!(expected, actual)
+    }
+
+    #[test]
+    fn impl_field_name_markers_without_names_test() {
+        let ty_ident: Ident = parse_quote!(Unit);
+
+        let actual = impl_field_name_markers(
+            &parse_quote!(::preflect::drop::HasDroppableField),
+            "droppable",
+            &ty_ident,
+            &Generics::default(),
+            &[],
+        );
+        let expected: ItemConst = parse_quote! {
+            const _: () = {
+                #[diagnostic::on_unimplemented(
+                    message = "`Unit` has no droppable field with this name",
+                    label = "unknown field name",
+                    note = "`Unit` has no droppable fields"
+                )]
+                pub trait PreflectAcceptedName {}
+
+                impl<PreflectName: ::preflect::fields::FieldName + PreflectAcceptedName> ::preflect::drop::HasDroppableField<PreflectName> for Unit {}
+            };
+        };
+
+        assert_eq!(expected, actual)
+    }
+
+    #[test]
+    fn field_name_input_test() {
+        let names = [
+            syn::parse_str::<FieldNameInput>("name").unwrap(),
+            syn::parse_str::<FieldNameInput>("r#type").unwrap(),
+            syn::parse_str::<FieldNameInput>("\"name\"").unwrap(),
+            syn::parse_str::<FieldNameInput>("0").unwrap(),
+        ];
+
+        let actual = names.iter().map(|name| name.name()).collect::<Vec<_>>();
+        assert_eq!(vec!["name", "type", "name", "0"], actual)
+    }
+}
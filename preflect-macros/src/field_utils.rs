@@ -0,0 +1,338 @@
+use crate::attr_utils::get_preflect_attr;
+use crate::attr_utils::HelperAttr;
+use crate::errors::GetHelperAttrError;
+use quote::format_ident;
+use syn::ext::IdentExt;
+use syn::parse::Error as ParseError;
+use syn::parse_quote;
+use syn::spanned::Spanned;
+use syn::Expr;
+use syn::Field;
+use syn::Ident;
+use syn::ImplItemMethod;
+use syn::Index;
+use syn::LitStr;
+use syn::Member;
+use syn::Type;
+
+/// Gets the member used to access a field. Fields of tuple structs are
+/// accessed by their position.
+pub fn get_field_member(field: &Field, index: usize) -> Member {
+    match &field.ident {
+        Some(field_ident) => Member::Named(field_ident.clone()),
+        None => Member::Unnamed(Index {
+            index: index as u32,
+            span: field.ty.span(),
+        }),
+    }
+}
+
+/// Gets the name a field is reflected as. Fields of tuple structs are named by
+/// their position (e.g. `"0"`), and raw identifiers are named without their
+/// `r#` prefix (e.g. `"type"` for `r#type`).
+pub fn get_field_name(field: &Field, index: usize) -> LitStr {
+    match &field.ident {
+        Some(field_ident) => LitStr::new(&field_ident.unraw().to_string(), field_ident.span()),
+        None => LitStr::new(&index.to_string(), field.ty.span()),
+    }
+}
+
+/// Ensures that no alias repeats the name or an alias of another reflected
+/// field, since only the first of the fields could be accessed by it.
+pub fn check_unique_aliases<'a>(
+    fields: impl IntoIterator<Item = &'a Field>,
+) -> Result<(), GetHelperAttrError> {
+    let mut names = Vec::<String>::new();
+    let mut aliases = Vec::<LitStr>::new();
+    for (index, field) in fields.into_iter().enumerate() {
+        let attr = get_preflect_attr(field)?;
+        if !attr.ignore() {
+            names.push(get_field_name(field, index).value());
+            aliases.extend(attr.alias_lits());
+        }
+    }
+
+    for alias in aliases {
+        if names.contains(&alias.value()) {
+            let message = format!(
+                "The alias '{}' is already the name or an alias of a field.",
+                alias.value()
+            );
+            return Err(ParseError::new(alias.span(), message).into());
+        }
+
+        names.push(alias.value());
+    }
+
+    Ok(())
+}
+
+/// Removes the names which were already listed (e.g. a field name shared by
+/// several variants of an enum), keeping the first occurrence of each.
+pub fn get_unique_names<'a>(names: impl IntoIterator<Item = &'a LitStr>) -> Vec<LitStr> {
+    let mut unique_names = Vec::<LitStr>::new();
+    for name in names {
+        if !unique_names
+            .iter()
+            .any(|listed| listed.value() == name.value())
+        {
+            unique_names.push(name.clone());
+        }
+    }
+
+    unique_names
+}
+
+/// Builds the expression which views the elements of a collection field of
+/// type `ty` as reflected values whenever they can be reflected. The cast is
+/// selected through autoref specialization, so it falls back to treating the
+/// elements as opaque values.
+pub fn make_element_cast(ty: &Type) -> Expr {
+    parse_quote! {
+        {
+            use ::preflect::collections::OpaqueElements as _;
+            use ::preflect::collections::ReflectElements as _;
+            (&&::preflect::collections::Elements::<#ty>::new()).cast()
+        }
+    }
+}
+
+/// Makes an expression for the `TargetCast` which views the target of a
+/// pointer of type `ty` as a reflected value (or as a pointer to one) whenever
+/// it can be. Like [`make_element_cast`], it falls back to treating the target
+/// as an opaque value.
+pub fn make_target_cast(ty: &Type) -> Expr {
+    parse_quote! {
+        {
+            use ::preflect::pointers::OpaqueTarget as _;
+            use ::preflect::pointers::PointerTarget as _;
+            use ::preflect::pointers::ReflectPointerTarget as _;
+            use ::preflect::pointers::ReflectTarget as _;
+            (&&&&::preflect::pointers::Target::<<#ty as ::preflect::pointers::AsPointer>::Target>::new()).cast()
+        }
+    }
+}
+
+/// A field which wraps other values and is accessed through its own methods
+/// of `BaseHasFields`, as selected by `#[preflect(collection)]` or
+/// `#[preflect(pointer)]`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Wrapper {
+    Collection,
+    Pointer,
+}
+
+impl Wrapper {
+    /// Gets the wrapper selected by the helper attribute of a field.
+    pub fn of(attr: &HelperAttr) -> Option<Self> {
+        if attr.collection() {
+            Some(Wrapper::Collection)
+        } else if attr.pointer() {
+            Some(Wrapper::Pointer)
+        } else {
+            None
+        }
+    }
+
+    /// The constructor of the `FieldAccessError` for the fields which are not
+    /// wrapped this way.
+    pub fn not_wrapper(self) -> Ident {
+        format_ident!("not_{}", self.name())
+    }
+
+    /// Wraps an immutable reference to a field of type `ty`.
+    pub fn make_ref(self, ty: &Type, field_ref: Expr) -> Expr {
+        let module = self.module();
+        let ref_ident = format_ident!("{}Ref", self.type_prefix());
+        let cast = self.make_cast(ty);
+        parse_quote!(::preflect::#module::#ref_ident::new(#field_ref, #cast))
+    }
+
+    /// Wraps a mutable reference to a field of type `ty`.
+    pub fn make_mut(self, ty: &Type, field_mut: Expr) -> Expr {
+        let module = self.module();
+        let mut_ident = format_ident!("{}Mut", self.type_prefix());
+        let cast = self.make_cast(ty);
+        parse_quote!(::preflect::#module::#mut_ident::new(#field_mut, #cast))
+    }
+
+    /// Builds the immutable and mutable accessor methods from the expressions
+    /// which match the name of the field.
+    pub fn make_methods(self, ref_match: Expr, mut_match: Expr) -> Vec<ImplItemMethod> {
+        let module = self.module();
+        let ref_ident = format_ident!("{}Ref", self.type_prefix());
+        let mut_ident = format_ident!("{}Mut", self.type_prefix());
+        let get_raw = format_ident!("get_{}_raw", self.name());
+        let get_mut_raw = format_ident!("get_{}_mut_raw", self.name());
+        vec![
+            parse_quote! {
+                fn #get_raw<'s, 'n>(&'s self, name: &'n str) -> preflect::fields::FieldAccessResult<'n, ::preflect::#module::#ref_ident<'s>> {
+                    #ref_match
+                }
+            },
+            parse_quote! {
+                fn #get_mut_raw<'s, 'n>(&'s mut self, name: &'n str) -> preflect::fields::FieldAccessResult<'n, ::preflect::#module::#mut_ident<'s>> {
+                    #mut_match
+                }
+            },
+        ]
+    }
+
+    fn make_cast(self, ty: &Type) -> Expr {
+        match self {
+            Wrapper::Collection => make_element_cast(ty),
+            Wrapper::Pointer => make_target_cast(ty),
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Wrapper::Collection => "collection",
+            Wrapper::Pointer => "pointer",
+        }
+    }
+
+    fn module(self) -> Ident {
+        format_ident!("{}s", self.name())
+    }
+
+    fn type_prefix(self) -> &'static str {
+        match self {
+            Wrapper::Collection => "Collection",
+            Wrapper::Pointer => "Pointer",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use syn::FieldsNamed;
+    use syn::FieldsUnnamed;
+
+    #[test]
+    fn get_field_member_test() {
+        let fields: FieldsUnnamed = parse_quote!((u32, String));
+        let field = &fields.unnamed[1];
+
+        let actual = get_field_member(field, 1);
+        let expected: Member = parse_quote!(1);
+
+        assert_eq!(actual, expected)
+    }
+
+    #[test]
+    fn get_field_name_test() {
+        let fields: FieldsUnnamed = parse_quote!((u32, String));
+        let field = &fields.unnamed[1];
+
+        let actual = get_field_name(field, 1);
+        let expected: LitStr = parse_quote!("1");
+
+        assert_eq!(actual, expected)
+    }
+
+    #[test]
+    fn get_field_name_raw_test() {
+        let fields: FieldsNamed = parse_quote!({ r#type: String });
+        let field = &fields.named[0];
+
+        let actual = get_field_name(field, 0);
+        let expected: LitStr = parse_quote!("type");
+
+        assert_eq!(actual, expected)
+    }
+
+    #[test]
+    fn make_element_cast_test() {
+        let ty: Type = parse_quote!(Vec<Item>);
+
+        let actual = make_element_cast(&ty);
+        let expected: Expr = parse_quote! {
+            {
+                use ::preflect::collections::OpaqueElements as _;
+                use ::preflect::collections::ReflectElements as _;
+                (&&::preflect::collections::Elements::<Vec<Item> >::new()).cast()
+            }
+        };
+
+        assert_eq!(actual, expected)
+    }
+
+    #[test]
+    fn make_target_cast_test() {
+        let ty: Type = parse_quote!(Rc<Config>);
+
+        let actual = make_target_cast(&ty);
+        let expected: Expr = parse_quote! {
+            {
+                use ::preflect::pointers::OpaqueTarget as _;
+                use ::preflect::pointers::PointerTarget as _;
+                use ::preflect::pointers::ReflectPointerTarget as _;
+                use ::preflect::pointers::ReflectTarget as _;
+                (&&&&::preflect::pointers::Target::<<Rc<Config> as ::preflect::pointers::AsPointer>::Target>::new()).cast()
+            }
+        };
+
+        assert_eq!(actual, expected)
+    }
+
+    #[test]
+    fn wrapper_make_ref_test() {
+        let ty: Type = parse_quote!(Box<Config>);
+
+        let actual = Wrapper::Pointer.make_ref(&ty, parse_quote!(&self.config));
+        let target_cast = make_target_cast(&ty);
+        let expected: Expr =
+            parse_quote!(::preflect::pointers::PointerRef::new(&self.config, #target_cast));
+
+        assert_eq!(actual, expected)
+    }
+
+    #[test]
+    fn check_unique_aliases_test() {
+        let fields: FieldsNamed = parse_quote!({
+            #[preflect(alias = "uid")]
+            id: u32,
+            #[preflect(alias = ["login", "id"])]
+            name: String,
+        });
+
+        let actual = check_unique_aliases(&fields.named).unwrap_err();
+
+        assert_eq!(
+            "There was a problem parsing the attribute body: The alias 'id' is already the name or an alias of a field.",
+            actual.to_string()
+        )
+    }
+
+    #[test]
+    fn check_unique_aliases_repeated_test() {
+        let fields: FieldsNamed = parse_quote!({
+            #[preflect(alias = "uid")]
+            id: u32,
+            #[preflect(ignore, alias = "name")]
+            cache: u32,
+            #[preflect(alias = ["uid"])]
+            user: String,
+        });
+
+        let actual = check_unique_aliases(&fields.named).unwrap_err();
+
+        assert_eq!(
+            "There was a problem parsing the attribute body: The alias 'uid' is already the name or an alias of a field.",
+            actual.to_string()
+        )
+    }
+
+    #[test]
+    fn get_unique_names_test() {
+        let names: Vec<LitStr> = vec![parse_quote!("a"), parse_quote!("b"), parse_quote!("a")];
+
+        let actual = get_unique_names(&names);
+        let expected: Vec<LitStr> = vec![parse_quote!("a"), parse_quote!("b")];
+
+        assert_eq!(actual, expected)
+    }
+}
@@ -0,0 +1,152 @@
+#![cfg(feature = "alloc")]
+
+use crate::attr_utils::get_preflect_attr;
+use crate::errors::GetHelperAttrError;
+use crate::errors::PreflectMacroError;
+use crate::field_utils::check_unique_aliases;
+use crate::field_utils::get_field_member;
+use crate::field_utils::get_field_name;
+use crate::generics_utils::add_static_bounds;
+use syn::ext::IdentExt;
+use syn::parse_quote;
+use syn::Arm;
+use syn::Data;
+use syn::DataEnum;
+use syn::DeriveInput;
+use syn::FieldValue;
+use syn::Generics;
+use syn::Ident;
+use syn::ItemImpl;
+use syn::LitStr;
+use syn::Type;
+use syn::Variant;
+
+pub fn from_variant_derive_impl(
+    derive_input: &DeriveInput,
+) -> Result<ItemImpl, PreflectMacroError> {
+    let enum_ident = &derive_input.ident;
+    if let Data::Enum(data_enum) = &derive_input.data {
+        impl_from_variant_for_data_enum(enum_ident, &derive_input.generics, data_enum)
+            .map_err(PreflectMacroError::from)
+    } else {
+        let message = "FromVariant can only be derived for enums".into();
+        let span = enum_ident.span();
+        Err(PreflectMacroError::new(message, span))
+    }
+}
+
+fn impl_from_variant_for_data_enum(
+    enum_ident: &Ident,
+    generics: &Generics,
+    data_enum: &DataEnum,
+) -> Result<ItemImpl, GetHelperAttrError> {
+    let mut reflected_tys = Vec::<&Type>::new();
+    let mut ignored_tys = Vec::<&Type>::new();
+    let mut variant_arms = Vec::<Arm>::new();
+    for variant in data_enum.variants.iter() {
+        check_unique_aliases(&variant.fields)?;
+        for field in variant.fields.iter() {
+            if get_preflect_attr(field)?.ignore() {
+                ignored_tys.push(&field.ty);
+            } else {
+                reflected_tys.push(&field.ty);
+            }
+        }
+
+        variant_arms.push(make_variant_arm(variant)?);
+    }
+
+    // Ignored fields are not provided so they must be created from their default.
+    let mut generics = add_static_bounds(generics, reflected_tys);
+    if !generics.params.is_empty() {
+        let where_clause = generics.make_where_clause();
+        for ignored_ty in ignored_tys {
+            where_clause
+                .predicates
+                .push(parse_quote!(#ignored_ty: core::default::Default));
+        }
+    }
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    Ok(parse_quote! {
+        impl #impl_generics ::preflect::fields::FromVariant for #enum_ident #ty_generics #where_clause {
+            fn from_variant(variant: &str, mut fields: ::preflect::fields::FieldMap) -> core::result::Result<Self, ::preflect::fields::FromVariantError<'_>> {
+                let value = match variant {
+                    #(#variant_arms,)*
+                    _ => return core::result::Result::Err(::preflect::fields::FieldAccessError::missing_variant(variant).into())
+                };
+
+                ::preflect::fields::ensure_fields_taken(fields)?;
+                core::result::Result::Ok(value)
+            }
+        }
+    })
+}
+
+fn make_variant_arm(variant: &Variant) -> Result<Arm, GetHelperAttrError> {
+    let variant_ident = &variant.ident;
+    let variant_name = LitStr::new(&variant_ident.unraw().to_string(), variant_ident.span());
+
+    let mut field_values = Vec::<FieldValue>::new();
+    for (index, field) in variant.fields.iter().enumerate() {
+        let attr = get_preflect_attr(field)?;
+        let field_member = get_field_member(field, index);
+        if attr.ignore() {
+            field_values.push(parse_quote!(#field_member: core::default::Default::default()));
+        } else {
+            let field_name = get_field_name(field, index);
+            let alias_lits = attr
+                .aliases()
+                .map(|alias| LitStr::new(alias, field_name.span()));
+            field_values.push(parse_quote! {
+                #field_member: ::preflect::fields::remove_field(&mut fields, &[#field_name #(, #alias_lits)*])?
+            });
+        }
+    }
+
+    Ok(parse_quote! {
+        #variant_name => Self::#variant_ident { #(#field_values),* }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn make_variant_arm_test() {
+        let variant: Variant = parse_quote! {
+            Connected {
+                #[preflect(alias = ["addr"])]
+                address: String,
+                #[preflect(ignore)]
+                retries: u32,
+            }
+        };
+
+        let actual = make_variant_arm(&variant).unwrap();
+        let expected: Arm = parse_quote! {
+            "Connected" => Self::Connected {
+                address: ::preflect::fields::remove_field(&mut fields, &["address", "addr"])?,
+                retries: core::default::Default::default()
+            }
+        };
+
+        assert_eq!(actual, expected)
+    }
+
+    #[test]
+    fn make_variant_arm_unnamed_test() {
+        let variant: Variant = parse_quote!(Failed(String));
+
+        let actual = make_variant_arm(&variant).unwrap();
+        let expected: Arm = parse_quote! {
+            "Failed" => Self::Failed {
+                0: ::preflect::fields::remove_field(&mut fields, &["0"])?
+            }
+        };
+
+        assert_eq!(actual, expected)
+    }
+}
@@ -0,0 +1,50 @@
+use syn::parse_quote;
+use syn::Generics;
+use syn::Type;
+
+/// Adds a `'static` bound for each of the field types to the generics of a
+/// type. This allows the fields of generic types to be viewed as `dyn Any`.
+pub fn add_static_bounds<'a>(
+    generics: &Generics,
+    field_tys: impl IntoIterator<Item = &'a Type>,
+) -> Generics {
+    let mut generics = generics.clone();
+    if !generics.params.is_empty() {
+        let where_clause = generics.make_where_clause();
+        for field_ty in field_tys {
+            where_clause
+                .predicates
+                .push(parse_quote!(#field_ty: 'static));
+        }
+    }
+
+    generics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn add_static_bounds_test() {
+        let generics: Generics = parse_quote!(<'a, T: Clone, const N: usize>);
+        let field_tys: [Type; 2] = [parse_quote!(T), parse_quote!([&'a str; N])];
+
+        let actual = add_static_bounds(&generics, field_tys.iter());
+        let mut expected: Generics = parse_quote!(<'a, T: Clone, const N: usize>);
+        expected.where_clause = parse_quote!(where T: 'static, [&'a str; N]: 'static);
+
+        assert_eq!(actual, expected)
+    }
+
+    #[test]
+    fn add_static_bounds_non_generic_test() {
+        let generics = Generics::default();
+        let field_tys: [Type; 1] = [parse_quote!(u32)];
+
+        let actual = add_static_bounds(&generics, field_tys.iter());
+
+        assert_eq!(actual, Generics::default())
+    }
+}
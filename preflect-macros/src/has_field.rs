@@ -1,11 +1,18 @@
 use crate::attr_utils::get_preflect_attr;
 use crate::errors::PreflectMacroError;
+use crate::field_info::make_field_info;
+use crate::field_name::make_field_name_type;
+use crate::field_utils::check_unique_aliases;
+use crate::field_utils::get_field_member;
+use crate::field_utils::get_field_name;
+use crate::generics_utils::add_static_bounds;
 use proc_macro2::TokenStream;
 use quote::ToTokens;
 use syn::parse_quote;
 use syn::Data;
 use syn::DeriveInput;
 use syn::Field;
+use syn::Generics;
 use syn::Ident;
 use syn::ItemImpl;
 use syn::LitStr;
@@ -14,33 +21,105 @@ pub fn has_field_derive_impl(
     derive_input: &DeriveInput,
 ) -> Result<TokenStream, PreflectMacroError> {
     let ty_name = &derive_input.ident;
+    let generics = &derive_input.generics;
     if let Data::Struct(data_struct) = &derive_input.data {
+        check_unique_aliases(&data_struct.fields)?;
         let mut token_stream = TokenStream::new();
-        for field in data_struct.fields.iter() {
+        for (index, field) in data_struct.fields.iter().enumerate() {
             let attr = get_preflect_attr(field)?;
             if !attr.ignore() {
-                token_stream.extend(field_impl(ty_name, field).into_token_stream());
+                let field_name = get_field_name(field, index);
+                let field_names = std::iter::once(field_name.clone()).chain(
+                    attr.aliases()
+                        .map(|alias| LitStr::new(alias, field_name.span())),
+                );
+
+                for field_name in field_names {
+                    let item_impl = named_field_impl(
+                        ty_name,
+                        generics,
+                        field,
+                        &field_name,
+                        index,
+                        attr.redacted(),
+                    );
+                    token_stream.extend(item_impl.into_token_stream());
+
+                    if cfg!(feature = "nightly") {
+                        let item_impl = field_impl(
+                            ty_name,
+                            generics,
+                            field,
+                            &field_name,
+                            index,
+                            attr.redacted(),
+                        );
+                        token_stream.extend(item_impl.into_token_stream());
+                    }
+                }
             }
         }
 
         Ok(token_stream)
     } else {
-        let message = "".into();
+        let message = "HasField can only be derived for structs".into();
         let span = ty_name.span();
         Err(PreflectMacroError::new(message, span))
     }
 }
 
-fn field_impl(ty_name: &Ident, field: &Field) -> ItemImpl {
+/// Implements `BaseHasNamedField`, which encodes the name of the field as a
+/// type so that it can be used on stable Rust.
+fn named_field_impl(
+    ty_name: &Ident,
+    generics: &Generics,
+    field: &Field,
+    field_name: &LitStr,
+    index: usize,
+    redacted: bool,
+) -> ItemImpl {
     let field_ty = &field.ty;
-    let field_ident = &field.ident.as_ref().unwrap();
-    let field_name = LitStr::new(&field_ident.to_string(), field_ident.span());
+    let field_member = get_field_member(field, index);
+    let field_info = make_field_info(field, index, redacted);
+    let name_ty = make_field_name_type(&field_name.value());
+    let generics = add_static_bounds(generics, Some(field_ty));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
     parse_quote! {
-        impl ::preflect::fields::BaseHasField<#field_name> for #ty_name {
+        unsafe impl #impl_generics ::preflect::fields::BaseHasNamedField<#name_ty> for #ty_name #ty_generics #where_clause {
             type FieldType = #field_ty;
 
+            const INFO: ::preflect::fields::FieldInfo = #field_info;
+
+            fn offset() -> usize {
+                ::preflect::memoffset::offset_of!(Self, #field_member)
+            }
+        }
+    }
+}
+
+/// Implements `BaseHasField`, which uses the name of the field as a const
+/// generic parameter. This requires the `nightly` feature.
+fn field_impl(
+    ty_name: &Ident,
+    generics: &Generics,
+    field: &Field,
+    field_name: &LitStr,
+    index: usize,
+    redacted: bool,
+) -> ItemImpl {
+    let field_ty = &field.ty;
+    let field_member = get_field_member(field, index);
+    let field_info = make_field_info(field, index, redacted);
+    let generics = add_static_bounds(generics, Some(field_ty));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    parse_quote! {
+        unsafe impl #impl_generics ::preflect::fields::BaseHasField<#field_name> for #ty_name #ty_generics #where_clause {
+            type FieldType = #field_ty;
+
+            const INFO: ::preflect::fields::FieldInfo = #field_info;
+
             fn offset() -> usize {
-                ::preflect::memoffset::offset_of!(#ty_name, #field_ident)
+                ::preflect::memoffset::offset_of!(Self, #field_member)
             }
         }
     }
@@ -57,13 +136,142 @@ mod tests {
         let ty_name = Ident::new("User", Span::call_site());
         let field = make_field();
 
-        let actual = field_impl(&ty_name, &field);
+        let field_name = parse_quote!("id");
+
+        let actual = field_impl(
+            &ty_name,
+            &Generics::default(),
+            &field,
+            &field_name,
+            0,
+            false,
+        );
         let expected = parse_quote! {
-            impl ::preflect::fields::BaseHasField<"id"> for User {
+            unsafe impl ::preflect::fields::BaseHasField<"id"> for User {
                 type FieldType = u32;
 
+                const INFO: ::preflect::fields::FieldInfo = ::preflect::fields::FieldInfo::new::<u32>(
+                    "id",
+                    0usize,
+                    ::preflect::fields::Visibility::Public,
+                    || ::preflect::memoffset::offset_of!(Self, id)
+                );
+
+                fn offset() -> usize {
+                    ::preflect::memoffset::offset_of!(Self, id)
+                }
+            }
+        };
+
+        assert_eq!(actual, expected)
+    }
+
+    #[test]
+    fn named_field_impl_test() {
+        let ty_name = Ident::new("User", Span::call_site());
+        let field = make_field();
+
+        let field_name = parse_quote!("id");
+
+        let actual = named_field_impl(
+            &ty_name,
+            &Generics::default(),
+            &field,
+            &field_name,
+            0,
+            false,
+        );
+        let expected = parse_quote! {
+            unsafe impl ::preflect::fields::BaseHasNamedField<
+                ::preflect::fields::NameChar<'i', ::preflect::fields::NameChar<'d', ::preflect::fields::NameEnd>>
+            > for User {
+                type FieldType = u32;
+
+                const INFO: ::preflect::fields::FieldInfo = ::preflect::fields::FieldInfo::new::<u32>(
+                    "id",
+                    0usize,
+                    ::preflect::fields::Visibility::Public,
+                    || ::preflect::memoffset::offset_of!(Self, id)
+                );
+
+                fn offset() -> usize {
+                    ::preflect::memoffset::offset_of!(Self, id)
+                }
+            }
+        };
+
+        assert_eq!(actual, expected)
+    }
+
+    #[test]
+    fn has_field_derive_impl_alias_test() {
+        let derive_input: DeriveInput = parse_quote! {
+            struct User {
+                #[preflect(alias = ["user_id"])]
+                pub id: u32,
+            }
+        };
+
+        let actual = has_field_derive_impl(&derive_input).unwrap().to_string();
+        let expected = ["\"id\"", "\"user_id\""]
+            .iter()
+            .map(|name| {
+                let field_name: LitStr = syn::parse_str(name).unwrap();
+                let ty_name = Ident::new("User", Span::call_site());
+                let field = make_field();
+                let mut tokens = named_field_impl(
+                    &ty_name,
+                    &Generics::default(),
+                    &field,
+                    &field_name,
+                    0,
+                    false,
+                )
+                .into_token_stream();
+                if cfg!(feature = "nightly") {
+                    tokens.extend(
+                        field_impl(
+                            &ty_name,
+                            &Generics::default(),
+                            &field,
+                            &field_name,
+                            0,
+                            false,
+                        )
+                        .into_token_stream(),
+                    );
+                }
+
+                tokens
+            })
+            .collect::<TokenStream>()
+            .to_string();
+
+        assert_eq!(actual, expected)
+    }
+
+    #[test]
+    fn field_impl_generic_test() {
+        let ty_name = Ident::new("Wrapper", Span::call_site());
+        let generics: Generics = parse_quote!(<T: Clone>);
+        let mut field = make_field();
+        field.ty = parse_quote!(T);
+        let field_name = parse_quote!("id");
+
+        let actual = field_impl(&ty_name, &generics, &field, &field_name, 0, false);
+        let expected = parse_quote! {
+            unsafe impl<T: Clone> ::preflect::fields::BaseHasField<"id"> for Wrapper<T> where T: 'static {
+                type FieldType = T;
+
+                const INFO: ::preflect::fields::FieldInfo = ::preflect::fields::FieldInfo::new::<T>(
+                    "id",
+                    0usize,
+                    ::preflect::fields::Visibility::Public,
+                    || ::preflect::memoffset::offset_of!(Self, id)
+                );
+
                 fn offset() -> usize {
-                    ::preflect::memoffset::offset_of!(User, id)
+                    ::preflect::memoffset::offset_of!(Self, id)
                 }
             }
         };
@@ -1,8 +1,18 @@
 use crate::attr_utils::get_preflect_attr;
 use crate::errors::GetHelperAttrError;
 use crate::errors::PreflectMacroError;
+use crate::field_info::make_field_info;
+use crate::field_name::impl_field_name_markers;
+use crate::field_utils::check_unique_aliases;
+use crate::field_utils::get_field_member;
+use crate::field_utils::get_field_name;
+use crate::field_utils::get_unique_names;
+use crate::field_utils::Wrapper;
+use crate::generics_utils::add_static_bounds;
+use crate::has_fields_enum::impl_has_fields_for_data_enum;
 use proc_macro2::Span;
 use proc_macro2::TokenStream;
+use quote::format_ident;
 use quote::ToTokens;
 use syn::parse::Error as ParseError;
 use syn::parse_quote;
@@ -11,56 +21,195 @@ use syn::Arm;
 use syn::Data;
 use syn::DataStruct;
 use syn::DeriveInput;
+use syn::Expr;
 use syn::ExprMatch;
 use syn::Field;
 use syn::Fields;
+use syn::Generics;
 use syn::Ident;
+use syn::ImplItemMethod;
 use syn::ItemImpl;
 use syn::LitStr;
+use syn::Member;
+use syn::Stmt;
 use syn::Token;
+use syn::Type;
 
 pub fn has_fields_derive_impl(
     derive_input: &DeriveInput,
 ) -> Result<TokenStream, PreflectMacroError> {
-    if let Data::Struct(data_struct) = &derive_input.data {
-        let struct_ident = &derive_input.ident;
+    let ty_ident = &derive_input.ident;
+    let generics = &derive_input.generics;
+    match &derive_input.data {
+        Data::Struct(data_struct) => {
+            check_unique_aliases(get_fields(data_struct, &Punctuated::new()))?;
+            let mut token_stream = TokenStream::new();
+            let has_fields_impl = impl_has_fields_for_data_struct(ty_ident, generics, data_struct)?;
+            let field_info_impl =
+                impl_has_field_info_for_data_struct(ty_ident, generics, data_struct)?;
+            let accepted_names = get_accepted_names(get_fields(data_struct, &Punctuated::new()))?;
+            let name_markers = impl_field_name_markers(
+                &parse_quote!(::preflect::fields::HasFieldName),
+                "reflected",
+                ty_ident,
+                generics,
+                &accepted_names,
+            );
+            has_fields_impl.to_tokens(&mut token_stream);
+            field_info_impl.to_tokens(&mut token_stream);
+            name_markers.to_tokens(&mut token_stream);
 
-        impl_has_fields_for_data_struct(struct_ident, data_struct)
-            .map(ToTokens::into_token_stream)
-            .map_err(PreflectMacroError::from)
-    } else {
-        panic!("HasFields can only be derived for structs")
+            Ok(token_stream)
+        }
+        Data::Enum(data_enum) => impl_has_fields_for_data_enum(ty_ident, generics, data_enum)
+            .map_err(PreflectMacroError::from),
+        Data::Union(_) => {
+            let message = "HasFields can only be derived for structs and enums".into();
+            let span = ty_ident.span();
+            Err(PreflectMacroError::new(message, span))
+        }
     }
 }
 
 fn impl_has_fields_for_data_struct(
     struct_ident: &Ident,
+    generics: &Generics,
     data_struct: &DataStruct,
 ) -> Result<ItemImpl, GetHelperAttrError> {
     let empty_punct = Punctuated::default();
-    let fields = match &data_struct.fields {
-        Fields::Named(fields_named) => &fields_named.named,
-        Fields::Unnamed(fields_unnamed) => &fields_unnamed.unnamed,
-        Fields::Unit => &empty_punct,
-    };
+    let fields = get_fields(data_struct, &empty_punct);
 
+    let generics = add_static_bounds(generics, get_reflected_field_types(fields.iter())?);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let field_names = make_field_names(fields.iter())?;
     let reg_match = make_match(fields.iter(), false)?;
     let mut_match = make_match(fields.iter(), true)?;
+    let info_method = make_get_field_info(fields.iter())?;
+    let visit_method = make_visit_fields_mut(fields.iter())?;
+    let nested_methods = make_nested_methods(fields.iter())?;
+    let collection_methods = make_wrapper_methods(fields.iter(), Wrapper::Collection)?;
+    let pointer_methods = make_wrapper_methods(fields.iter(), Wrapper::Pointer)?;
 
     // Builds the match arms for the immutable and mutable version of the get_field method.
     Ok(parse_quote! {
-        impl ::preflect::fields::BaseHasFields for #struct_ident {
-            fn get_field_raw<'s>(&'s self, name: &str) -> preflect::fields::FieldAccessResult<&'s dyn core::any::Any> {
+        impl #impl_generics ::preflect::fields::BaseHasFields for #struct_ident #ty_generics #where_clause {
+            fn field_names(&self) -> &'static [&'static str] {
+                &[#(#field_names),*]
+            }
+
+            fn field_info(&self) -> &'static [::preflect::fields::FieldInfo] {
+                <Self as ::preflect::fields::HasFieldInfo>::FIELD_INFO
+            }
+
+            #info_method
+
+            fn get_field_raw<'s, 'n>(&'s self, name: &'n str) -> preflect::fields::FieldAccessResult<'n, &'s dyn core::any::Any> {
                 #reg_match
             }
 
-            fn get_field_mut_raw<'s>(&'s mut self, name: &str) -> preflect::fields::FieldAccessResult<&'s mut dyn core::any::Any> {
+            fn get_field_mut_raw<'s, 'n>(&'s mut self, name: &'n str) -> preflect::fields::FieldAccessResult<'n, &'s mut dyn core::any::Any> {
                 #mut_match
             }
+
+            #visit_method
+
+            #(#nested_methods)*
+
+            #(#collection_methods)*
+
+            #(#pointer_methods)*
         }
     })
 }
 
+fn impl_has_field_info_for_data_struct(
+    struct_ident: &Ident,
+    generics: &Generics,
+    data_struct: &DataStruct,
+) -> Result<ItemImpl, GetHelperAttrError> {
+    let empty_punct = Punctuated::default();
+    let fields = get_fields(data_struct, &empty_punct);
+
+    let mut field_info = Vec::<Expr>::new();
+    for (index, field) in fields.iter().enumerate() {
+        let attr = get_preflect_attr(field)?;
+        if !attr.ignore() {
+            field_info.push(make_field_info(field, index, attr.redacted()));
+        }
+    }
+
+    let accepted_names = get_accepted_names(fields)?;
+
+    let generics = add_static_bounds(generics, get_reflected_field_types(fields.iter())?);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    Ok(parse_quote! {
+        impl #impl_generics ::preflect::fields::HasFieldInfo for #struct_ident #ty_generics #where_clause {
+            const FIELD_INFO: &'static [::preflect::fields::FieldInfo] = &[#(#field_info),*];
+            const ACCEPTED_NAMES: &'static [&'static str] = &[#(#accepted_names),*];
+        }
+    })
+}
+
+fn get_fields<'a>(
+    data_struct: &'a DataStruct,
+    empty_punct: &'a Punctuated<Field, Token![,]>,
+) -> &'a Punctuated<Field, Token![,]> {
+    match &data_struct.fields {
+        Fields::Named(fields_named) => &fields_named.named,
+        Fields::Unnamed(fields_unnamed) => &fields_unnamed.unnamed,
+        Fields::Unit => empty_punct,
+    }
+}
+
+/// Gets the names and aliases of the reflected fields, without duplicates.
+fn get_accepted_names(
+    fields: &Punctuated<Field, Token![,]>,
+) -> Result<Vec<LitStr>, GetHelperAttrError> {
+    let mut accepted_names = Vec::<LitStr>::new();
+    for (index, field) in fields.iter().enumerate() {
+        let attr = get_preflect_attr(field)?;
+        if !attr.ignore() {
+            let field_name = get_field_name(field, index);
+            let alias_lits = attr
+                .aliases()
+                .map(|alias| LitStr::new(alias, field_name.span()))
+                .collect::<Vec<_>>();
+            accepted_names.push(field_name);
+            accepted_names.extend(alias_lits);
+        }
+    }
+
+    Ok(get_unique_names(&accepted_names))
+}
+
+fn get_reflected_field_types<'a>(
+    fields: impl Iterator<Item = &'a Field>,
+) -> Result<Vec<&'a Type>, GetHelperAttrError> {
+    let mut field_tys = vec![];
+    for field in fields {
+        let attr = get_preflect_attr(field)?;
+        if !attr.ignore() {
+            field_tys.push(&field.ty);
+        }
+    }
+
+    Ok(field_tys)
+}
+
+fn make_field_names<'a>(
+    fields: impl Iterator<Item = &'a Field>,
+) -> Result<Vec<LitStr>, GetHelperAttrError> {
+    let mut field_names = vec![];
+    for (index, field) in fields.enumerate() {
+        let attr = get_preflect_attr(field)?;
+        if !attr.ignore() {
+            field_names.push(get_field_name(field, index));
+        }
+    }
+
+    Ok(field_names)
+}
+
 fn make_match<'a>(
     fields: impl Iterator<Item = &'a Field>,
     is_mut: bool,
@@ -74,25 +223,237 @@ fn make_match<'a>(
 
     // Build a token stream for each match arm
     let match_arms = fields
-        .filter_map(|field| match make_match_arm(field, mut_token) {
-            Ok(Some(arm)) => Some(Ok(arm)),
-            Err(e) => Some(Err(e)),
-            _ => None,
-        })
+        .enumerate()
+        .filter_map(
+            |(index, field)| match make_match_arm(index, field, mut_token) {
+                Ok(Some(arm)) => Some(Ok(arm)),
+                Err(e) => Some(Err(e)),
+                _ => None,
+            },
+        )
         .collect::<Result<Vec<Arm>, _>>()
         .map_err(<GetHelperAttrError as Into<syn::Error>>::into)?;
 
     let match_statement = parse_quote! {
         match name {
             #(#match_arms,)*
-            _ => core::result::Result::Err(preflect::fields::FieldAccessError::MissingField)
+            _ => core::result::Result::Err(preflect::fields::FieldAccessError::missing_field(name, <Self as ::preflect::fields::BaseHasFields>::field_names(self)))
         }
     };
 
     Ok(match_statement)
 }
 
+/// Builds the method which looks up the descriptor of a field by its name or
+/// one of its aliases.
+fn make_get_field_info<'a>(
+    fields: impl Iterator<Item = &'a Field>,
+) -> Result<ImplItemMethod, GetHelperAttrError> {
+    let mut info_arms = Vec::<Arm>::new();
+    for (index, field) in fields.enumerate() {
+        let attr = get_preflect_attr(field)?;
+        if attr.ignore() {
+            continue;
+        }
+
+        let info_index = info_arms.len();
+        let field_name = get_field_name(field, index);
+        let alias_lits = attr
+            .aliases()
+            .map(|alias| LitStr::new(alias, field_name.span()));
+        info_arms.push(parse_quote! {
+            #field_name #(| #alias_lits)* => core::result::Result::Ok(&<Self as ::preflect::fields::HasFieldInfo>::FIELD_INFO[#info_index])
+        });
+    }
+
+    Ok(parse_quote! {
+        fn get_field_info<'n>(&self, name: &'n str) -> preflect::fields::FieldAccessResult<'n, &'static ::preflect::fields::FieldInfo> {
+            match name {
+                #(#info_arms,)*
+                _ => core::result::Result::Err(preflect::fields::FieldAccessError::missing_field(name, <Self as ::preflect::fields::BaseHasFields>::field_names(self)))
+            }
+        }
+    })
+}
+
+/// Builds the method which borrows every reflected field mutably at once by
+/// destructuring `self`, so the references passed to the visitor never alias.
+fn make_visit_fields_mut<'a>(
+    fields: impl Iterator<Item = &'a Field>,
+) -> Result<ImplItemMethod, GetHelperAttrError> {
+    let mut field_members = Vec::<Member>::new();
+    let mut field_bindings = Vec::<Ident>::new();
+    let mut field_names = Vec::<LitStr>::new();
+    for (index, field) in fields.enumerate() {
+        let attr = get_preflect_attr(field)?;
+        if !attr.ignore() {
+            field_members.push(get_field_member(field, index));
+            field_bindings.push(format_ident!("field_{}", index));
+            field_names.push(get_field_name(field, index));
+        }
+    }
+
+    let body: Vec<Stmt> = if field_names.is_empty() {
+        vec![parse_quote!(let _ = visit;)]
+    } else {
+        let mut body: Vec<Stmt> = vec![parse_quote! {
+            let Self { #(#field_members: #field_bindings,)* .. } = self;
+        }];
+        body.extend(
+            field_names
+                .iter()
+                .zip(&field_bindings)
+                .map(|(name, binding)| -> Stmt { parse_quote!(visit(#name, #binding);) }),
+        );
+        body
+    };
+
+    Ok(parse_quote! {
+        fn visit_fields_mut<'s>(&'s mut self, visit: &mut dyn FnMut(&'static str, &'s mut dyn core::any::Any)) {
+            #(#body)*
+        }
+    })
+}
+
+/// Builds the methods which access the fields marked with
+/// `#[preflect(nested)]`. If there are no such fields, the default
+/// implementations are used.
+fn make_nested_methods<'a>(
+    fields: impl Iterator<Item = &'a Field>,
+) -> Result<Vec<ImplItemMethod>, GetHelperAttrError> {
+    let mut nested_arms = Vec::<Arm>::new();
+    let mut nested_mut_arms = Vec::<Arm>::new();
+    let mut other_names = Vec::<LitStr>::new();
+    for (index, field) in fields.enumerate() {
+        let attr = get_preflect_attr(field)?;
+        if attr.ignore() {
+            continue;
+        }
+
+        let field_name = get_field_name(field, index);
+        let alias_lits = attr
+            .aliases()
+            .map(|alias| LitStr::new(alias, field_name.span()))
+            .collect::<Vec<_>>();
+        if attr.nested() {
+            let field_member = get_field_member(field, index);
+            nested_arms.push(parse_quote! {
+                #field_name #(| #alias_lits)* => core::result::Result::Ok(&self.#field_member)
+            });
+            nested_mut_arms.push(parse_quote! {
+                #field_name #(| #alias_lits)* => core::result::Result::Ok(&mut self.#field_member)
+            });
+        } else {
+            other_names.push(field_name);
+            other_names.extend(alias_lits);
+        }
+    }
+
+    if nested_arms.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let other_arm: Option<Arm> = if other_names.is_empty() {
+        None
+    } else {
+        Some(parse_quote! {
+            #(#other_names)|* => core::result::Result::Err(preflect::fields::FieldAccessError::not_nested(name)),
+        })
+    };
+
+    Ok(vec![
+        parse_quote! {
+            fn get_nested_raw<'s, 'n>(&'s self, name: &'n str) -> preflect::fields::FieldAccessResult<'n, &'s dyn ::preflect::fields::Reflect> {
+                match name {
+                    #(#nested_arms,)*
+                    #other_arm
+                    _ => core::result::Result::Err(preflect::fields::FieldAccessError::missing_field(name, <Self as ::preflect::fields::BaseHasFields>::field_names(self)))
+                }
+            }
+        },
+        parse_quote! {
+            fn get_nested_mut_raw<'s, 'n>(&'s mut self, name: &'n str) -> preflect::fields::FieldAccessResult<'n, &'s mut dyn ::preflect::fields::Reflect> {
+                match name {
+                    #(#nested_mut_arms,)*
+                    #other_arm
+                    _ => core::result::Result::Err(preflect::fields::FieldAccessError::missing_field(name, <Self as ::preflect::fields::BaseHasFields>::field_names(self)))
+                }
+            }
+        },
+    ])
+}
+
+/// Builds the methods which access the fields marked with
+/// `#[preflect(collection)]` or `#[preflect(pointer)]`, depending on the
+/// wrapper. If there are no such fields, the default implementations are
+/// used.
+fn make_wrapper_methods<'a>(
+    fields: impl Iterator<Item = &'a Field>,
+    wrapper: Wrapper,
+) -> Result<Vec<ImplItemMethod>, GetHelperAttrError> {
+    let mut wrapper_arms = Vec::<Arm>::new();
+    let mut wrapper_mut_arms = Vec::<Arm>::new();
+    let mut other_names = Vec::<LitStr>::new();
+    for (index, field) in fields.enumerate() {
+        let attr = get_preflect_attr(field)?;
+        if attr.ignore() {
+            continue;
+        }
+
+        let field_name = get_field_name(field, index);
+        let alias_lits = attr
+            .aliases()
+            .map(|alias| LitStr::new(alias, field_name.span()))
+            .collect::<Vec<_>>();
+        if Wrapper::of(&attr) == Some(wrapper) {
+            let field_member = get_field_member(field, index);
+            let field_ref = wrapper.make_ref(&field.ty, parse_quote!(&self.#field_member));
+            let field_mut = wrapper.make_mut(&field.ty, parse_quote!(&mut self.#field_member));
+            wrapper_arms.push(parse_quote! {
+                #field_name #(| #alias_lits)* => core::result::Result::Ok(#field_ref)
+            });
+            wrapper_mut_arms.push(parse_quote! {
+                #field_name #(| #alias_lits)* => core::result::Result::Ok(#field_mut)
+            });
+        } else {
+            other_names.push(field_name);
+            other_names.extend(alias_lits);
+        }
+    }
+
+    if wrapper_arms.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let not_wrapper = wrapper.not_wrapper();
+    let other_arm: Option<Arm> = if other_names.is_empty() {
+        None
+    } else {
+        Some(parse_quote! {
+            #(#other_names)|* => core::result::Result::Err(preflect::fields::FieldAccessError::#not_wrapper(name)),
+        })
+    };
+
+    Ok(wrapper.make_methods(
+        parse_quote! {
+            match name {
+                #(#wrapper_arms,)*
+                #other_arm
+                _ => core::result::Result::Err(preflect::fields::FieldAccessError::missing_field(name, <Self as ::preflect::fields::BaseHasFields>::field_names(self)))
+            }
+        },
+        parse_quote! {
+            match name {
+                #(#wrapper_mut_arms,)*
+                #other_arm
+                _ => core::result::Result::Err(preflect::fields::FieldAccessError::missing_field(name, <Self as ::preflect::fields::BaseHasFields>::field_names(self)))
+            }
+        },
+    ))
+}
+
 fn make_match_arm(
+    index: usize,
     field: &Field,
     mut_token: Option<Token![mut]>,
 ) -> Result<Option<Arm>, GetHelperAttrError> {
@@ -100,9 +461,12 @@ fn make_match_arm(
         if attr.ignore() {
             None
         } else {
-            let field_ident = field.ident.as_ref().unwrap();
-            let field_name_lit = LitStr::new(&field_ident.to_string(), field_ident.span());
-            let arm: Arm = parse_quote! { #field_name_lit => core::result::Result::Ok(& #mut_token self.#field_ident) };
+            let field_member = get_field_member(field, index);
+            let field_name_lit = get_field_name(field, index);
+            let alias_lits = attr
+                .aliases()
+                .map(|alias| LitStr::new(alias, field_name_lit.span()));
+            let arm: Arm = parse_quote! { #field_name_lit #(| #alias_lits)* => core::result::Result::Ok(& #mut_token self.#field_member) };
             Some(arm)
         }
     })
@@ -112,6 +476,10 @@ fn make_match_arm(
 mod tests {
 
     use super::*;
+    #[cfg(feature = "alloc")]
+    use crate::field_utils::make_element_cast;
+    #[cfg(feature = "alloc")]
+    use crate::field_utils::make_target_cast;
     use pretty_assertions::assert_eq;
     use proc_macro2::Span;
     use syn::parse_quote;
@@ -122,27 +490,181 @@ mod tests {
     use syn::Field;
     use syn::Fields;
     use syn::FieldsNamed;
+    use syn::Generics;
 
     #[test]
     fn impl_has_fields_for_data_struct_test() {
         let struct_ident = Ident::new("User", Span::call_site());
         let data_struct = make_data_struct();
-        let actual = impl_has_fields_for_data_struct(&struct_ident, &data_struct).unwrap();
+        let actual =
+            impl_has_fields_for_data_struct(&struct_ident, &Generics::default(), &data_struct)
+                .unwrap();
         let expected: ItemImpl = parse_quote! {
             impl ::preflect::fields::BaseHasFields for User {
-                fn get_field_raw<'s>(&'s self, name: &str) -> preflect::fields::FieldAccessResult<&'s dyn core::any::Any> {
+                fn field_names(&self) -> &'static [&'static str] {
+                    &["id"]
+                }
+
+                fn field_info(&self) -> &'static [::preflect::fields::FieldInfo] {
+                    <Self as ::preflect::fields::HasFieldInfo>::FIELD_INFO
+                }
+
+                fn get_field_info<'n>(&self, name: &'n str) -> preflect::fields::FieldAccessResult<'n, &'static ::preflect::fields::FieldInfo> {
+                    match name {
+                        "id" => core::result::Result::Ok(&<Self as ::preflect::fields::HasFieldInfo>::FIELD_INFO[0usize]),
+                        _ => core::result::Result::Err(preflect::fields::FieldAccessError::missing_field(name, <Self as ::preflect::fields::BaseHasFields>::field_names(self)))
+                    }
+                }
+
+                fn get_field_raw<'s, 'n>(&'s self, name: &'n str) -> preflect::fields::FieldAccessResult<'n, &'s dyn core::any::Any> {
                     match name {
                         "id" => core::result::Result::Ok(&self.id),
-                        _ => core::result::Result::Err(preflect::fields::FieldAccessError::MissingField)
+                        _ => core::result::Result::Err(preflect::fields::FieldAccessError::missing_field(name, <Self as ::preflect::fields::BaseHasFields>::field_names(self)))
                     }
                 }
 
-                fn get_field_mut_raw<'s>(&'s mut self, name: &str) -> preflect::fields::FieldAccessResult<&'s mut dyn core::any::Any> {
+                fn get_field_mut_raw<'s, 'n>(&'s mut self, name: &'n str) -> preflect::fields::FieldAccessResult<'n, &'s mut dyn core::any::Any> {
                     match name {
                         "id" => core::result::Result::Ok(&mut self.id),
-                        _ => core::result::Result::Err(preflect::fields::FieldAccessError::MissingField)
+                        _ => core::result::Result::Err(preflect::fields::FieldAccessError::missing_field(name, <Self as ::preflect::fields::BaseHasFields>::field_names(self)))
                     }
                 }
+
+                fn visit_fields_mut<'s>(&'s mut self, visit: &mut dyn FnMut(&'static str, &'s mut dyn core::any::Any)) {
+                    let Self { id: field_0, .. } = self;
+                    visit("id", field_0);
+                }
+            }
+        };
+
+        assert_eq!(actual, expected)
+    }
+
+    #[test]
+    fn impl_has_fields_for_generic_data_struct_test() {
+        let struct_ident = Ident::new("Wrapper", Span::call_site());
+        let generics: Generics = parse_quote!(<'a, T, const N: usize>);
+        let data_struct: DataStruct = match parse_quote!(
+            struct Wrapper {
+                inner: [T; N],
+                #[preflect(ignore)]
+                name: &'a str,
+            }
+        ) {
+            syn::DeriveInput {
+                data: Data::Struct(data_struct),
+                ..
+            } => data_struct,
+            _ => unreachable!(),
+        };
+
+        let actual =
+            impl_has_fields_for_data_struct(&struct_ident, &generics, &data_struct).unwrap();
+        let expected: ItemImpl = parse_quote! {
+            impl<'a, T, const N: usize> ::preflect::fields::BaseHasFields for Wrapper<'a, T, N> where [T; N]: 'static {
+                fn field_names(&self) -> &'static [&'static str] {
+                    &["inner"]
+                }
+
+                fn field_info(&self) -> &'static [::preflect::fields::FieldInfo] {
+                    <Self as ::preflect::fields::HasFieldInfo>::FIELD_INFO
+                }
+
+                fn get_field_info<'n>(&self, name: &'n str) -> preflect::fields::FieldAccessResult<'n, &'static ::preflect::fields::FieldInfo> {
+                    match name {
+                        "inner" => core::result::Result::Ok(&<Self as ::preflect::fields::HasFieldInfo>::FIELD_INFO[0usize]),
+                        _ => core::result::Result::Err(preflect::fields::FieldAccessError::missing_field(name, <Self as ::preflect::fields::BaseHasFields>::field_names(self)))
+                    }
+                }
+
+                fn get_field_raw<'s, 'n>(&'s self, name: &'n str) -> preflect::fields::FieldAccessResult<'n, &'s dyn core::any::Any> {
+                    match name {
+                        "inner" => core::result::Result::Ok(&self.inner),
+                        _ => core::result::Result::Err(preflect::fields::FieldAccessError::missing_field(name, <Self as ::preflect::fields::BaseHasFields>::field_names(self)))
+                    }
+                }
+
+                fn get_field_mut_raw<'s, 'n>(&'s mut self, name: &'n str) -> preflect::fields::FieldAccessResult<'n, &'s mut dyn core::any::Any> {
+                    match name {
+                        "inner" => core::result::Result::Ok(&mut self.inner),
+                        _ => core::result::Result::Err(preflect::fields::FieldAccessError::missing_field(name, <Self as ::preflect::fields::BaseHasFields>::field_names(self)))
+                    }
+                }
+
+                fn visit_fields_mut<'s>(&'s mut self, visit: &mut dyn FnMut(&'static str, &'s mut dyn core::any::Any)) {
+                    let Self { inner: field_0, .. } = self;
+                    visit("inner", field_0);
+                }
+            }
+        };
+
+        assert_eq!(actual, expected)
+    }
+
+    #[test]
+    fn impl_has_field_info_for_data_struct_test() {
+        let struct_ident = Ident::new("User", Span::call_site());
+        let data_struct = make_data_struct();
+        let actual =
+            impl_has_field_info_for_data_struct(&struct_ident, &Generics::default(), &data_struct)
+                .unwrap();
+        let expected: ItemImpl = parse_quote! {
+            impl ::preflect::fields::HasFieldInfo for User {
+                const FIELD_INFO: &'static [::preflect::fields::FieldInfo] = &[
+                    ::preflect::fields::FieldInfo::new::<u32>(
+                        "id",
+                        0usize,
+                        ::preflect::fields::Visibility::Public,
+                        || ::preflect::memoffset::offset_of!(Self, id)
+                    )
+                ];
+                const ACCEPTED_NAMES: &'static [&'static str] = &["id"];
+            }
+        };
+
+        assert_eq!(actual, expected)
+    }
+
+    #[test]
+    fn make_field_names_test() {
+        let mut ignored = make_field();
+        ignored.ident = Some(Ident::new("name", Span::call_site()));
+        ignored.attrs.push(parse_quote!(#[preflect(ignore)]));
+        let fields = [make_field(), ignored];
+
+        let actual = make_field_names(fields.iter()).unwrap();
+        let expected: Vec<LitStr> = vec![parse_quote!("id")];
+
+        assert_eq!(actual, expected)
+    }
+
+    #[test]
+    fn make_visit_fields_mut_unnamed_test() {
+        let fields: Fields = Fields::Unnamed(parse_quote!((
+            u32,
+            #[preflect(ignore)]
+            bool,
+            String
+        )));
+
+        let actual = make_visit_fields_mut(fields.iter()).unwrap();
+        let expected: ImplItemMethod = parse_quote! {
+            fn visit_fields_mut<'s>(&'s mut self, visit: &mut dyn FnMut(&'static str, &'s mut dyn core::any::Any)) {
+                let Self { 0: field_0, 2: field_2, .. } = self;
+                visit("0", field_0);
+                visit("2", field_2);
+            }
+        };
+
+        assert_eq!(actual, expected)
+    }
+
+    #[test]
+    fn make_visit_fields_mut_without_fields_test() {
+        let actual = make_visit_fields_mut(Fields::Unit.iter()).unwrap();
+        let expected: ImplItemMethod = parse_quote! {
+            fn visit_fields_mut<'s>(&'s mut self, visit: &mut dyn FnMut(&'static str, &'s mut dyn core::any::Any)) {
+                let _ = visit;
             }
         };
 
@@ -156,7 +678,7 @@ mod tests {
         let expected: ExprMatch = parse_quote! {
             match name {
                 "id" => core::result::Result::Ok(&self.id),
-                _ => core::result::Result::Err(preflect::fields::FieldAccessError::MissingField)
+                _ => core::result::Result::Err(preflect::fields::FieldAccessError::missing_field(name, <Self as ::preflect::fields::BaseHasFields>::field_names(self)))
             }
         };
 
@@ -167,7 +689,7 @@ mod tests {
     fn make_match_arm_test() {
         let field = make_field();
 
-        let actual = make_match_arm(&field, None).unwrap().unwrap();
+        let actual = make_match_arm(0, &field, None).unwrap().unwrap();
         let expected: Arm = parse_quote! {
             "id" => core::result::Result::Ok(&self.id)
         };
@@ -175,6 +697,180 @@ mod tests {
         assert_eq!(actual, expected)
     }
 
+    #[test]
+    fn make_match_arm_alias_test() {
+        let mut field = make_field();
+        field
+            .attrs
+            .push(parse_quote!(#[preflect(alias = ["user_id", "uid"])]));
+
+        let actual = make_match_arm(0, &field, None).unwrap().unwrap();
+        let expected: Arm = parse_quote! {
+            "id" | "user_id" | "uid" => core::result::Result::Ok(&self.id)
+        };
+
+        assert_eq!(actual, expected)
+    }
+
+    #[test]
+    fn has_fields_derive_impl_duplicate_alias_test() {
+        let derive_input: DeriveInput = parse_quote! {
+            struct User {
+                id: u32,
+                #[preflect(alias = "id")]
+                user_id: u32,
+            }
+        };
+
+        let error: TokenStream = has_fields_derive_impl(&derive_input).unwrap_err().into();
+
+        assert!(error
+            .to_string()
+            .contains("The alias 'id' is already the name or an alias of a field."))
+    }
+
+    #[test]
+    fn make_match_unnamed_test() {
+        let fields: Fields = Fields::Unnamed(parse_quote!((
+            u32,
+            #[preflect(ignore)]
+            bool,
+            String
+        )));
+        let actual = make_match(fields.iter(), false).unwrap();
+        let expected: ExprMatch = parse_quote! {
+            match name {
+                "0" => core::result::Result::Ok(&self.0),
+                "2" => core::result::Result::Ok(&self.2),
+                _ => core::result::Result::Err(preflect::fields::FieldAccessError::missing_field(name, <Self as ::preflect::fields::BaseHasFields>::field_names(self)))
+            }
+        };
+
+        assert_eq!(actual, expected)
+    }
+
+    #[test]
+    fn make_nested_methods_test() {
+        let fields: Fields = Fields::Named(parse_quote!({
+            id: u32,
+            #[preflect(nested, alias = "home")]
+            address: Address,
+            #[preflect(alias = ["label"])]
+            name: String,
+        }));
+
+        let actual = make_nested_methods(fields.iter()).unwrap();
+        let expected: Vec<ImplItemMethod> = vec![
+            parse_quote! {
+                fn get_nested_raw<'s, 'n>(&'s self, name: &'n str) -> preflect::fields::FieldAccessResult<'n, &'s dyn ::preflect::fields::Reflect> {
+                    match name {
+                        "address" | "home" => core::result::Result::Ok(&self.address),
+                        "id" | "name" | "label" => core::result::Result::Err(preflect::fields::FieldAccessError::not_nested(name)),
+                        _ => core::result::Result::Err(preflect::fields::FieldAccessError::missing_field(name, <Self as ::preflect::fields::BaseHasFields>::field_names(self)))
+                    }
+                }
+            },
+            parse_quote! {
+                fn get_nested_mut_raw<'s, 'n>(&'s mut self, name: &'n str) -> preflect::fields::FieldAccessResult<'n, &'s mut dyn ::preflect::fields::Reflect> {
+                    match name {
+                        "address" | "home" => core::result::Result::Ok(&mut self.address),
+                        "id" | "name" | "label" => core::result::Result::Err(preflect::fields::FieldAccessError::not_nested(name)),
+                        _ => core::result::Result::Err(preflect::fields::FieldAccessError::missing_field(name, <Self as ::preflect::fields::BaseHasFields>::field_names(self)))
+                    }
+                }
+            },
+        ];
+
+        assert_eq!(actual, expected)
+    }
+
+    #[test]
+    fn make_nested_methods_without_nested_fields_test() {
+        let fields = make_named_fields();
+
+        let actual = make_nested_methods(fields.iter()).unwrap();
+
+        assert!(actual.is_empty())
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn make_collection_methods_test() {
+        let fields: Fields = Fields::Named(parse_quote!({
+            id: u32,
+            #[preflect(collection)]
+            items: Vec<Item>,
+        }));
+
+        let actual = make_wrapper_methods(fields.iter(), Wrapper::Collection).unwrap();
+        let element_cast = make_element_cast(&parse_quote!(Vec<Item>));
+        let expected: Vec<ImplItemMethod> = vec![
+            parse_quote! {
+                fn get_collection_raw<'s, 'n>(&'s self, name: &'n str) -> preflect::fields::FieldAccessResult<'n, ::preflect::collections::CollectionRef<'s>> {
+                    match name {
+                        "items" => core::result::Result::Ok(::preflect::collections::CollectionRef::new(&self.items, #element_cast)),
+                        "id" => core::result::Result::Err(preflect::fields::FieldAccessError::not_collection(name)),
+                        _ => core::result::Result::Err(preflect::fields::FieldAccessError::missing_field(name, <Self as ::preflect::fields::BaseHasFields>::field_names(self)))
+                    }
+                }
+            },
+            parse_quote! {
+                fn get_collection_mut_raw<'s, 'n>(&'s mut self, name: &'n str) -> preflect::fields::FieldAccessResult<'n, ::preflect::collections::CollectionMut<'s>> {
+                    match name {
+                        "items" => core::result::Result::Ok(::preflect::collections::CollectionMut::new(&mut self.items, #element_cast)),
+                        "id" => core::result::Result::Err(preflect::fields::FieldAccessError::not_collection(name)),
+                        _ => core::result::Result::Err(preflect::fields::FieldAccessError::missing_field(name, <Self as ::preflect::fields::BaseHasFields>::field_names(self)))
+                    }
+                }
+            },
+        ];
+
+        assert_eq!(actual, expected)
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn make_pointer_methods_test() {
+        let fields: Fields = Fields::Unnamed(parse_quote!((
+            #[preflect(pointer)] Rc<Config>,
+            #[preflect(collection)] Vec<Item>,
+        )));
+
+        let actual = make_wrapper_methods(fields.iter(), Wrapper::Pointer).unwrap();
+        let target_cast = make_target_cast(&parse_quote!(Rc<Config>));
+        let expected: Vec<ImplItemMethod> = vec![
+            parse_quote! {
+                fn get_pointer_raw<'s, 'n>(&'s self, name: &'n str) -> preflect::fields::FieldAccessResult<'n, ::preflect::pointers::PointerRef<'s>> {
+                    match name {
+                        "0" => core::result::Result::Ok(::preflect::pointers::PointerRef::new(&self.0, #target_cast)),
+                        "1" => core::result::Result::Err(preflect::fields::FieldAccessError::not_pointer(name)),
+                        _ => core::result::Result::Err(preflect::fields::FieldAccessError::missing_field(name, <Self as ::preflect::fields::BaseHasFields>::field_names(self)))
+                    }
+                }
+            },
+            parse_quote! {
+                fn get_pointer_mut_raw<'s, 'n>(&'s mut self, name: &'n str) -> preflect::fields::FieldAccessResult<'n, ::preflect::pointers::PointerMut<'s>> {
+                    match name {
+                        "0" => core::result::Result::Ok(::preflect::pointers::PointerMut::new(&mut self.0, #target_cast)),
+                        "1" => core::result::Result::Err(preflect::fields::FieldAccessError::not_pointer(name)),
+                        _ => core::result::Result::Err(preflect::fields::FieldAccessError::missing_field(name, <Self as ::preflect::fields::BaseHasFields>::field_names(self)))
+                    }
+                }
+            },
+        ];
+
+        assert_eq!(actual, expected)
+    }
+
+    #[test]
+    fn make_collection_methods_without_collection_fields_test() {
+        let fields = make_named_fields();
+
+        let actual = make_wrapper_methods(fields.iter(), Wrapper::Collection).unwrap();
+
+        assert!(actual.is_empty())
+    }
+
     fn make_data_struct() -> DataStruct {
         DataStruct {
             fields: make_named_fields(),
@@ -0,0 +1,893 @@
+use crate::attr_utils::get_preflect_attr;
+use crate::errors::GetHelperAttrError;
+use crate::field_info::make_variant_field_info;
+use crate::field_name::impl_field_name_markers;
+use crate::field_utils::check_unique_aliases;
+use crate::field_utils::get_field_member;
+use crate::field_utils::get_field_name;
+use crate::field_utils::get_unique_names;
+use crate::field_utils::Wrapper;
+use crate::generics_utils::add_static_bounds;
+use proc_macro2::TokenStream;
+use quote::format_ident;
+use quote::quote;
+use quote::ToTokens;
+use syn::ext::IdentExt;
+use syn::parse_quote;
+use syn::Arm;
+use syn::DataEnum;
+use syn::Expr;
+use syn::ExprMatch;
+use syn::Field;
+use syn::Generics;
+use syn::Ident;
+use syn::ImplItemMethod;
+use syn::ItemImpl;
+use syn::LitInt;
+use syn::LitStr;
+
+/// A variant of an enum along with its fields which are reflected.
+struct ReflectedVariant<'a> {
+    ident: &'a Ident,
+    name: LitStr,
+    /// The discriminant of the variant as an `i128`.
+    discriminant: Expr,
+    fields: Vec<ReflectedField<'a>>,
+}
+
+/// A field of an enum variant which is reflected.
+struct ReflectedField<'a> {
+    index: usize,
+    field: &'a Field,
+    nested: bool,
+    /// Whether the field is accessed as a collection or a pointer.
+    wrapper: Option<Wrapper>,
+    redacted: bool,
+    /// The name of the field followed by its aliases.
+    names: Vec<LitStr>,
+}
+
+pub fn impl_has_fields_for_data_enum(
+    enum_ident: &Ident,
+    generics: &Generics,
+    data_enum: &DataEnum,
+) -> Result<TokenStream, GetHelperAttrError> {
+    let variants = get_reflected_variants(data_enum)?;
+
+    let mut token_stream = TokenStream::new();
+    impl_has_fields_for_variants(enum_ident, generics, &variants).to_tokens(&mut token_stream);
+    impl_has_field_info_for_variants(enum_ident, generics, &variants).to_tokens(&mut token_stream);
+    impl_has_variants_for_variants(enum_ident, generics, &variants).to_tokens(&mut token_stream);
+    let name_markers = impl_field_name_markers(
+        &parse_quote!(::preflect::fields::HasFieldName),
+        "reflected",
+        enum_ident,
+        generics,
+        &get_accepted_names(&variants),
+    );
+    name_markers.to_tokens(&mut token_stream);
+
+    Ok(token_stream)
+}
+
+fn get_reflected_variants(
+    data_enum: &DataEnum,
+) -> Result<Vec<ReflectedVariant<'_>>, GetHelperAttrError> {
+    let mut variants = vec![];
+    // Variants without an explicit discriminant follow the last explicit one.
+    let mut last_explicit: Option<&Expr> = None;
+    let mut offset = 0usize;
+    for variant in data_enum.variants.iter() {
+        check_unique_aliases(&variant.fields)?;
+        if let Some((_, explicit)) = &variant.discriminant {
+            last_explicit = Some(explicit);
+            offset = 0;
+        }
+
+        let offset_lit = LitInt::new(&format!("{}i128", offset), variant.ident.span());
+        let discriminant: Expr = match last_explicit {
+            Some(explicit) if offset == 0 => parse_quote!((#explicit) as i128),
+            Some(explicit) => parse_quote!((#explicit) as i128 + #offset_lit),
+            None => parse_quote!(#offset_lit),
+        };
+        offset += 1;
+
+        let mut fields = vec![];
+        for (index, field) in variant.fields.iter().enumerate() {
+            let attr = get_preflect_attr(field)?;
+            if !attr.ignore() {
+                let field_name = get_field_name(field, index);
+                let alias_lits = attr
+                    .aliases()
+                    .map(|alias| LitStr::new(alias, field_name.span()));
+                let names = std::iter::once(field_name.clone())
+                    .chain(alias_lits)
+                    .collect();
+
+                fields.push(ReflectedField {
+                    index,
+                    field,
+                    nested: attr.nested(),
+                    wrapper: Wrapper::of(&attr),
+                    redacted: attr.redacted(),
+                    names,
+                });
+            }
+        }
+
+        let ident = &variant.ident;
+        let name = LitStr::new(&ident.unraw().to_string(), ident.span());
+        variants.push(ReflectedVariant {
+            ident,
+            name,
+            discriminant,
+            fields,
+        });
+    }
+
+    Ok(variants)
+}
+
+fn impl_has_fields_for_variants(
+    enum_ident: &Ident,
+    generics: &Generics,
+    variants: &[ReflectedVariant],
+) -> ItemImpl {
+    let field_tys = variants
+        .iter()
+        .flat_map(|variant| variant.fields.iter().map(|field| &field.field.ty));
+    let generics = add_static_bounds(generics, field_tys);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let mut names_arms = Vec::<Arm>::new();
+    let mut info_arms = Vec::<Arm>::new();
+    let mut info_start = 0;
+    for variant in variants {
+        let variant_ident = variant.ident;
+        let field_names = variant.fields.iter().map(|field| &field.names[0]);
+        names_arms.push(parse_quote! {
+            Self::#variant_ident { .. } => &[#(#field_names),*]
+        });
+
+        let info_end = info_start + variant.fields.len();
+        info_arms.push(parse_quote! {
+            Self::#variant_ident { .. } => &field_info[#info_start..#info_end]
+        });
+        info_start = info_end;
+    }
+
+    let field_match = make_variant_match(variants);
+    let info_method = make_get_field_info(variants);
+    let visit_method = make_visit_fields_mut(variants);
+    let nested_methods = make_nested_methods(variants);
+    let collection_methods = make_wrapper_methods(variants, Wrapper::Collection);
+    let pointer_methods = make_wrapper_methods(variants, Wrapper::Pointer);
+    parse_quote! {
+        impl #impl_generics ::preflect::fields::BaseHasFields for #enum_ident #ty_generics #where_clause {
+            fn field_names(&self) -> &'static [&'static str] {
+                match *self {
+                    #(#names_arms,)*
+                }
+            }
+
+            fn field_info(&self) -> &'static [::preflect::fields::FieldInfo] {
+                let field_info = <Self as ::preflect::fields::HasFieldInfo>::FIELD_INFO;
+                match *self {
+                    #(#info_arms,)*
+                }
+            }
+
+            fn active_variant(&self) -> core::option::Option<&'static str> {
+                core::option::Option::Some(<Self as ::preflect::fields::HasVariants>::variant_name(self))
+            }
+
+            #info_method
+
+            fn get_field_raw<'s, 'n>(&'s self, name: &'n str) -> preflect::fields::FieldAccessResult<'n, &'s dyn core::any::Any> {
+                #field_match
+            }
+
+            fn get_field_mut_raw<'s, 'n>(&'s mut self, name: &'n str) -> preflect::fields::FieldAccessResult<'n, &'s mut dyn core::any::Any> {
+                #field_match
+            }
+
+            #visit_method
+
+            #(#nested_methods)*
+
+            #(#collection_methods)*
+
+            #(#pointer_methods)*
+        }
+    }
+}
+
+/// Builds a match which looks up a field of the active variant by name. The
+/// same match serves both the immutable and the mutable accessor since the
+/// fields are bound by reference.
+fn make_variant_match(variants: &[ReflectedVariant]) -> ExprMatch {
+    let variant_arms = variants.iter().map(|variant| {
+        let variant_ident = variant.ident;
+        let field_members = variant
+            .fields
+            .iter()
+            .map(|field| get_field_member(field.field, field.index));
+        let field_bindings = variant
+            .fields
+            .iter()
+            .map(|field| format_ident!("field_{}", field.index))
+            .collect::<Vec<_>>();
+        let field_patterns = variant.fields.iter().map(|field| {
+            let names = &field.names;
+            quote!(#(#names)|*)
+        });
+
+        // The names are listed since the fields of the variant are still
+        // borrowed, which prevents calling `field_names`.
+        let field_names = variant.fields.iter().map(|field| &field.names[0]);
+
+        let inactive_names = get_inactive_names(variants, variant);
+        let mut inactive_arms = Vec::<Arm>::new();
+        if !inactive_names.is_empty() {
+            inactive_arms.push(parse_quote! {
+                #(#inactive_names)|* => core::result::Result::Err(preflect::fields::FieldAccessError::inactive_variant(name))
+            });
+        }
+
+        let arm: Arm = parse_quote! {
+            Self::#variant_ident { #(#field_members: #field_bindings,)* .. } => match name {
+                #(#field_patterns => core::result::Result::Ok(#field_bindings),)*
+                #(#inactive_arms,)*
+                _ => core::result::Result::Err(preflect::fields::FieldAccessError::missing_field(name, &[#(#field_names),*]))
+            }
+        };
+
+        arm
+    });
+
+    let match_target: Expr = if variants.is_empty() {
+        parse_quote!(*self)
+    } else {
+        parse_quote!(self)
+    };
+
+    parse_quote! {
+        match #match_target {
+            #(#variant_arms,)*
+        }
+    }
+}
+
+/// Builds the method which looks up the descriptor of a field of the active
+/// variant by its name or one of its aliases.
+fn make_get_field_info(variants: &[ReflectedVariant]) -> ImplItemMethod {
+    let mut info_start = 0;
+    let variant_arms = variants
+        .iter()
+        .map(|variant| -> Arm {
+            let variant_ident = variant.ident;
+            let field_patterns = variant.fields.iter().map(|field| {
+                let names = &field.names;
+                quote!(#(#names)|*)
+            });
+            let info_indices = (info_start..info_start + variant.fields.len()).collect::<Vec<_>>();
+            info_start += variant.fields.len();
+
+            let field_names = variant.fields.iter().map(|field| &field.names[0]);
+            let inactive_names = get_inactive_names(variants, variant);
+            let mut inactive_arms = Vec::<Arm>::new();
+            if !inactive_names.is_empty() {
+                inactive_arms.push(parse_quote! {
+                    #(#inactive_names)|* => core::result::Result::Err(preflect::fields::FieldAccessError::inactive_variant(name))
+                });
+            }
+
+            parse_quote! {
+                Self::#variant_ident { .. } => match name {
+                    #(#field_patterns => core::result::Result::Ok(&<Self as ::preflect::fields::HasFieldInfo>::FIELD_INFO[#info_indices]),)*
+                    #(#inactive_arms,)*
+                    _ => core::result::Result::Err(preflect::fields::FieldAccessError::missing_field(name, &[#(#field_names),*]))
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+
+    parse_quote! {
+        fn get_field_info<'n>(&self, name: &'n str) -> preflect::fields::FieldAccessResult<'n, &'static ::preflect::fields::FieldInfo> {
+            match *self {
+                #(#variant_arms,)*
+            }
+        }
+    }
+}
+
+/// Builds the method which borrows every reflected field of the active variant
+/// mutably at once, so the references passed to the visitor never alias.
+fn make_visit_fields_mut(variants: &[ReflectedVariant]) -> ImplItemMethod {
+    let has_fields = variants.iter().any(|variant| !variant.fields.is_empty());
+    if !has_fields {
+        return parse_quote! {
+            fn visit_fields_mut<'s>(&'s mut self, visit: &mut dyn FnMut(&'static str, &'s mut dyn core::any::Any)) {
+                let _ = visit;
+            }
+        };
+    }
+
+    let variant_arms = variants.iter().map(|variant| -> Arm {
+        let variant_ident = variant.ident;
+        let field_members = variant
+            .fields
+            .iter()
+            .map(|field| get_field_member(field.field, field.index));
+        let field_bindings = variant
+            .fields
+            .iter()
+            .map(|field| format_ident!("field_{}", field.index))
+            .collect::<Vec<_>>();
+        let field_names = variant.fields.iter().map(|field| &field.names[0]);
+        parse_quote! {
+            Self::#variant_ident { #(#field_members: #field_bindings,)* .. } => {
+                #(visit(#field_names, #field_bindings);)*
+            }
+        }
+    });
+
+    parse_quote! {
+        fn visit_fields_mut<'s>(&'s mut self, visit: &mut dyn FnMut(&'static str, &'s mut dyn core::any::Any)) {
+            match self {
+                #(#variant_arms,)*
+            }
+        }
+    }
+}
+
+/// Builds the methods which access the fields marked with
+/// `#[preflect(nested)]`. If there are no such fields, the default
+/// implementations are used.
+fn make_nested_methods(variants: &[ReflectedVariant]) -> Vec<ImplItemMethod> {
+    let has_nested = variants
+        .iter()
+        .any(|variant| variant.fields.iter().any(|field| field.nested));
+    if !has_nested {
+        return vec![];
+    }
+
+    let not_nested = format_ident!("not_nested");
+    let variant_arms = make_selected_arms(
+        variants,
+        |field| field.nested,
+        &not_nested,
+        |_, binding| parse_quote!(#binding),
+    );
+
+    vec![
+        parse_quote! {
+            fn get_nested_raw<'s, 'n>(&'s self, name: &'n str) -> preflect::fields::FieldAccessResult<'n, &'s dyn ::preflect::fields::Reflect> {
+                match self {
+                    #(#variant_arms,)*
+                }
+            }
+        },
+        parse_quote! {
+            fn get_nested_mut_raw<'s, 'n>(&'s mut self, name: &'n str) -> preflect::fields::FieldAccessResult<'n, &'s mut dyn ::preflect::fields::Reflect> {
+                match self {
+                    #(#variant_arms,)*
+                }
+            }
+        },
+    ]
+}
+
+/// Builds the methods which access the fields marked with
+/// `#[preflect(collection)]` or `#[preflect(pointer)]`, depending on the
+/// wrapper. If there are no such fields, the default implementations are
+/// used.
+fn make_wrapper_methods(variants: &[ReflectedVariant], wrapper: Wrapper) -> Vec<ImplItemMethod> {
+    let is_selected = |field: &ReflectedField| field.wrapper == Some(wrapper);
+    let has_wrapper = variants
+        .iter()
+        .any(|variant| variant.fields.iter().any(is_selected));
+    if !has_wrapper {
+        return vec![];
+    }
+
+    let not_wrapper = wrapper.not_wrapper();
+    let variant_arms = make_selected_arms(variants, is_selected, &not_wrapper, |field, binding| {
+        wrapper.make_ref(&field.field.ty, parse_quote!(#binding))
+    });
+    let variant_mut_arms =
+        make_selected_arms(variants, is_selected, &not_wrapper, |field, binding| {
+            wrapper.make_mut(&field.field.ty, parse_quote!(#binding))
+        });
+
+    wrapper.make_methods(
+        parse_quote! {
+            match self {
+                #(#variant_arms,)*
+            }
+        },
+        parse_quote! {
+            match self {
+                #(#variant_mut_arms,)*
+            }
+        },
+    )
+}
+
+/// Builds the arms of a match on the variants which access the fields chosen
+/// by `is_selected`. The value returned for a selected field is built by
+/// `make_value` from the binding of the field, and the other fields of the
+/// variant produce the error named by `not_selected`.
+fn make_selected_arms(
+    variants: &[ReflectedVariant],
+    is_selected: impl Fn(&ReflectedField) -> bool,
+    not_selected: &Ident,
+    make_value: impl Fn(&ReflectedField, &Ident) -> Expr,
+) -> Vec<Arm> {
+    variants
+        .iter()
+        .map(|variant| {
+            let variant_ident = variant.ident;
+            let selected_fields = variant
+                .fields
+                .iter()
+                .filter(|field| is_selected(field))
+                .collect::<Vec<_>>();
+            let field_members = selected_fields
+                .iter()
+                .map(|field| get_field_member(field.field, field.index));
+            let field_bindings = selected_fields
+                .iter()
+                .map(|field| format_ident!("field_{}", field.index))
+                .collect::<Vec<_>>();
+            let field_patterns = selected_fields.iter().map(|field| {
+                let names = &field.names;
+                quote!(#(#names)|*)
+            });
+            let field_values = selected_fields
+                .iter()
+                .zip(&field_bindings)
+                .map(|(field, binding)| make_value(field, binding));
+
+            let mut other_arms = Vec::<Arm>::new();
+            let other_names = variant
+                .fields
+                .iter()
+                .filter(|field| !is_selected(field))
+                .flat_map(|field| field.names.iter())
+                .collect::<Vec<_>>();
+            if !other_names.is_empty() {
+                other_arms.push(parse_quote! {
+                    #(#other_names)|* => core::result::Result::Err(preflect::fields::FieldAccessError::#not_selected(name))
+                });
+            }
+
+            let field_names = variant.fields.iter().map(|field| &field.names[0]);
+            let inactive_names = get_inactive_names(variants, variant);
+            if !inactive_names.is_empty() {
+                other_arms.push(parse_quote! {
+                    #(#inactive_names)|* => core::result::Result::Err(preflect::fields::FieldAccessError::inactive_variant(name))
+                });
+            }
+
+            parse_quote! {
+                Self::#variant_ident { #(#field_members: #field_bindings,)* .. } => match name {
+                    #(#field_patterns => core::result::Result::Ok(#field_values),)*
+                    #(#other_arms,)*
+                    _ => core::result::Result::Err(preflect::fields::FieldAccessError::missing_field(name, &[#(#field_names),*]))
+                }
+            }
+        })
+        .collect()
+}
+
+/// Gets the names of the fields which only exist on variants other than
+/// `variant`.
+fn get_inactive_names<'a>(
+    variants: &'a [ReflectedVariant],
+    variant: &ReflectedVariant,
+) -> Vec<&'a LitStr> {
+    let mut inactive_names = Vec::<&LitStr>::new();
+    for other in variants.iter().filter(|other| other.ident != variant.ident) {
+        for name in other.fields.iter().flat_map(|field| field.names.iter()) {
+            let is_active = variant
+                .fields
+                .iter()
+                .any(|field| field.names.iter().any(|own| own.value() == name.value()));
+            let is_listed = inactive_names
+                .iter()
+                .any(|listed| listed.value() == name.value());
+            if !is_active && !is_listed {
+                inactive_names.push(name);
+            }
+        }
+    }
+
+    inactive_names
+}
+
+fn impl_has_field_info_for_variants(
+    enum_ident: &Ident,
+    generics: &Generics,
+    variants: &[ReflectedVariant],
+) -> ItemImpl {
+    let field_tys = variants
+        .iter()
+        .flat_map(|variant| variant.fields.iter().map(|field| &field.field.ty));
+    let generics = add_static_bounds(generics, field_tys);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let field_info = variants.iter().flat_map(|variant| {
+        variant.fields.iter().map(move |field| {
+            make_variant_field_info(&variant.name, field.field, field.index, field.redacted)
+        })
+    });
+    let accepted_names = get_accepted_names(variants);
+
+    parse_quote! {
+        impl #impl_generics ::preflect::fields::HasFieldInfo for #enum_ident #ty_generics #where_clause {
+            const FIELD_INFO: &'static [::preflect::fields::FieldInfo] = &[#(#field_info),*];
+            const ACCEPTED_NAMES: &'static [&'static str] = &[#(#accepted_names),*];
+        }
+    }
+}
+
+/// Gets the names and aliases of the fields of every variant, without
+/// duplicates.
+fn get_accepted_names(variants: &[ReflectedVariant]) -> Vec<LitStr> {
+    get_unique_names(
+        variants
+            .iter()
+            .flat_map(|variant| variant.fields.iter().flat_map(|field| &field.names)),
+    )
+}
+
+fn impl_has_variants_for_variants(
+    enum_ident: &Ident,
+    generics: &Generics,
+    variants: &[ReflectedVariant],
+) -> ItemImpl {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let variant_idents = variants
+        .iter()
+        .map(|variant| variant.ident)
+        .collect::<Vec<_>>();
+    let variant_names = variants
+        .iter()
+        .map(|variant| &variant.name)
+        .collect::<Vec<_>>();
+    let variant_indices = 0..variants.len();
+    let variant_discriminants = variants.iter().map(|variant| &variant.discriminant);
+    let variant_names_body = quote!(&[#(#variant_names),*]);
+
+    parse_quote! {
+        impl #impl_generics ::preflect::fields::HasVariants for #enum_ident #ty_generics #where_clause {
+            fn variant_names(&self) -> &'static [&'static str] {
+                #variant_names_body
+            }
+
+            fn variant_name(&self) -> &'static str {
+                match *self {
+                    #(Self::#variant_idents { .. } => #variant_names,)*
+                }
+            }
+
+            fn variant_index(&self) -> usize {
+                match *self {
+                    #(Self::#variant_idents { .. } => #variant_indices,)*
+                }
+            }
+
+            fn variant_discriminant(&self) -> i128 {
+                match *self {
+                    #(Self::#variant_idents { .. } => #variant_discriminants,)*
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "alloc")]
+    use crate::field_utils::make_element_cast;
+    #[cfg(feature = "alloc")]
+    use crate::field_utils::make_target_cast;
+    use pretty_assertions::assert_eq;
+    use syn::Data;
+    use syn::DeriveInput;
+
+    #[test]
+    fn make_variant_match_test() {
+        let data_enum = make_data_enum();
+        let variants = get_reflected_variants(&data_enum).unwrap();
+
+        let actual = make_variant_match(&variants);
+        let expected: ExprMatch = parse_quote! {
+            match self {
+                Self::Circle { radius: field_0, .. } => match name {
+                    "radius" | "r" => core::result::Result::Ok(field_0),
+                    "0" => core::result::Result::Err(preflect::fields::FieldAccessError::inactive_variant(name)),
+                    _ => core::result::Result::Err(preflect::fields::FieldAccessError::missing_field(name, &["radius"]))
+                },
+                Self::Square { 0: field_0, .. } => match name {
+                    "0" => core::result::Result::Ok(field_0),
+                    "radius" | "r" => core::result::Result::Err(preflect::fields::FieldAccessError::inactive_variant(name)),
+                    _ => core::result::Result::Err(preflect::fields::FieldAccessError::missing_field(name, &["0"]))
+                },
+                Self::Empty { .. } => match name {
+                    "radius" | "r" | "0" => core::result::Result::Err(preflect::fields::FieldAccessError::inactive_variant(name)),
+                    _ => core::result::Result::Err(preflect::fields::FieldAccessError::missing_field(name, &[]))
+                },
+            }
+        };
+
+        assert_eq!(actual, expected)
+    }
+
+    #[test]
+    fn get_reflected_variants_discriminant_test() {
+        let derive_input: DeriveInput = parse_quote! {
+            enum Status {
+                Pending,
+                Active = 5,
+                Paused,
+                Closed = -1,
+            }
+        };
+
+        let data_enum = match derive_input.data {
+            Data::Enum(data_enum) => data_enum,
+            _ => unreachable!(),
+        };
+
+        let variants = get_reflected_variants(&data_enum).unwrap();
+        let actual = variants
+            .iter()
+            .map(|variant| variant.discriminant.clone())
+            .collect::<Vec<_>>();
+        let expected: Vec<Expr> = vec![
+            parse_quote!(0i128),
+            parse_quote!((5) as i128),
+            parse_quote!((5) as i128 + 1i128),
+            parse_quote!((-1) as i128),
+        ];
+
+        assert_eq!(actual, expected)
+    }
+
+    #[test]
+    fn make_get_field_info_test() {
+        let data_enum = make_data_enum();
+        let variants = get_reflected_variants(&data_enum).unwrap();
+
+        let actual = make_get_field_info(&variants);
+        let expected: ImplItemMethod = parse_quote! {
+            fn get_field_info<'n>(&self, name: &'n str) -> preflect::fields::FieldAccessResult<'n, &'static ::preflect::fields::FieldInfo> {
+                match *self {
+                    Self::Circle { .. } => match name {
+                        "radius" | "r" => core::result::Result::Ok(&<Self as ::preflect::fields::HasFieldInfo>::FIELD_INFO[0usize]),
+                        "0" => core::result::Result::Err(preflect::fields::FieldAccessError::inactive_variant(name)),
+                        _ => core::result::Result::Err(preflect::fields::FieldAccessError::missing_field(name, &["radius"]))
+                    },
+                    Self::Square { .. } => match name {
+                        "0" => core::result::Result::Ok(&<Self as ::preflect::fields::HasFieldInfo>::FIELD_INFO[1usize]),
+                        "radius" | "r" => core::result::Result::Err(preflect::fields::FieldAccessError::inactive_variant(name)),
+                        _ => core::result::Result::Err(preflect::fields::FieldAccessError::missing_field(name, &["0"]))
+                    },
+                    Self::Empty { .. } => match name {
+                        "radius" | "r" | "0" => core::result::Result::Err(preflect::fields::FieldAccessError::inactive_variant(name)),
+                        _ => core::result::Result::Err(preflect::fields::FieldAccessError::missing_field(name, &[]))
+                    },
+                }
+            }
+        };
+
+        assert_eq!(actual, expected)
+    }
+
+    #[test]
+    fn make_visit_fields_mut_test() {
+        let data_enum = make_data_enum();
+        let variants = get_reflected_variants(&data_enum).unwrap();
+
+        let actual = make_visit_fields_mut(&variants);
+        let expected: ImplItemMethod = parse_quote! {
+            fn visit_fields_mut<'s>(&'s mut self, visit: &mut dyn FnMut(&'static str, &'s mut dyn core::any::Any)) {
+                match self {
+                    Self::Circle { radius: field_0, .. } => {
+                        visit("radius", field_0);
+                    },
+                    Self::Square { 0: field_0, .. } => {
+                        visit("0", field_0);
+                    },
+                    Self::Empty { .. } => {},
+                }
+            }
+        };
+
+        assert_eq!(actual, expected)
+    }
+
+    #[test]
+    fn impl_has_variants_for_variants_test() {
+        let enum_ident = Ident::new("Shape", proc_macro2::Span::call_site());
+        let data_enum = make_data_enum();
+        let variants = get_reflected_variants(&data_enum).unwrap();
+
+        let actual = impl_has_variants_for_variants(&enum_ident, &Generics::default(), &variants);
+        let expected: ItemImpl = parse_quote! {
+            impl ::preflect::fields::HasVariants for Shape {
+                fn variant_names(&self) -> &'static [&'static str] {
+                    &["Circle", "Square", "Empty"]
+                }
+
+                fn variant_name(&self) -> &'static str {
+                    match *self {
+                        Self::Circle { .. } => "Circle",
+                        Self::Square { .. } => "Square",
+                        Self::Empty { .. } => "Empty",
+                    }
+                }
+
+                fn variant_index(&self) -> usize {
+                    match *self {
+                        Self::Circle { .. } => 0usize,
+                        Self::Square { .. } => 1usize,
+                        Self::Empty { .. } => 2usize,
+                    }
+                }
+
+                fn variant_discriminant(&self) -> i128 {
+                    match *self {
+                        Self::Circle { .. } => 0i128,
+                        Self::Square { .. } => 1i128,
+                        Self::Empty { .. } => 2i128,
+                    }
+                }
+            }
+        };
+
+        assert_eq!(actual, expected)
+    }
+
+    #[test]
+    fn make_nested_methods_test() {
+        let derive_input: DeriveInput = parse_quote! {
+            enum Location {
+                Home {
+                    #[preflect(nested)]
+                    address: Address,
+                    floor: u8,
+                },
+                Unknown,
+            }
+        };
+
+        let data_enum = match derive_input.data {
+            Data::Enum(data_enum) => data_enum,
+            _ => unreachable!(),
+        };
+
+        let variants = get_reflected_variants(&data_enum).unwrap();
+        let actual = make_nested_methods(&variants);
+        let expected: ImplItemMethod = parse_quote! {
+            fn get_nested_raw<'s, 'n>(&'s self, name: &'n str) -> preflect::fields::FieldAccessResult<'n, &'s dyn ::preflect::fields::Reflect> {
+                match self {
+                    Self::Home { address: field_0, .. } => match name {
+                        "address" => core::result::Result::Ok(field_0),
+                        "floor" => core::result::Result::Err(preflect::fields::FieldAccessError::not_nested(name)),
+                        _ => core::result::Result::Err(preflect::fields::FieldAccessError::missing_field(name, &["address", "floor"]))
+                    },
+                    Self::Unknown { .. } => match name {
+                        "address" | "floor" => core::result::Result::Err(preflect::fields::FieldAccessError::inactive_variant(name)),
+                        _ => core::result::Result::Err(preflect::fields::FieldAccessError::missing_field(name, &[]))
+                    },
+                }
+            }
+        };
+
+        assert_eq!(2, actual.len());
+        assert_eq!(actual[0], expected)
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn make_collection_methods_test() {
+        let derive_input: DeriveInput = parse_quote! {
+            enum Order {
+                Open {
+                    #[preflect(collection)]
+                    items: Vec<Item>,
+                    total: u32,
+                },
+                Cancelled,
+            }
+        };
+
+        let data_enum = match derive_input.data {
+            Data::Enum(data_enum) => data_enum,
+            _ => unreachable!(),
+        };
+
+        let variants = get_reflected_variants(&data_enum).unwrap();
+        let actual = make_wrapper_methods(&variants, Wrapper::Collection);
+        let element_cast = make_element_cast(&parse_quote!(Vec<Item>));
+        let expected: ImplItemMethod = parse_quote! {
+            fn get_collection_mut_raw<'s, 'n>(&'s mut self, name: &'n str) -> preflect::fields::FieldAccessResult<'n, ::preflect::collections::CollectionMut<'s>> {
+                match self {
+                    Self::Open { items: field_0, .. } => match name {
+                        "items" => core::result::Result::Ok(::preflect::collections::CollectionMut::new(field_0, #element_cast)),
+                        "total" => core::result::Result::Err(preflect::fields::FieldAccessError::not_collection(name)),
+                        _ => core::result::Result::Err(preflect::fields::FieldAccessError::missing_field(name, &["items", "total"]))
+                    },
+                    Self::Cancelled { .. } => match name {
+                        "items" | "total" => core::result::Result::Err(preflect::fields::FieldAccessError::inactive_variant(name)),
+                        _ => core::result::Result::Err(preflect::fields::FieldAccessError::missing_field(name, &[]))
+                    },
+                }
+            }
+        };
+
+        assert_eq!(2, actual.len());
+        assert_eq!(actual[1], expected)
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn make_pointer_methods_test() {
+        let derive_input: DeriveInput = parse_quote! {
+            enum Source {
+                Shared(#[preflect(pointer)] Rc<Config>),
+                Inline(Config),
+            }
+        };
+
+        let data_enum = match derive_input.data {
+            Data::Enum(data_enum) => data_enum,
+            _ => unreachable!(),
+        };
+
+        let variants = get_reflected_variants(&data_enum).unwrap();
+        let actual = make_wrapper_methods(&variants, Wrapper::Pointer);
+        let target_cast = make_target_cast(&parse_quote!(Rc<Config>));
+        let expected: ImplItemMethod = parse_quote! {
+            fn get_pointer_raw<'s, 'n>(&'s self, name: &'n str) -> preflect::fields::FieldAccessResult<'n, ::preflect::pointers::PointerRef<'s>> {
+                match self {
+                    Self::Shared { 0: field_0, .. } => match name {
+                        "0" => core::result::Result::Ok(::preflect::pointers::PointerRef::new(field_0, #target_cast)),
+                        _ => core::result::Result::Err(preflect::fields::FieldAccessError::missing_field(name, &["0"]))
+                    },
+                    Self::Inline { .. } => match name {
+                        "0" => core::result::Result::Err(preflect::fields::FieldAccessError::not_pointer(name)),
+                        _ => core::result::Result::Err(preflect::fields::FieldAccessError::missing_field(name, &["0"]))
+                    },
+                }
+            }
+        };
+
+        assert_eq!(2, actual.len());
+        assert_eq!(actual[0], expected)
+    }
+
+    fn make_data_enum() -> DataEnum {
+        let derive_input: DeriveInput = parse_quote! {
+            enum Shape {
+                Circle {
+                    #[preflect(alias = ["r"])]
+                    radius: f32,
+                    #[preflect(ignore)]
+                    label: String,
+                },
+                Square(f32),
+                Empty,
+            }
+        };
+
+        match derive_input.data {
+            Data::Enum(data_enum) => data_enum,
+            _ => unreachable!(),
+        }
+    }
+}
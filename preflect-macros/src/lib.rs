@@ -1,10 +1,17 @@
 #![warn(clippy::all)]
 
 mod attr_utils;
+mod checked_access;
 mod drop;
 mod errors;
+mod field_info;
+mod field_name;
+mod field_utils;
+mod from_variant;
+mod generics_utils;
 mod has_field;
 mod has_fields;
+mod has_fields_enum;
 
 use proc_macro::TokenStream;
 use quote::ToTokens;
@@ -29,11 +36,48 @@ pub fn has_field_derive(input_stream: TokenStream) -> TokenStream {
     }
 }
 
-#[proc_macro_derive(PartialDrop)]
+#[proc_macro]
+pub fn field(input_stream: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input_stream as self::checked_access::FieldInput);
+    match input {
+        self::checked_access::FieldInput::Name(name) => {
+            self::field_name::make_field_name_type(name.name())
+                .into_token_stream()
+                .into()
+        }
+        self::checked_access::FieldInput::Access(access) => {
+            self::checked_access::make_checked_field_access(&access)
+                .into_token_stream()
+                .into()
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[proc_macro_derive(FromVariant, attributes(preflect))]
+pub fn from_variant_derive(input_stream: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input_stream as DeriveInput);
+    match self::from_variant::from_variant_derive_impl(&derive_input) {
+        Ok(output) => output.into_token_stream().into(),
+        Err(err) => err.into(),
+    }
+}
+
+#[cfg(feature = "partial-drop")]
+#[proc_macro_derive(PartialDrop, attributes(preflect))]
 pub fn partial_drop_derive(input_stream: TokenStream) -> TokenStream {
     let derive_input = parse_macro_input!(input_stream as DeriveInput);
     match self::drop::partial_drop_derive_impl(&derive_input) {
         Ok(output) => output.into_token_stream().into(),
-        Err(err) => err.to_compile_error().into(),
+        Err(err) => err.into(),
     }
 }
+
+#[cfg(feature = "partial-drop")]
+#[proc_macro]
+pub fn partial_drop(input_stream: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input_stream as self::checked_access::PartialDropInput);
+    self::checked_access::make_checked_partial_drop(&input)
+        .into_token_stream()
+        .into()
+}
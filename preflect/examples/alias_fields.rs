@@ -0,0 +1,43 @@
+use preflect::fields::field;
+use preflect::fields::BaseHasFields;
+use preflect::fields::HasField;
+use preflect::fields::HasFields;
+use preflect::fields::HasNamedField;
+use std::any::type_name;
+
+#[derive(HasFields, HasField)]
+struct User {
+    id: u32,
+    #[preflect(alias = ["username", "login"])]
+    name: String,
+}
+
+fn main() {
+    let mut user = User {
+        id: 1,
+        name: "Jim".into(),
+    };
+
+    // Aliases resolve to the renamed field, but are not enumerated.
+    assert_eq!(&["id", "name"], user.field_names());
+    append_to_login(&mut user, "my");
+
+    // Errors name the type of the field that an alias resolves to.
+    assert_eq!("name", user.get_field_info("login").unwrap().name());
+    let error = HasFields::get_field::<u32>(&user, "username").unwrap_err();
+    assert_eq!(Some(type_name::<String>()), error.actual_type());
+    println!("{}", error);
+
+    println!("name = {}", read_username(&user));
+}
+
+fn append_to_login(obj: &mut impl HasFields, suffix: &str) {
+    let login: &mut String = obj.get_field_mut("login").unwrap();
+    login.push_str(suffix);
+}
+
+fn read_username(
+    has_username: &impl HasNamedField<field!(username), FieldType = String>,
+) -> &String {
+    has_username.get_field()
+}
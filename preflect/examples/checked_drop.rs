@@ -0,0 +1,67 @@
+use preflect::drop::CheckedPartialDrop;
+use preflect::drop::PartialDrop;
+use preflect::drop::PartialDropError;
+use preflect::fields::HasFields;
+use std::any::type_name;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
+#[derive(HasFields, PartialDrop)]
+struct Connection {
+    socket: LogDrop<u16>,
+    #[preflect(alias = ["buf"])]
+    buffer: LogDrop<Vec<u8>>,
+    peer: LogDrop<String>,
+    #[preflect(ignore)]
+    stats: LogDrop<u64>,
+}
+
+struct LogDrop<T>(T);
+
+/// The number of `LogDrop` values which have been dropped.
+static DROPS: AtomicUsize = AtomicUsize::new(0);
+
+impl<T> Drop for LogDrop<T> {
+    fn drop(&mut self) {
+        println!("Dropping {}", type_name::<T>());
+        DROPS.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+fn main() {
+    let connection = Connection {
+        socket: LogDrop(8080),
+        buffer: LogDrop(vec![]),
+        peer: LogDrop("localhost".into()),
+        stats: LogDrop(0),
+    };
+
+    let mut connection = CheckedPartialDrop::new(connection);
+
+    // A typo is reported instead of silently dropping the field.
+    let error = connection.drop_all_fields_except(&["sockt"]).unwrap_err();
+    println!("{}", error);
+    assert_eq!(Ok(false), connection.is_dropped("socket"));
+
+    println!("Partial dropping...");
+    connection.drop_all_fields_except(&["socket"]).unwrap();
+    assert_eq!(Ok(true), connection.is_dropped("buf"));
+    assert_eq!(2, DROPS.load(Ordering::SeqCst));
+
+    // Calling it again would drop the same fields twice.
+    let error = connection.drop_all_fields_except(&["socket"]).unwrap_err();
+    assert_eq!(PartialDropError::AlreadyDropped("buffer"), error);
+    println!("{}", error);
+
+    let socket: LogDrop<u16> = connection.take_field("socket").unwrap();
+    assert_eq!(8080, socket.0);
+    assert!(connection.drop_field("socket").is_err());
+
+    println!("Dropping the moved field...");
+    drop(socket);
+    drop(connection);
+
+    // The ignored field is dropped along with the rest of the value.
+    assert_eq!(4, DROPS.load(Ordering::SeqCst));
+    println!("Done!");
+}
@@ -0,0 +1,39 @@
+use preflect::drop::partial_drop;
+use preflect::drop::PartialDrop;
+use preflect::fields::field;
+use preflect::fields::HasFields;
+use std::mem::ManuallyDrop;
+use std::ptr::read;
+
+#[derive(Debug, HasFields, PartialDrop)]
+struct User {
+    id: u32,
+    #[preflect(alias = ["username"])]
+    name: String,
+    email: String,
+}
+
+fn main() {
+    let mut user = User {
+        id: 1,
+        name: "Jim".into(),
+        email: "jim@example.com".into(),
+    };
+
+    let id: &u32 = field!(user.id).unwrap();
+    assert_eq!(1, *id);
+
+    let name: &mut String = field!(mut user.username).unwrap();
+    name.push_str("my");
+    assert_eq!("Jimmy", user.name);
+
+    // Would fail to type check with "`User` has no reflected field with this
+    // name", pointing at `nmae` and noting that "the accepted names are `id`,
+    // `name`, `username`, `email`".
+    // let name: &String = field!(user.nmae).unwrap();
+
+    let mut user = ManuallyDrop::new(user);
+    unsafe { partial_drop!(*user, id, name) };
+    let name = unsafe { read(&user.name) };
+    println!("{} kept their name", name);
+}
@@ -0,0 +1,196 @@
+use preflect::collections::CollectionErrorKind;
+use preflect::collections::CollectionKind;
+use preflect::collections::CollectionKindMut;
+use preflect::fields::BaseHasFields;
+use preflect::fields::FieldAccessErrorKind;
+use preflect::fields::HasFields;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+
+#[derive(Debug, HasFields)]
+struct Item {
+    name: String,
+    quantity: u32,
+}
+
+#[derive(Debug, HasFields)]
+struct Discount {
+    code: String,
+    percent: u8,
+}
+
+#[derive(Debug, HasFields)]
+struct Order {
+    id: u32,
+    #[preflect(collection)]
+    items: Vec<Item>,
+    #[preflect(collection)]
+    tags: [&'static str; 2],
+    #[preflect(collection)]
+    discount: Option<Discount>,
+    #[preflect(collection)]
+    notes: BTreeMap<String, String>,
+    #[preflect(collection)]
+    stock: HashMap<u32, Item>,
+}
+
+#[derive(Debug, HasFields)]
+enum Cart {
+    Open {
+        #[preflect(collection)]
+        items: Vec<Item>,
+    },
+    Empty,
+}
+
+#[derive(Debug, HasFields)]
+struct Batch<T> {
+    #[preflect(collection)]
+    values: Vec<T>,
+}
+
+fn main() {
+    let mut order = Order {
+        id: 7,
+        items: vec![
+            Item {
+                name: "Pen".into(),
+                quantity: 2,
+            },
+            Item {
+                name: "Ink".into(),
+                quantity: 1,
+            },
+        ],
+        tags: ["office", "gift"],
+        discount: None,
+        notes: BTreeMap::new(),
+        stock: HashMap::new(),
+    };
+
+    // Paths continue into the elements of lists.
+    let name: &String = order.get_path("items.1.name").unwrap();
+    assert_eq!("Ink", name);
+    *order.get_path_mut::<u32>("items.0.quantity").unwrap() = 5;
+    assert_eq!(5, order.items[0].quantity);
+    assert_eq!(Ok(&"gift"), order.get_path::<&str>("tags.1"));
+
+    // Elements can be added and removed through the type-erased collection.
+    let mut items = order.get_collection_mut_raw("items").unwrap();
+    if let CollectionKindMut::List(list) = items.kind() {
+        let item = Item {
+            name: "Paper".into(),
+            quantity: 10,
+        };
+        list.push(Box::new(item)).unwrap();
+        let error = list.push(Box::new("Stapler")).unwrap_err();
+        assert!(matches!(
+            error.kind(),
+            CollectionErrorKind::InvalidType { .. }
+        ));
+        println!("{}", error);
+    }
+
+    assert_eq!(Ok(&10), order.get_path::<u32>("items.2.quantity"));
+
+    let tags = order.get_collection_mut_raw("tags").unwrap();
+    if let CollectionKindMut::List(list) = tags.into_kind() {
+        let error = list.remove(0).unwrap_err();
+        assert_eq!(CollectionErrorKind::FixedSize, error.kind());
+    }
+
+    // The value of an option is its element at index 0.
+    let error = order.get_path::<u8>("discount.0.percent").unwrap_err();
+    assert_eq!("0", error.segment());
+    assert_eq!(FieldAccessErrorKind::MissingField, error.error().kind());
+    println!("{}", error);
+
+    if let CollectionKindMut::Option(option) = order
+        .get_collection_mut_raw("discount")
+        .unwrap()
+        .into_kind()
+    {
+        let discount = Discount {
+            code: "SPRING".into(),
+            percent: 15,
+        };
+        option.insert(Box::new(discount)).unwrap();
+    }
+
+    assert_eq!(Ok(&15), order.get_path::<u8>("discount.0.percent"));
+
+    // Maps are addressed by their keys.
+    if let CollectionKindMut::Map(map) = order.get_collection_mut_raw("notes").unwrap().into_kind()
+    {
+        map.insert(
+            Box::new(String::from("gift")),
+            Box::new(String::from("Wrap it")),
+        )
+        .unwrap();
+
+        // An entry which can not be inserted is returned whole.
+        let error = map
+            .insert(Box::new("rush"), Box::new(String::from("Today")))
+            .unwrap_err();
+        println!("{}", error);
+        let (key, value) = error.into_entry().unwrap();
+        assert_eq!(Some(&"rush"), key.downcast_ref::<&str>());
+        assert_eq!(Some(&"Today".to_string()), value.downcast_ref::<String>());
+    }
+
+    order.stock.insert(
+        42,
+        Item {
+            name: "Pen".into(),
+            quantity: 100,
+        },
+    );
+    assert_eq!(
+        Ok(&"Wrap it".to_string()),
+        order.get_path::<String>("notes.gift")
+    );
+    *order.get_path_mut::<u32>("stock.42.quantity").unwrap() -= 1;
+    assert_eq!(99, order.stock[&42].quantity);
+
+    // Only fields marked as collections can be traversed this way, and paths
+    // only continue into elements which can be reflected.
+    let error = order.get_path::<u32>("id.0").unwrap_err();
+    assert_eq!(FieldAccessErrorKind::NotNested, error.error().kind());
+    let error = order.get_path::<u32>("notes.gift.len").unwrap_err();
+    assert_eq!("gift", error.segment());
+    assert_eq!(FieldAccessErrorKind::NotNested, error.error().kind());
+    let error = order.get_collection_raw("id").unwrap_err();
+    assert_eq!(FieldAccessErrorKind::NotCollection, error.kind());
+    println!("{}", error);
+
+    // The collection can also be inspected as a whole.
+    if let CollectionKind::List(list) = order.get_collection_raw("items").unwrap().kind() {
+        println!(
+            "{} elements of type {}",
+            list.len(),
+            list.element_type_name()
+        );
+        for item in list.iter() {
+            println!("{:?}", item.downcast_ref::<Item>().unwrap());
+        }
+    }
+
+    let cart = Cart::Open { items: order.items };
+    assert_eq!(
+        Ok(&"Paper".to_string()),
+        cart.get_path::<String>("items.2.name")
+    );
+    let error = Cart::Empty.get_collection_raw("items").unwrap_err();
+    assert_eq!(FieldAccessErrorKind::InactiveVariant, error.kind());
+
+    // Elements of a generic type are not assumed to be reflected.
+    let batch = Batch {
+        values: vec![1u8, 2, 3],
+    };
+    assert_eq!(Ok(&3), batch.get_path::<u8>("values.2"));
+    assert!(!batch
+        .get_collection_raw("values")
+        .unwrap()
+        .elements()
+        .is_reflect());
+}
@@ -0,0 +1,56 @@
+use preflect::drop::Destructure;
+use preflect::drop::PartialDrop;
+use preflect::fields::field;
+use preflect::fields::HasField;
+use std::any::type_name;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
+#[derive(HasField, PartialDrop)]
+struct Request {
+    method: LogDrop<&'static str>,
+    #[preflect(alias = ["url"])]
+    path: LogDrop<String>,
+    headers: LogDrop<Vec<String>>,
+    body: LogDrop<Vec<u8>>,
+    #[preflect(ignore)]
+    trace: LogDrop<u64>,
+}
+
+#[derive(Debug)]
+struct LogDrop<T>(T);
+
+/// The number of `LogDrop` values which have been dropped.
+static DROPS: AtomicUsize = AtomicUsize::new(0);
+
+impl<T> Drop for LogDrop<T> {
+    fn drop(&mut self) {
+        println!("Dropping {}", type_name::<T>());
+        DROPS.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+fn main() {
+    let request = Request {
+        method: LogDrop("GET"),
+        path: LogDrop("/index.html".into()),
+        headers: LogDrop(vec!["Accept: */*".into()]),
+        body: LogDrop(vec![]),
+        trace: LogDrop(7),
+    };
+
+    println!("Destructuring...");
+    let (path, method) = request.destructure::<(field!(url), field!(method))>();
+    assert_eq!("/index.html", path.0);
+    assert_eq!("GET", method.0);
+
+    // The remaining fields are dropped, including the ignored one.
+    assert_eq!(3, DROPS.load(Ordering::SeqCst));
+
+    println!("Dropping the moved fields...");
+    drop(path);
+    drop(method);
+    assert_eq!(5, DROPS.load(Ordering::SeqCst));
+
+    println!("Done!");
+}
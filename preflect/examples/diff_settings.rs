@@ -0,0 +1,86 @@
+use preflect::diff::diff;
+use preflect::diff::Comparators;
+use preflect::diff::DiffError;
+use preflect::diff::FieldChange;
+use preflect::diff::ValueComparator;
+use preflect::fields::HasFields;
+
+#[derive(Clone, Debug, Default, HasFields, PartialEq)]
+struct Settings {
+    theme: String,
+    #[preflect(redact)]
+    api_key: String,
+    tags: Vec<String>,
+    #[preflect(nested)]
+    network: Network,
+}
+
+#[derive(Clone, Debug, Default, HasFields, PartialEq)]
+struct Network {
+    host: String,
+    port: u16,
+}
+
+const COMPARATORS: &[ValueComparator] = &[ValueComparator::partial_eq::<Vec<String>>()];
+
+fn describe(change: &FieldChange<'_>) -> String {
+    if change.is_redacted() {
+        return format!("{} changed", change.path());
+    }
+
+    match (
+        change.downcast_old::<String>(),
+        change.downcast_new::<String>(),
+    ) {
+        (Some(old), Some(new)) => format!("{}: {:?} -> {:?}", change.path(), old, new),
+        _ => match (change.downcast_old::<u16>(), change.downcast_new::<u16>()) {
+            (Some(old), Some(new)) => format!("{}: {} -> {}", change.path(), old, new),
+            _ => format!("{} ({}) changed", change.path(), change.type_name()),
+        },
+    }
+}
+
+fn main() {
+    let old = Settings {
+        theme: "light".into(),
+        api_key: "secret".into(),
+        tags: vec!["beta".into()],
+        network: Network {
+            host: "localhost".into(),
+            port: 8080,
+        },
+    };
+
+    // `Vec<String>` has no default comparator, so it must be registered.
+    let error = diff(&old, &old).unwrap_err();
+    println!("{}", error);
+    assert_eq!(
+        DiffError::Unsupported {
+            path: "tags".into(),
+            type_name: "alloc::vec::Vec<alloc::string::String>",
+        },
+        error
+    );
+
+    let mut new = old.clone();
+    new.theme = "dark".into();
+    new.api_key = "rotated".into();
+    new.tags.push("canary".into());
+    new.network.port = 9090;
+
+    let comparators = Comparators::new(COMPARATORS);
+    assert!(comparators.diff(&old, &old).unwrap().is_empty());
+
+    let changes = comparators.diff(&old, &new).unwrap();
+    for change in &changes {
+        println!("{}", describe(change));
+    }
+
+    let paths: Vec<_> = changes.iter().map(FieldChange::path).collect();
+    assert_eq!(vec!["theme", "api_key", "tags", "network.port"], paths);
+    assert_eq!(Some(&8080), changes[3].downcast_old::<u16>());
+    assert_eq!(
+        Ok(&9090),
+        new.get_path::<u16>(changes[3].path()).map_err(|_| ())
+    );
+}
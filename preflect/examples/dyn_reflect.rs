@@ -0,0 +1,53 @@
+use preflect::fields::HasFields;
+use preflect::fields::Reflect;
+
+#[derive(HasFields)]
+struct Window {
+    title: String,
+    width: u32,
+}
+
+#[derive(HasFields)]
+struct Button {
+    label: String,
+    enabled: bool,
+}
+
+fn main() {
+    let mut plugins: Vec<Box<dyn Reflect>> = vec![
+        Box::new(Window {
+            title: "Main".into(),
+            width: 640,
+        }),
+        Box::new(Button {
+            label: "OK".into(),
+            enabled: true,
+        }),
+    ];
+
+    for plugin in plugins.iter_mut() {
+        if let Ok(label) = plugin.get_field_mut::<String>("label") {
+            label.push('!');
+        }
+    }
+
+    for plugin in plugins.iter() {
+        inspect(plugin.as_ref());
+    }
+
+    let button = plugins[1].downcast_ref::<Button>().unwrap();
+    assert_eq!("OK!", button.label);
+    assert!(button.enabled);
+    assert!(plugins[0].is::<Window>());
+}
+
+fn inspect(plugin: &dyn Reflect) {
+    println!("{} {:?}", plugin.type_name(), plugin.field_names());
+    for info in plugin.field_info() {
+        println!("  {} : {}", info.name(), info.type_name());
+    }
+
+    if let Ok(title) = plugin.get_field::<String>("title") {
+        println!("  title = {}", title);
+    }
+}
@@ -0,0 +1,84 @@
+use preflect::fields::DynamicLayout;
+use preflect::fields::DynamicStruct;
+use preflect::fields::FieldAccessErrorKind;
+use preflect::fields::HasFields;
+
+#[derive(Debug, Default, HasFields, PartialEq)]
+struct User {
+    name: String,
+    age: u32,
+    #[preflect(nested)]
+    address: Address,
+}
+
+#[derive(Clone, Debug, Default, HasFields, PartialEq)]
+struct Address {
+    city: String,
+}
+
+fn main() {
+    let address_layout = DynamicLayout::builder("Address")
+        .field::<String>("city")
+        .build();
+    let mut address = DynamicStruct::new(address_layout);
+    *address.get_field_mut::<String>("city").unwrap() = "Oslo".into();
+
+    let user_layout = DynamicLayout::builder("User")
+        .field::<String>("name")
+        .field::<u32>("age")
+        .nested("address", address_layout)
+        .build();
+
+    // Building an identical layout again reuses the existing one.
+    let same_layout = DynamicLayout::builder("User")
+        .field::<String>("name")
+        .field::<u32>("age")
+        .nested("address", address_layout)
+        .build();
+    assert!(std::ptr::eq(user_layout, same_layout));
+
+    let mut record = DynamicStruct::new(user_layout);
+    *record.get_field_mut::<String>("name").unwrap() = "Ada".into();
+    *record.get_field_mut::<u32>("age").unwrap() = 36;
+    record
+        .set_field("address", Box::new(address.clone()))
+        .unwrap();
+    println!("{:?}", record);
+
+    // Nested dynamic structs are accessed like nested fields.
+    assert_eq!(
+        Ok(&"Oslo".to_string()),
+        record.get_path::<String>("address.city")
+    );
+
+    let mut user = User::default();
+    record.apply_to(&mut user).unwrap();
+    assert_eq!(
+        User {
+            name: "Ada".into(),
+            age: 36,
+            address: Address {
+                city: "Oslo".into()
+            },
+        },
+        user
+    );
+
+    user.age = 37;
+    let mut copy = record.clone();
+    copy.clone_from_reflect(&user).unwrap();
+    assert_eq!(Ok(&37), copy.get_field::<u32>("age"));
+    assert_eq!(Ok(&36), record.get_field::<u32>("age"));
+
+    // Fields which do not match are rejected without modifying the target.
+    let other_layout = DynamicLayout::builder("User")
+        .field::<String>("name")
+        .field::<u64>("age")
+        .build();
+    assert!(!std::ptr::eq(user_layout, other_layout));
+    let other = DynamicStruct::new(other_layout);
+    let error = other.apply_to(&mut user).unwrap_err();
+    println!("{}", error);
+    assert_eq!(FieldAccessErrorKind::InvalidType, error.kind());
+    assert_eq!("Ada", user.name);
+}
@@ -0,0 +1,89 @@
+use preflect::fields::FieldAccessErrorKind;
+use preflect::fields::FieldMap;
+use preflect::fields::FromVariant;
+use std::any::Any;
+
+#[derive(Debug, FromVariant, PartialEq)]
+enum Command {
+    Quit,
+    Move {
+        x: i32,
+        #[preflect(alias = ["vertical"])]
+        y: i32,
+    },
+    Say(String),
+    Wait {
+        seconds: u32,
+        #[preflect(ignore)]
+        interrupted: bool,
+    },
+}
+
+fn main() {
+    let command = Command::from_variant(
+        "Move",
+        fields(vec![("x", Box::new(1i32)), ("vertical", Box::new(2i32))]),
+    );
+    assert_eq!(Command::Move { x: 1, y: 2 }, command.unwrap());
+
+    let command = Command::from_variant("Say", fields(vec![("0", Box::new(String::from("hi")))]));
+    assert_eq!(Command::Say("hi".into()), command.unwrap());
+
+    let command = Command::from_variant("Wait", fields(vec![("seconds", Box::new(5u32))]));
+    assert_eq!(
+        Command::Wait {
+            seconds: 5,
+            interrupted: false
+        },
+        command.unwrap()
+    );
+
+    assert_eq!(
+        Command::Quit,
+        Command::from_variant("Quit", FieldMap::new()).unwrap()
+    );
+
+    let errors = [
+        Command::from_variant("Jump", FieldMap::new()),
+        Command::from_variant("Move", fields(vec![("x", Box::new(1i32))])),
+        Command::from_variant(
+            "Move",
+            fields(vec![("x", Box::new(1i32)), ("y", Box::new(2u8))]),
+        ),
+        Command::from_variant("Quit", fields(vec![("force", Box::new(true))])),
+    ];
+
+    let kinds = errors
+        .iter()
+        .map(|result| result.as_ref().unwrap_err().kind())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        vec![
+            FieldAccessErrorKind::MissingVariant,
+            FieldAccessErrorKind::MissingField,
+            FieldAccessErrorKind::InvalidType,
+            FieldAccessErrorKind::UnknownField,
+        ],
+        kinds
+    );
+
+    for result in errors.iter() {
+        println!("{}", result.as_ref().unwrap_err());
+    }
+
+    // The unknown field is named, and rejected values are handed back.
+    let [_, _, mismatched, unknown] = errors;
+    let unknown = unknown.unwrap_err();
+    assert_eq!(Some("force"), unknown.field());
+    assert_eq!(Some(&true), unknown.into_value().unwrap().downcast_ref());
+    let mismatched = mismatched.unwrap_err();
+    assert_eq!(Some("y"), mismatched.error().name());
+    assert_eq!(Some(&2u8), mismatched.into_value().unwrap().downcast_ref());
+}
+
+fn fields(values: Vec<(&str, Box<dyn Any>)>) -> FieldMap {
+    values
+        .into_iter()
+        .map(|(name, value)| (name.to_string(), value))
+        .collect()
+}
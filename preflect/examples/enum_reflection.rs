@@ -0,0 +1,81 @@
+use preflect::fields::BaseHasFields;
+use preflect::fields::FieldAccessError;
+use preflect::fields::FieldAccessErrorKind;
+use preflect::fields::HasFieldInfo;
+use preflect::fields::HasFields;
+use preflect::fields::HasVariants;
+
+#[derive(Debug, HasFields)]
+enum Connection {
+    Idle,
+    Connecting { attempt: u32 },
+    Connected { address: String, latency_ms: u32 },
+    Failed(String),
+}
+
+#[derive(Debug, HasFields)]
+enum Priority {
+    Low = 1,
+    Normal,
+    High = 10,
+}
+
+fn main() {
+    let mut connection = Connection::Connecting { attempt: 1 };
+
+    assert_eq!(
+        &["Idle", "Connecting", "Connected", "Failed"],
+        connection.variant_names()
+    );
+    assert_eq!("Connecting", connection.variant_name());
+    assert_eq!(1, connection.variant_index());
+    assert_eq!(&["attempt"], connection.field_names());
+
+    retry(&mut connection);
+    assert_eq!(Ok(&2), read_attempt(&connection));
+
+    connection = Connection::Connected {
+        address: "127.0.0.1".into(),
+        latency_ms: 12,
+    };
+
+    assert_eq!(
+        FieldAccessErrorKind::InactiveVariant,
+        read_attempt(&connection).unwrap_err().kind()
+    );
+
+    for (name, value) in connection.fields() {
+        println!("{}::{} = {:?}", connection.variant_name(), name, value);
+    }
+
+    for info in Connection::FIELD_INFO {
+        println!(
+            "{}::{} : {}",
+            info.variant().unwrap(),
+            info.name(),
+            info.type_name()
+        );
+    }
+
+    println!("{:?}", Connection::Failed("timed out".into()).field_names());
+    println!("{:?}", Connection::Idle.field_info());
+
+    // Discriminants follow the declaration, unlike the position of a variant.
+    assert_eq!(0, Connection::Idle.variant_discriminant());
+    assert_eq!(1, Priority::Low.variant_discriminant());
+    assert_eq!(
+        Priority::Normal as i128,
+        Priority::Normal.variant_discriminant()
+    );
+    assert_eq!(1, Priority::Normal.variant_index());
+    assert_eq!(10, Priority::High.variant_discriminant());
+}
+
+fn retry(obj: &mut impl HasFields) {
+    let attempt: &mut u32 = obj.get_field_mut("attempt").unwrap();
+    *attempt += 1;
+}
+
+fn read_attempt(obj: &impl HasFields) -> Result<&u32, FieldAccessError<'static>> {
+    obj.get_field("attempt")
+}
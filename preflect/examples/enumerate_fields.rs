@@ -0,0 +1,43 @@
+use preflect::fields::BaseHasFields;
+use preflect::fields::HasFields;
+
+#[derive(HasFields)]
+struct User {
+    id: u32,
+    name: String,
+    #[allow(dead_code)]
+    #[preflect(ignore)]
+    password: String,
+}
+
+fn main() {
+    let mut user = User {
+        id: 1,
+        name: "Jim".into(),
+        password: "hunter2".into(),
+    };
+
+    assert_eq!(&["id", "name"], user.field_names());
+
+    // Every field is borrowed at once, so the fields can be modified together.
+    user.visit_fields_mut(&mut |name, value| {
+        if let Some(value) = value.downcast_mut::<String>() {
+            value.make_ascii_uppercase();
+        }
+
+        println!("Visited {}", name);
+    });
+    assert_eq!("JIM", user.name);
+
+    print_fields(&user);
+}
+
+fn print_fields(obj: &impl HasFields) {
+    for (name, value) in obj.fields() {
+        if let Some(value) = value.downcast_ref::<u32>() {
+            println!("{} = {}", name, value);
+        } else if let Some(value) = value.downcast_ref::<String>() {
+            println!("{} = {:?}", name, value);
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use preflect::fields::FieldAccessErrorKind;
+use preflect::fields::HasFields;
+
+#[derive(Debug, HasFields)]
+struct Address {
+    city: String,
+    zip: u32,
+}
+
+#[derive(Debug, HasFields)]
+struct Config {
+    name: String,
+    retries: u8,
+    #[preflect(nested)]
+    address: Address,
+}
+
+fn main() {
+    let config = Config {
+        name: "server".into(),
+        retries: 3,
+        address: Address {
+            city: "Springfield".into(),
+            zip: 12345,
+        },
+    };
+
+    let error = config.get_field::<u8>("retires").unwrap_err();
+    assert_eq!(FieldAccessErrorKind::MissingField, error.kind());
+    assert_eq!(Some("retires"), error.name());
+    assert_eq!(Some("retries"), error.suggestion());
+    println!("{}", error);
+
+    let error = config.get_field::<u32>("retries").unwrap_err();
+    assert_eq!(FieldAccessErrorKind::InvalidType, error.kind());
+    assert_eq!(Some("u32"), error.expected_type());
+    assert_eq!(Some("u8"), error.actual_type());
+    println!("{}", error);
+
+    let error = config.get_field::<String>("timeout").unwrap_err();
+    assert_eq!(None, error.suggestion());
+    println!("{}", error);
+
+    let error = config.get_path::<String>("address.zip").unwrap_err();
+    assert_eq!(Some("u32"), error.error().actual_type());
+    println!("{}", error);
+}
@@ -0,0 +1,60 @@
+use preflect::fields::field;
+use preflect::fields::BaseHasNamedField;
+use preflect::fields::HasField;
+use preflect::fields::HasFieldInfo;
+use preflect::fields::HasFields;
+use preflect::fields::Visibility;
+use std::any::TypeId;
+
+#[derive(HasFields, HasField)]
+#[repr(C)]
+pub struct User {
+    pub id: u32,
+    pub(crate) name: String,
+    #[allow(dead_code)]
+    #[preflect(ignore)]
+    password: String,
+    age: u8,
+}
+
+fn main() {
+    for info in User::FIELD_INFO {
+        println!(
+            "#{} {} : {} (size = {}, align = {}, offset = {:?}, {:?})",
+            info.index(),
+            info.name(),
+            info.type_name(),
+            info.size(),
+            info.align(),
+            info.offset(),
+            info.visibility(),
+        );
+    }
+
+    let age = &User::FIELD_INFO[2];
+    assert_eq!("age", age.name());
+    assert_eq!(3, age.index());
+    assert_eq!(TypeId::of::<u8>(), age.type_id());
+    assert_eq!(Visibility::Inherited, age.visibility());
+
+    let name = <User as BaseHasNamedField<field!(name)>>::INFO;
+    assert_eq!(Visibility::Crate, name.visibility());
+    assert_eq!(Some(std::mem::align_of::<String>()), name.offset());
+
+    print_schema(&User {
+        id: 1,
+        name: "Jim".into(),
+        password: "hunter2".into(),
+        age: 18,
+    });
+}
+
+fn print_schema(obj: &impl HasFields) {
+    let names = obj
+        .field_info()
+        .iter()
+        .map(|info| info.name())
+        .collect::<Vec<_>>();
+
+    println!("fields = {}", names.join(", "));
+}
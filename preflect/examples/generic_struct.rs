@@ -0,0 +1,63 @@
+use preflect::drop::PartialDrop;
+use preflect::fields::field;
+use preflect::fields::HasField;
+use preflect::fields::HasFieldInfo;
+use preflect::fields::HasFields;
+use preflect::fields::HasNamedField;
+use std::fmt::Debug;
+use std::mem::ManuallyDrop;
+
+#[derive(Debug, HasFields, HasField, PartialDrop)]
+struct Wrapper<T: Debug> {
+    inner: T,
+    label: &'static str,
+}
+
+#[derive(HasFields, HasField, PartialDrop)]
+struct Buffer<'a, T, const N: usize>
+where
+    T: Copy,
+{
+    items: [T; N],
+    #[preflect(ignore)]
+    #[allow(dead_code)]
+    source: &'a str,
+}
+
+fn main() {
+    let mut wrapper = Wrapper {
+        inner: 5u8,
+        label: "five",
+    };
+
+    double_inner(&mut wrapper);
+    assert_eq!(&10, read_inner(&wrapper));
+    println!("{:?}", wrapper);
+
+    let source = String::from("local");
+    let buffer = Buffer {
+        items: [1u16, 2, 3],
+        source: &source,
+    };
+
+    assert_eq!(Ok(&[1u16, 2, 3]), HasFields::get_field(&buffer, "items"));
+    assert_eq!(6, Buffer::<u16, 3>::FIELD_INFO[0].size());
+
+    let mut wrapper = ManuallyDrop::new(Wrapper {
+        inner: String::from("partially dropped"),
+        label: "string",
+    });
+
+    unsafe {
+        wrapper.drop_all_fields_except(&["label"]);
+    }
+}
+
+fn double_inner(obj: &mut impl HasFields) {
+    let inner: &mut u8 = obj.get_field_mut("inner").unwrap();
+    *inner *= 2;
+}
+
+fn read_inner<T>(has_inner: &impl HasNamedField<field!(inner), FieldType = T>) -> &T {
+    has_inner.get_field()
+}
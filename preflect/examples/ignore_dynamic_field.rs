@@ -1,4 +1,4 @@
-use preflect::fields::FieldAccessError;
+use preflect::fields::FieldAccessErrorKind;
 use preflect::fields::FieldAccessResult;
 use preflect::fields::HasFields;
 
@@ -20,13 +20,16 @@ fn main() {
     let name_result = read_name(&user);
 
     assert_eq!(Ok(&1), id_result);
-    assert_eq!(Err(FieldAccessError::MissingField), name_result);
+    assert_eq!(
+        FieldAccessErrorKind::MissingField,
+        name_result.unwrap_err().kind()
+    );
 }
 
-fn read_id(has_id: &impl HasFields) -> FieldAccessResult<&u32> {
+fn read_id(has_id: &impl HasFields) -> FieldAccessResult<'static, &u32> {
     has_id.get_field("id")
 }
 
-fn read_name(has_name: &impl HasFields) -> FieldAccessResult<&String> {
+fn read_name(has_name: &impl HasFields) -> FieldAccessResult<'static, &String> {
     has_name.get_field("name")
 }
@@ -0,0 +1,173 @@
+use preflect::fields::HasFields;
+use preflect::json::JsonError;
+use preflect::json::JsonPatch;
+use preflect::json::JsonPointer;
+use preflect::json::PatchOperation;
+use preflect::value::Converters;
+use preflect::value::FromValue;
+use preflect::value::ToValue;
+use preflect::value::Value;
+use preflect::value::ValueConverter;
+use preflect::value::ValueResult;
+use serde_json::json;
+use std::collections::BTreeMap;
+
+#[derive(Clone, Debug, Default, HasFields, PartialEq)]
+struct Order {
+    id: u64,
+    #[preflect(nested)]
+    customer: Customer,
+    items: Vec<Item>,
+    tags: Vec<String>,
+}
+
+#[derive(Clone, Debug, Default, HasFields, PartialEq)]
+struct Customer {
+    name: String,
+    #[preflect(nested)]
+    address: Address,
+}
+
+#[derive(Clone, Debug, Default, HasFields, PartialEq)]
+struct Address {
+    city: String,
+}
+
+#[derive(Clone, Debug, Default, HasFields, PartialEq)]
+struct Item {
+    name: String,
+    quantity: u32,
+}
+
+// Elements of a list are converted through reflection as well.
+impl ToValue for Item {
+    fn to_value(&self) -> Value {
+        Value::from_reflect(self).unwrap_or(Value::Null)
+    }
+}
+
+impl FromValue for Item {
+    fn from_value(value: &Value) -> ValueResult<Self> {
+        value.to_reflect()
+    }
+}
+
+const CONVERTERS: &[ValueConverter] = &[
+    ValueConverter::of::<Vec<Item>>(),
+    ValueConverter::of::<Vec<String>>(),
+];
+
+fn to_value(json: &serde_json::Value) -> Value {
+    match json {
+        serde_json::Value::Null => Value::Null,
+        serde_json::Value::Bool(value) => Value::Bool(*value),
+        serde_json::Value::Number(number) => match (number.as_u64(), number.as_i64()) {
+            (Some(value), _) => Value::UInt(value),
+            (_, Some(value)) => Value::Int(value),
+            _ => Value::Float(number.as_f64().unwrap()),
+        },
+        serde_json::Value::String(value) => Value::String(value.clone()),
+        serde_json::Value::Array(elements) => Value::List(elements.iter().map(to_value).collect()),
+        serde_json::Value::Object(entries) => Value::Map(
+            entries
+                .iter()
+                .map(|(key, value)| (key.clone(), to_value(value)))
+                .collect::<BTreeMap<_, _>>(),
+        ),
+    }
+}
+
+fn to_patch(json: &serde_json::Value) -> JsonPatch {
+    let pointer = |operation: &serde_json::Value, key: &str| {
+        JsonPointer::parse(operation[key].as_str().unwrap()).unwrap()
+    };
+
+    let operations = json.as_array().unwrap().iter().map(|operation| {
+        let path = pointer(operation, "path");
+        let value = to_value(&operation["value"]);
+        match operation["op"].as_str().unwrap() {
+            "add" => PatchOperation::Add { path, value },
+            "remove" => PatchOperation::Remove { path },
+            "replace" => PatchOperation::Replace { path, value },
+            "move" => PatchOperation::Move {
+                from: pointer(operation, "from"),
+                path,
+            },
+            "copy" => PatchOperation::Copy {
+                from: pointer(operation, "from"),
+                path,
+            },
+            "test" => PatchOperation::Test { path, value },
+            op => panic!("Unknown operation '{}'", op),
+        }
+    });
+
+    JsonPatch::from(operations.collect::<Vec<_>>())
+}
+
+fn main() {
+    let mut order = Order {
+        id: 7,
+        customer: Customer {
+            name: "Ada".into(),
+            address: Address {
+                city: "London".into(),
+            },
+        },
+        items: vec![Item {
+            name: "ink".into(),
+            quantity: 1,
+        }],
+        tags: vec![],
+    };
+    let original = order.clone();
+
+    let city = JsonPointer::parse("/customer/address/city").unwrap();
+    assert_eq!(Ok(Value::String("London".into())), city.get(&order));
+
+    let converters = Converters::new(CONVERTERS);
+    let patch = to_patch(&json!([
+        { "op": "test", "path": "/id", "value": 7 },
+        { "op": "replace", "path": "/customer/address/city", "value": "Oslo" },
+        { "op": "add", "path": "/items/-", "value": { "name": "pen", "quantity": 2 } },
+        { "op": "replace", "path": "/items/0/quantity", "value": 3 },
+        { "op": "copy", "from": "/items/1/name", "path": "/tags/-" },
+        { "op": "add", "path": "/tags/0", "value": "rush" }
+    ]));
+    converters.apply_patch(&patch, &mut order).unwrap();
+    assert_eq!("Oslo", order.customer.address.city);
+    assert_eq!(3, order.items[0].quantity);
+    assert_eq!("pen", order.items[1].name);
+    assert_eq!(vec!["rush".to_string(), "pen".to_string()], order.tags);
+
+    // A failing operation restores the fields which were already modified.
+    let patched = order.clone();
+    let patch = to_patch(&json!([
+        { "op": "replace", "path": "/items/0/quantity", "value": 10 },
+        { "op": "remove", "path": "/id" }
+    ]));
+    let error = converters.apply_patch(&patch, &mut order).unwrap_err();
+    println!("{}", error);
+    assert!(matches!(error, JsonError::CannotRemove { .. }));
+    assert_eq!(patched, order);
+
+    let patch = to_patch(&json!([
+        { "op": "replace", "path": "/customer/name", "value": "Grace" },
+        { "op": "replace", "path": "/id", "value": "seven" }
+    ]));
+    let error = converters.apply_patch(&patch, &mut order).unwrap_err();
+    println!("{}", error);
+    assert_eq!(patched, order);
+
+    // The default converters do not handle `Vec<Item>`.
+    let error = JsonPointer::parse("/items/0").unwrap().get(&order);
+    println!("{}", error.unwrap_err());
+
+    // The empty pointer replaces the whole value.
+    let original = converters.to_value(&original).unwrap();
+    converters
+        .set_pointer(&mut order, &JsonPointer::root(), original.clone())
+        .unwrap();
+    assert_eq!(original, converters.to_value(&order).unwrap());
+    assert_eq!(Ok(Value::String("London".into())), city.get(&order));
+}
@@ -0,0 +1,70 @@
+use preflect::fields::field;
+use preflect::fields::BaseHasNamedField;
+use preflect::fields::HasField;
+use preflect::fields::HasNamedField;
+
+#[derive(Debug, HasField)]
+struct User {
+    id: u32,
+    name: String,
+    #[preflect(ignore)]
+    #[allow(dead_code)]
+    password: String,
+}
+
+#[derive(Debug, HasField)]
+struct Group {
+    id: u32,
+    r#type: String,
+    members: Vec<User>,
+}
+
+fn main() {
+    let mut user = User {
+        id: 1,
+        name: "Jim".into(),
+        password: "hunter2".into(),
+    };
+
+    change_name_to_bob(&mut user);
+    assert_eq!("Bob", user.name);
+
+    let group = Group {
+        id: 7,
+        r#type: "admins".into(),
+        members: vec![user],
+    };
+
+    println!("user id = {}", read_id(&group.members[0]));
+    println!("group id = {}", read_id(&group));
+    println!("{} members", group.members.len());
+
+    let info = <User as BaseHasNamedField<field!(name)>>::INFO;
+    assert_eq!("name", info.name());
+
+    // Raw identifiers are named without their `r#` prefix.
+    let info = <Group as BaseHasNamedField<field!(r#type)>>::INFO;
+    assert_eq!("type", info.name());
+    let kind: &String = HasNamedField::<field!("type")>::get_field(&group);
+    assert_eq!("admins", kind);
+
+    // Would fail to compile:
+    // read_password(&group.members[0]);
+}
+
+fn change_name_to_bob(obj: &mut impl HasNamedField<field!(name), FieldType = String>) {
+    let name: &mut String = obj.get_field_mut();
+    name.clear();
+    name.push_str("Bob");
+}
+
+fn read_id(has_id: &impl HasNamedField<field!(id), FieldType = u32>) -> &u32 {
+    has_id.get_field()
+}
+
+#[allow(dead_code)]
+fn read_password(
+    has_password: &impl HasNamedField<field!(password), FieldType = String>,
+) -> &String {
+    has_password.get_field()
+}
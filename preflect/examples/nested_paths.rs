@@ -0,0 +1,70 @@
+use preflect::fields::FieldAccessErrorKind;
+use preflect::fields::HasFields;
+
+#[derive(Debug, HasFields)]
+struct Address {
+    city: String,
+    zip: u32,
+}
+
+#[derive(Debug, HasFields)]
+struct Person {
+    name: String,
+    #[preflect(nested, alias = "home")]
+    address: Address,
+}
+
+#[derive(Debug, HasFields)]
+enum Contact {
+    Mail {
+        #[preflect(nested)]
+        address: Address,
+    },
+    Phone {
+        number: String,
+    },
+}
+
+fn main() {
+    let mut person = Person {
+        name: "Alice".into(),
+        address: Address {
+            city: "Springfield".into(),
+            zip: 12345,
+        },
+    };
+
+    let city: &String = person.get_path("address.city").unwrap();
+    assert_eq!("Springfield", city);
+
+    // The aliases of a nested field can also be traversed.
+    assert_eq!(Ok(&12345), person.get_path::<u32>("home.zip"));
+
+    let zip: &mut u32 = person.get_path_mut("address.zip").unwrap();
+    *zip = 54321;
+    assert_eq!(54321, person.address.zip);
+
+    let error = person.get_path::<String>("address.street").unwrap_err();
+    assert_eq!("street", error.segment());
+    assert_eq!(1, error.index());
+    assert_eq!(FieldAccessErrorKind::MissingField, error.error().kind());
+    println!("{}", error);
+
+    let error = person.get_path::<String>("name.first").unwrap_err();
+    assert_eq!("name", error.segment());
+    assert_eq!(FieldAccessErrorKind::NotNested, error.error().kind());
+    println!("{}", error);
+
+    let contact = Contact::Mail {
+        address: person.address,
+    };
+    let zip: &u32 = contact.get_path("address.zip").unwrap();
+    assert_eq!(54321, *zip);
+
+    let contact = Contact::Phone {
+        number: "555-0100".into(),
+    };
+    let error = contact.get_path::<String>("address.city").unwrap_err();
+    assert_eq!(FieldAccessErrorKind::InactiveVariant, error.error().kind());
+    println!("{}", error);
+}
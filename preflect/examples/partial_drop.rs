@@ -8,6 +8,15 @@ struct Example {
     third: LogDrop<i32>,
 }
 
+#[derive(PartialDrop)]
+enum Shape {
+    Circle {
+        radius: LogDrop<f64>,
+        label: LogDrop<&'static str>,
+    },
+    Polygon(LogDrop<Vec<(f64, f64)>>, #[preflect(ignore)] LogDrop<u8>),
+}
+
 struct LogDrop<T>(T);
 
 impl<T> Drop for LogDrop<T> {
@@ -35,5 +44,26 @@ fn main() {
         ::core::ptr::drop_in_place(first);
     }
 
+    println!("Partial dropping the active variant...");
+    let mut circle = ::core::mem::ManuallyDrop::new(Shape::Circle {
+        radius: LogDrop(1.0),
+        label: LogDrop("unit"),
+    });
+    unsafe {
+        circle.drop_all_fields_except(&["label"]);
+        if let Shape::Circle { label, .. } = &mut *circle {
+            ::core::ptr::drop_in_place(label);
+        }
+    }
+
+    println!("Partial dropping a tuple variant...");
+    let mut polygon = ::core::mem::ManuallyDrop::new(Shape::Polygon(LogDrop(vec![]), LogDrop(3)));
+    unsafe {
+        // The ignored field is never dropped by name, so it is dropped
+        // separately.
+        polygon.drop_all_fields_except(&[]);
+        polygon.drop_ignored_fields();
+    }
+
     println!("Done!");
 }
@@ -0,0 +1,71 @@
+use preflect::fields::FieldAccessErrorKind;
+use preflect::fields::HasFields;
+use preflect::fields::Patch;
+use std::any::Any;
+
+#[derive(Clone, Debug, Default, HasFields, PartialEq)]
+struct Settings {
+    theme: String,
+    font_size: u32,
+    #[preflect(nested)]
+    network: Network,
+}
+
+#[derive(Clone, Debug, Default, HasFields, PartialEq)]
+struct Network {
+    host: String,
+    port: u16,
+}
+
+fn main() {
+    let mut settings = Settings {
+        theme: "light".into(),
+        font_size: 12,
+        network: Network {
+            host: "localhost".into(),
+            port: 8080,
+        },
+    };
+    let original = settings.clone();
+
+    // Updates which arrive as data. The last one has the wrong type, so none
+    // of them are applied.
+    let updates: Vec<(&str, Box<dyn Any>)> = vec![
+        ("theme", Box::new(String::from("dark"))),
+        ("network.port", Box::new(9090u16)),
+        ("font_size", Box::new("huge")),
+    ];
+    let patch: Patch = updates.into_iter().collect();
+    println!("{:?}", patch);
+
+    let error = patch.apply(&mut settings).unwrap_err();
+    println!("{}", error);
+    assert_eq!("font_size", error.path());
+    assert_eq!(
+        FieldAccessErrorKind::InvalidType,
+        error.error().error().kind()
+    );
+    assert_eq!(original, settings);
+
+    // Fix the rejected update and apply the patch again.
+    let mut patch = error.into_patch();
+    patch.set("font_size", Box::new(14u32));
+    let undo = patch.apply(&mut settings).unwrap();
+    assert_eq!("dark", settings.theme);
+    assert_eq!(14, settings.font_size);
+    assert_eq!(9090, settings.network.port);
+
+    // Unknown paths are reported with the segment which does not exist.
+    let error = Patch::new()
+        .with("network.hots", String::from("example.com"))
+        .apply(&mut settings)
+        .unwrap_err();
+    println!("{}", error);
+    assert_eq!("hots", error.error().segment());
+
+    // The inverse patch restores the previous values.
+    let redo = undo.apply(&mut settings).unwrap();
+    assert_eq!(original, settings);
+    redo.apply(&mut settings).unwrap();
+    assert_eq!(9090, settings.network.port);
+}
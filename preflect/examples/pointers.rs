@@ -0,0 +1,179 @@
+use preflect::fields::BaseHasFields;
+use preflect::fields::FieldAccessErrorKind;
+use preflect::fields::HasFields;
+use preflect::fields::Patch;
+use preflect::pointers::PointerError;
+use std::cell::Cell;
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+#[derive(Debug, HasFields)]
+struct Theme {
+    name: String,
+    dark: bool,
+}
+
+#[derive(Debug, HasFields)]
+struct Stats {
+    visits: u32,
+    errors: u32,
+}
+
+#[derive(Debug, HasFields)]
+struct Session {
+    id: u32,
+    #[preflect(pointer)]
+    theme: Box<Theme>,
+    #[preflect(pointer)]
+    shared: Rc<Theme>,
+    #[preflect(pointer)]
+    stats: Rc<RefCell<Stats>>,
+    #[preflect(pointer)]
+    clicks: Cell<u32>,
+    #[preflect(pointer)]
+    state: Arc<Mutex<Stats>>,
+}
+
+fn main() {
+    let shared = Rc::new(Theme {
+        name: "Solarized".into(),
+        dark: false,
+    });
+    let mut session = Session {
+        id: 1,
+        theme: Box::new(Theme {
+            name: "Dracula".into(),
+            dark: true,
+        }),
+        shared: Rc::clone(&shared),
+        stats: Rc::new(RefCell::new(Stats {
+            visits: 0,
+            errors: 0,
+        })),
+        clicks: Cell::new(0),
+        state: Arc::new(Mutex::new(Stats {
+            visits: 10,
+            errors: 1,
+        })),
+    };
+
+    // Paths pass through pointers to the fields of their targets.
+    assert_eq!(Ok(&true), session.get_path::<bool>("theme.dark"));
+    *session.get_path_mut::<String>("theme.name").unwrap() = "Nord".into();
+    assert_eq!("Nord", session.theme.name);
+    assert_eq!(
+        Ok(&"Solarized".to_string()),
+        session.get_path::<String>("shared.name")
+    );
+
+    // The target of a pointer is modified through a mutable reference to the
+    // pointer rather than through a guard.
+    let theme = session.get_pointer_raw("theme").unwrap();
+    assert_eq!(Err(PointerError::NotGuarded), theme.lock_mut().map(|_| ()));
+
+    // The target of a shared `Rc` can be read but not modified.
+    let error = session.get_path_mut::<bool>("shared.dark").unwrap_err();
+    assert_eq!(
+        FieldAccessErrorKind::Pointer(PointerError::Shared),
+        error.error().kind()
+    );
+    println!("{}", error);
+    drop(shared);
+    *session.get_path_mut::<bool>("shared.dark").unwrap() = true;
+    assert!(session.shared.dark);
+
+    // The value inside a cell is read through a guard, which keeps it borrowed.
+    let stats = session.get_pointer_raw("stats").unwrap();
+    assert_eq!(Err(PointerError::Guarded), stats.get_nested().map(|_| ()));
+    {
+        let rc_guard = stats.lock().unwrap();
+        let cell = rc_guard.as_pointer().unwrap();
+        let mut guard = cell.lock_mut().unwrap();
+        let stats = guard.as_reflect_mut().unwrap();
+        *stats.get_field_mut::<u32>("visits").unwrap() += 1;
+        assert_eq!(Err(PointerError::Borrowed), cell.lock().map(|_| ()));
+    }
+    assert_eq!(1, session.stats.borrow().visits);
+
+    // Immutable paths stop at cells and locks, while mutable paths pass
+    // through them since nothing else can be borrowing the value.
+    let error = session.get_path::<u32>("stats.errors").unwrap_err();
+    assert_eq!(
+        FieldAccessErrorKind::Pointer(PointerError::Guarded),
+        error.error().kind()
+    );
+    println!("{}", error);
+    *session.get_path_mut::<u32>("stats.errors").unwrap() = 3;
+    assert_eq!(3, session.stats.borrow().errors);
+
+    // Locked paths pass through cells and locks as well, which stay borrowed
+    // until the guard is dropped.
+    {
+        let errors = session.lock_path::<u32>("stats.errors").unwrap();
+        assert_eq!(3, *errors);
+        assert!(session.stats.try_borrow_mut().is_err());
+    }
+
+    // A shared `Rc` can only be modified through the cell it shares, which a
+    // locked path borrows for writing.
+    let stats = Rc::clone(&session.stats);
+    let error = session.get_path_mut::<u32>("stats.visits").unwrap_err();
+    assert_eq!(
+        FieldAccessErrorKind::Pointer(PointerError::Shared),
+        error.error().kind()
+    );
+    *session.lock_path_mut::<u32>("stats.visits").unwrap() += 1;
+    assert_eq!(2, stats.borrow().visits);
+
+    // Patches resolve their paths the same way when they are checked as when
+    // they are applied, so nothing is written if one of them is shared.
+    let theme = Rc::clone(&session.shared);
+    let error = Patch::new()
+        .with("stats.errors", 0u32)
+        .with("shared.dark", false)
+        .apply(&mut session)
+        .unwrap_err();
+    assert_eq!(
+        FieldAccessErrorKind::Pointer(PointerError::Shared),
+        error.error().error().kind()
+    );
+    println!("{}", error);
+    assert_eq!(3, stats.borrow().errors);
+    drop(theme);
+    Patch::new()
+        .with("stats.errors", 0u32)
+        .apply(&mut session)
+        .unwrap();
+    assert_eq!(0, stats.borrow().errors);
+
+    // The guard of a `Cell` holds a copy of its value, which is written back
+    // when the guard is dropped.
+    {
+        let clicks = session.get_pointer_raw("clicks").unwrap();
+        let mut guard = clicks.lock_mut().unwrap();
+        *guard.downcast_mut::<u32>().unwrap() += 2;
+        assert_eq!(0, session.clicks.get());
+    }
+    assert_eq!(2, session.clicks.get());
+
+    // Locks are acquired through the guard of the pointer which shares them.
+    {
+        let state = session.get_pointer_raw("state").unwrap();
+        let arc_guard = state.lock().unwrap();
+        let mutex = arc_guard.as_pointer().unwrap();
+        let guard = mutex.lock().unwrap();
+        let stats = guard.as_reflect().unwrap();
+        assert_eq!(Ok(&10), stats.get_field::<u32>("visits"));
+        println!("{:?}", guard);
+    }
+    *session.get_path_mut::<u32>("state.visits").unwrap() += 1;
+    assert_eq!(11, session.state.lock().unwrap().visits);
+    assert_eq!(11, *session.lock_path::<u32>("state.visits").unwrap());
+
+    // Only fields marked as pointers can be accessed this way.
+    let error = session.get_pointer_raw("id").unwrap_err();
+    assert_eq!(FieldAccessErrorKind::NotPointer, error.kind());
+    println!("{}", error);
+}
@@ -0,0 +1,86 @@
+use preflect::fields::HasFields;
+use preflect::fmt::Formatters;
+use preflect::fmt::ReflectFormat;
+use preflect::fmt::ValueFormatter;
+use std::any::Any;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+use std::time::Duration;
+
+#[derive(HasFields)]
+struct Config {
+    name: String,
+    #[preflect(redact)]
+    token: String,
+    #[preflect(nested)]
+    server: Server,
+    #[preflect(ignore)]
+    #[allow(dead_code)]
+    cache: Vec<u8>,
+    tags: Vec<&'static str>,
+}
+
+#[derive(HasFields)]
+struct Server {
+    host: String,
+    port: u16,
+    timeout: Duration,
+    #[preflect(nested)]
+    protocol: Protocol,
+}
+
+#[derive(Debug, HasFields)]
+enum Protocol {
+    Http,
+    Https { verify: bool },
+    Proxy(String, u16),
+}
+
+const FORMATTERS: &[ValueFormatter] = &[
+    ValueFormatter::debug::<Duration>(),
+    ValueFormatter::new::<Vec<&'static str>>(format_tags),
+];
+
+fn format_tags(value: &dyn Any, f: &mut Formatter<'_>) -> FmtResult {
+    let tags = value.downcast_ref::<Vec<&'static str>>().unwrap();
+    write!(f, "[{}]", tags.join(", "))
+}
+
+fn main() {
+    let config = Config {
+        name: "api".into(),
+        token: "hunter2".into(),
+        server: Server {
+            host: "localhost".into(),
+            port: 8080,
+            timeout: Duration::from_secs(30),
+            protocol: Protocol::Https { verify: true },
+        },
+        cache: vec![],
+        tags: vec!["prod", "eu"],
+    };
+
+    // Types without a formatter are printed as the name of their type.
+    println!("{:?}", ReflectFormat::new(&config));
+
+    let format = ReflectFormat::with_formatters(&config, Formatters::new(FORMATTERS));
+    println!("{:#?}", format);
+    println!("{}", format);
+
+    let debug = format!("{:?}", format);
+    assert!(debug.contains("token: ***"));
+    assert!(!debug.contains("hunter2"));
+    assert!(!debug.contains("cache"));
+    assert!(debug.contains("protocol: Https { verify: true }"));
+
+    // Enums are printed with the name of their active variant.
+    let protocols = [
+        Protocol::Http,
+        Protocol::Https { verify: false },
+        Protocol::Proxy("proxy.local".into(), 3128),
+    ];
+    for protocol in &protocols {
+        let expected = format!("{:?}", protocol);
+        assert_eq!(expected, format!("{:?}", ReflectFormat::new(protocol)));
+    }
+}
@@ -0,0 +1,60 @@
+use preflect::fields::HasFields;
+use preflect::fields::Reflect;
+use preflect::serde::ReflectSeed;
+use serde::de::DeserializeSeed;
+
+#[derive(Debug, Default, HasFields)]
+struct Config {
+    name: String,
+    #[preflect(alias = ["worker_count"])]
+    workers: u8,
+    #[preflect(nested)]
+    server: Server,
+    #[preflect(ignore)]
+    #[allow(dead_code)]
+    cache: Vec<u8>,
+}
+
+#[derive(Debug, Default, HasFields)]
+struct Server {
+    host: String,
+    port: u16,
+}
+
+fn main() {
+    let config = Config {
+        name: "api".into(),
+        workers: 4,
+        server: Server {
+            host: "localhost".into(),
+            port: 8080,
+        },
+        cache: vec![1, 2, 3],
+    };
+
+    // Serializes through a `dyn Reflect`, skipping the ignored field.
+    let reflected: &dyn Reflect = &config;
+    let json = serde_json::to_string(reflected).unwrap();
+    println!("{}", json);
+    assert_eq!(
+        r#"{"name":"api","workers":4,"server":{"host":"localhost","port":8080}}"#,
+        json
+    );
+
+    // Deserializes into a default value, accepting aliases.
+    let input = r#"{"name":"batch","worker_count":8,"server":{"port":9090},"extra":true}"#;
+    let mut deserializer = serde_json::Deserializer::from_str(input);
+    let copy: Config = preflect::serde::deserialize(&mut deserializer).unwrap();
+    println!("{:?}", copy);
+    assert_eq!(8, copy.workers);
+    assert_eq!(9090, copy.server.port);
+
+    // Deserializes into an existing value, keeping fields which are missing.
+    let mut existing = config;
+    let mut deserializer = serde_json::Deserializer::from_str(r#"{"workers":2}"#);
+    ReflectSeed::new(&mut existing)
+        .deserialize(&mut deserializer)
+        .unwrap();
+    assert_eq!(2, existing.workers);
+    assert_eq!("api", existing.name);
+}
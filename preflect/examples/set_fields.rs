@@ -0,0 +1,66 @@
+use preflect::fields::FieldAccessErrorKind;
+use preflect::fields::HasFields;
+use std::any::Any;
+
+#[derive(Debug, HasFields)]
+struct Settings {
+    theme: String,
+    font_size: u32,
+    recent_files: Vec<String>,
+}
+
+fn main() {
+    let mut settings = Settings {
+        theme: "light".into(),
+        font_size: 12,
+        recent_files: vec!["notes.txt".into()],
+    };
+
+    // Updates which arrive as data.
+    let updates: Vec<(&str, Box<dyn Any>)> = vec![
+        ("theme", Box::new(String::from("dark"))),
+        ("font_size", Box::new(14u32)),
+        ("font_size", Box::new("huge")),
+        ("line_height", Box::new(1.5f32)),
+    ];
+
+    for (name, value) in updates {
+        if let Err(err) = settings.set_field(name, value) {
+            let rejected = err.into_value();
+            println!(
+                "Rejected {}: {:?} ({:?})",
+                name,
+                rejected,
+                rejected.downcast_ref::<&str>()
+            );
+        }
+    }
+
+    let previous = settings
+        .replace_field("theme", Box::new(String::from("solarized")))
+        .unwrap();
+    assert_eq!("dark", *previous.downcast::<String>().unwrap());
+
+    let recent_files: Vec<String> = settings.take_field("recent_files").unwrap();
+    assert_eq!(vec![String::from("notes.txt")], recent_files);
+    assert!(settings.recent_files.is_empty());
+
+    let err = settings
+        .set_field("font_size", Box::new(-1i32))
+        .unwrap_err();
+    assert_eq!(FieldAccessErrorKind::InvalidType, err.error().kind());
+    println!("{}", err);
+
+    // The mutable references handed out by fields_mut can be held together.
+    let font_size_before = settings.font_size;
+    let mut fields = settings.fields_mut().collect::<Vec<_>>();
+    let (theme_name, theme) = fields.remove(0);
+    let (_, font_size) = fields.remove(0);
+    assert_eq!("theme", theme_name);
+    *font_size.downcast_mut::<u32>().unwrap() += 2;
+    theme.downcast_mut::<String>().unwrap().push_str("-large");
+    assert_eq!(font_size_before + 2, settings.font_size);
+    assert_eq!("solarized-large", settings.theme);
+
+    println!("{:?}", settings);
+}
@@ -0,0 +1,42 @@
+use preflect::drop::PartialDrop;
+use preflect::fields::field;
+use preflect::fields::BaseHasFields;
+use preflect::fields::HasField;
+use preflect::fields::HasFields;
+use preflect::fields::HasNamedField;
+use std::mem::ManuallyDrop;
+
+#[derive(Debug, HasFields, HasField, PartialDrop)]
+struct UserId(u32);
+
+#[derive(Debug, HasFields, HasField, PartialDrop)]
+struct Point(i32, i32, #[preflect(ignore)] String);
+
+fn main() {
+    let mut id = UserId(1);
+    increment(&mut id);
+    assert_eq!(&2, read_inner(&id));
+
+    let point = Point(3, 4, "origin".into());
+    assert_eq!(&["0", "1"], point.field_names());
+    for (name, value) in point.fields() {
+        println!("{} = {:?}", name, value.downcast_ref::<i32>());
+    }
+
+    let mut point = ManuallyDrop::new(point);
+    unsafe {
+        point.drop_all_fields_except(&["0", "1"]);
+
+        // The ignored field is never dropped by PartialDrop.
+        std::ptr::drop_in_place(&mut point.2);
+    }
+}
+
+fn increment(obj: &mut impl HasFields) {
+    let inner: &mut u32 = obj.get_field_mut("0").unwrap();
+    *inner += 1;
+}
+
+fn read_inner(has_inner: &impl HasNamedField<field!(0), FieldType = u32>) -> &u32 {
+    has_inner.get_field()
+}
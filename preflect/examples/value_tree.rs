@@ -0,0 +1,86 @@
+use preflect::fields::HasFields;
+use preflect::value::Converters;
+use preflect::value::StructValue;
+use preflect::value::Value;
+use preflect::value::ValueConverter;
+use preflect::value::ValueError;
+use preflect::value::ValueKind;
+
+#[derive(Debug, Default, HasFields, PartialEq)]
+struct Config {
+    name: String,
+    workers: u8,
+    tags: Vec<String>,
+    #[preflect(nested)]
+    server: Server,
+}
+
+#[derive(Debug, Default, HasFields, PartialEq)]
+struct Server {
+    #[preflect(alias = ["hostname"])]
+    host: String,
+    port: u16,
+}
+
+const CONVERTERS: &[ValueConverter] = &[ValueConverter::of::<Vec<String>>()];
+
+fn main() {
+    let config = Config {
+        name: "api".into(),
+        workers: 4,
+        tags: vec!["prod".into()],
+        server: Server {
+            host: "localhost".into(),
+            port: 8080,
+        },
+    };
+
+    // `Vec<String>` has no default converter, so it must be registered.
+    let error = Value::from_reflect(&config).unwrap_err();
+    println!("{}", error);
+
+    let converters = Converters::new(CONVERTERS);
+    let value = converters.to_value(&config).unwrap();
+    println!("{:?}", value);
+
+    let copy: Config = converters.from_value(&value).unwrap();
+    assert_eq!(config, copy);
+
+    // Values of the wrong shape are rejected without modifying the target.
+    let mut server = Server::default();
+    let bad_port = StructValue::new("Server")
+        .with_field("host", Value::String("example.com".into()))
+        .with_field("port", Value::UInt(70000));
+    let error = Value::Struct(bad_port).apply_to(&mut server).unwrap_err();
+    println!("{}", error);
+    assert_eq!(
+        &ValueError::OutOfRange { type_name: "u16" },
+        error.root_cause()
+    );
+    assert_eq!(Server::default(), server);
+
+    let wrong_kind = StructValue::new("Server")
+        .with_field("host", Value::Bool(true))
+        .with_field("port", Value::UInt(80));
+    let error = Value::Struct(wrong_kind).apply_to(&mut server).unwrap_err();
+    println!("{}", error);
+    assert_eq!(
+        &ValueError::mismatch(ValueKind::String, ValueKind::Bool),
+        error.root_cause()
+    );
+
+    // Fields can be given under their aliases, but only once.
+    let aliased = StructValue::new("Server")
+        .with_field("hostname", Value::String("example.com".into()))
+        .with_field("port", Value::UInt(443));
+    Value::Struct(aliased.clone())
+        .apply_to(&mut server)
+        .unwrap();
+    assert_eq!("example.com", server.host);
+
+    let repeated = aliased.with_field("host", Value::String("localhost".into()));
+    let error = Value::Struct(repeated).apply_to(&mut server).unwrap_err();
+    println!("{}", error);
+    assert_eq!(ValueError::DuplicateField { name: "host" }, error);
+    assert_eq!("example.com", server.host);
+}
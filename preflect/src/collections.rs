@@ -0,0 +1,39 @@
+//! Provides reflection for collections (lists, options and maps) whose
+//! elements are accessed as type-erased values. The traits are implemented
+//! for `Vec`, arrays, slices, `Option` and `BTreeMap`, along with `HashMap`
+//! when the `std` feature is enabled.
+//!
+//! A field marked with `#[preflect(collection)]` can be accessed as a
+//! collection through [`BaseHasFields`](crate::fields::BaseHasFields), which
+//! allows paths to continue into its elements: `items.0.name` addresses the
+//! `name` of the first element of a list, and `scores.alice` addresses the
+//! value of a map whose keys are strings. The value of an option is its
+//! element at index `0`. Paths only continue past an element when the element
+//! can itself be reflected.
+
+#![cfg(feature = "alloc")]
+
+mod access;
+mod errors;
+mod list;
+mod map;
+mod option;
+
+pub use self::access::*;
+pub use self::errors::*;
+pub use self::list::*;
+pub use self::map::*;
+pub use self::option::*;
+
+use alloc::boxed::Box;
+use core::any::type_name;
+use core::any::Any;
+
+/// Unboxes a type-erased value which should be of type `T`, returning it in
+/// an error if it is not.
+fn downcast_value<T: Any>(value: Box<dyn Any>) -> CollectionResult<T> {
+    match value.downcast::<T>() {
+        Ok(value) => Ok(*value),
+        Err(value) => Err(CollectionError::invalid_type(type_name::<T>(), value)),
+    }
+}
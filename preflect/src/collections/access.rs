@@ -0,0 +1,398 @@
+use crate::collections::ReflectList;
+use crate::collections::ReflectMap;
+use crate::collections::ReflectOption;
+use crate::fields::Reflect;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::any::Any;
+use core::fmt::Debug;
+use core::fmt::Formatter;
+use core::fmt::Result as FmtResult;
+#[cfg(feature = "std")]
+use core::hash::BuildHasher;
+#[cfg(feature = "std")]
+use core::hash::Hash;
+use core::marker::PhantomData;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+/// A type which can be accessed as a reflected collection. Fields of these
+/// types can be marked with `#[preflect(collection)]`.
+pub trait AsCollection {
+    /// The type of the elements (or the values of a map).
+    type Element: Any;
+
+    /// Views the value as a type-erased collection.
+    fn as_collection(&self) -> CollectionKind<'_>;
+
+    /// Views the value as a mutable type-erased collection.
+    fn as_collection_mut(&mut self) -> CollectionKindMut<'_>;
+}
+
+/// An immutable reference to a type-erased collection.
+#[derive(Clone, Copy)]
+pub enum CollectionKind<'a> {
+    /// A list (e.g. a `Vec` or an array).
+    List(&'a dyn ReflectList),
+    /// An `Option`.
+    Option(&'a dyn ReflectOption),
+    /// A map (e.g. a `BTreeMap`).
+    Map(&'a dyn ReflectMap),
+}
+
+/// A mutable reference to a type-erased collection.
+pub enum CollectionKindMut<'a> {
+    /// A list (e.g. a `Vec` or an array).
+    List(&'a mut dyn ReflectList),
+    /// An `Option`.
+    Option(&'a mut dyn ReflectOption),
+    /// A map (e.g. a `BTreeMap`).
+    Map(&'a mut dyn ReflectMap),
+}
+
+impl Debug for CollectionKind<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match *self {
+            CollectionKind::List(list) => debug_list(list, f),
+            CollectionKind::Option(option) => debug_option(option, f),
+            CollectionKind::Map(map) => debug_map(map, f),
+        }
+    }
+}
+
+impl Debug for CollectionKindMut<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            CollectionKindMut::List(list) => debug_list(*list, f),
+            CollectionKindMut::Option(option) => debug_option(*option, f),
+            CollectionKindMut::Map(map) => debug_map(*map, f),
+        }
+    }
+}
+
+fn debug_list(list: &dyn ReflectList, f: &mut Formatter<'_>) -> FmtResult {
+    f.debug_struct("List")
+        .field("element_type", &list.element_type_name())
+        .field("len", &list.len())
+        .finish()
+}
+
+fn debug_option(option: &dyn ReflectOption, f: &mut Formatter<'_>) -> FmtResult {
+    f.debug_struct("Option")
+        .field("value_type", &option.value_type_name())
+        .field("is_some", &option.is_some())
+        .finish()
+}
+
+fn debug_map(map: &dyn ReflectMap, f: &mut Formatter<'_>) -> FmtResult {
+    f.debug_struct("Map")
+        .field("key_type", &map.key_type_name())
+        .field("value_type", &map.value_type_name())
+        .field("len", &map.len())
+        .finish()
+}
+
+impl<T: Any> AsCollection for Vec<T> {
+    type Element = T;
+
+    fn as_collection(&self) -> CollectionKind<'_> {
+        CollectionKind::List(self)
+    }
+
+    fn as_collection_mut(&mut self) -> CollectionKindMut<'_> {
+        CollectionKindMut::List(self)
+    }
+}
+
+impl<T: Any, const N: usize> AsCollection for [T; N] {
+    type Element = T;
+
+    fn as_collection(&self) -> CollectionKind<'_> {
+        CollectionKind::List(self)
+    }
+
+    fn as_collection_mut(&mut self) -> CollectionKindMut<'_> {
+        CollectionKindMut::List(self)
+    }
+}
+
+impl<T: Any> AsCollection for Option<T> {
+    type Element = T;
+
+    fn as_collection(&self) -> CollectionKind<'_> {
+        CollectionKind::Option(self)
+    }
+
+    fn as_collection_mut(&mut self) -> CollectionKindMut<'_> {
+        CollectionKindMut::Option(self)
+    }
+}
+
+impl<K: Any + Ord, V: Any> AsCollection for BTreeMap<K, V> {
+    type Element = V;
+
+    fn as_collection(&self) -> CollectionKind<'_> {
+        CollectionKind::Map(self)
+    }
+
+    fn as_collection_mut(&mut self) -> CollectionKindMut<'_> {
+        CollectionKindMut::Map(self)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K: Any + Eq + Hash, V: Any, S: BuildHasher + 'static> AsCollection for HashMap<K, V, S> {
+    type Element = V;
+
+    fn as_collection(&self) -> CollectionKind<'_> {
+        CollectionKind::Map(self)
+    }
+
+    fn as_collection_mut(&mut self) -> CollectionKindMut<'_> {
+        CollectionKindMut::Map(self)
+    }
+}
+
+/// Views the type-erased elements of a collection as reflected values when
+/// the type of the elements can be reflected.
+#[derive(Clone, Copy)]
+pub struct ElementCast {
+    casts: Option<(ReflectFn, ReflectMutFn)>,
+}
+
+impl Debug for ElementCast {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("ElementCast")
+            .field("is_reflect", &self.is_reflect())
+            .finish()
+    }
+}
+
+type ReflectFn = fn(&dyn Any) -> Option<&dyn Reflect>;
+type ReflectMutFn = fn(&mut dyn Any) -> Option<&mut dyn Reflect>;
+
+impl ElementCast {
+    /// Views elements of type `T` as reflected values.
+    pub fn reflect<T: Reflect>() -> Self {
+        Self {
+            casts: Some((
+                |element| element.downcast_ref::<T>().map(|e| e as &dyn Reflect),
+                |element| element.downcast_mut::<T>().map(|e| e as &mut dyn Reflect),
+            )),
+        }
+    }
+
+    /// Used for elements which can not be reflected.
+    pub fn opaque() -> Self {
+        Self { casts: None }
+    }
+
+    /// Determines if the elements can be reflected.
+    pub fn is_reflect(&self) -> bool {
+        self.casts.is_some()
+    }
+
+    /// Views an element as a reflected value.
+    pub fn as_reflect<'a>(&self, element: &'a dyn Any) -> Option<&'a dyn Reflect> {
+        match self.casts {
+            Some((cast, _)) => cast(element),
+            None => None,
+        }
+    }
+
+    /// Views an element as a mutable reflected value.
+    pub fn as_reflect_mut<'a>(&self, element: &'a mut dyn Any) -> Option<&'a mut dyn Reflect> {
+        match self.casts {
+            Some((_, cast)) => cast(element),
+            None => None,
+        }
+    }
+}
+
+/// Selects the [`ElementCast`] for the elements of a collection of type `C`
+/// through autoref specialization: `(&&Elements::<C>::new()).cast()` views the
+/// elements as reflected values whenever they implement [`Reflect`]. This is
+/// used by the code generated for `#[preflect(collection)]`.
+#[doc(hidden)]
+pub struct Elements<C: ?Sized>(PhantomData<fn() -> *const C>);
+
+impl<C: ?Sized> Elements<C> {
+    #[doc(hidden)]
+    pub fn new() -> Self {
+        Elements(PhantomData)
+    }
+}
+
+impl<C: ?Sized> Default for Elements<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[doc(hidden)]
+pub trait ReflectElements {
+    fn cast(&self) -> ElementCast;
+}
+
+impl<C: AsCollection> ReflectElements for &Elements<C>
+where
+    C::Element: Reflect,
+{
+    fn cast(&self) -> ElementCast {
+        ElementCast::reflect::<C::Element>()
+    }
+}
+
+#[doc(hidden)]
+pub trait OpaqueElements {
+    fn cast(&self) -> ElementCast;
+}
+
+impl<C: ?Sized> OpaqueElements for Elements<C> {
+    fn cast(&self) -> ElementCast {
+        ElementCast::opaque()
+    }
+}
+
+/// An immutable reference to a field which is a collection, along with the
+/// means to view its elements as reflected values.
+#[derive(Clone, Copy, Debug)]
+pub struct CollectionRef<'a> {
+    kind: CollectionKind<'a>,
+    elements: ElementCast,
+}
+
+impl<'a> CollectionRef<'a> {
+    /// Creates a reference to a collection whose elements are viewed by
+    /// `elements`.
+    pub fn new<C: AsCollection>(collection: &'a C, elements: ElementCast) -> Self {
+        Self {
+            kind: collection.as_collection(),
+            elements,
+        }
+    }
+
+    /// The type-erased collection.
+    pub fn kind(&self) -> CollectionKind<'a> {
+        self.kind
+    }
+
+    /// Views the elements of the collection as reflected values.
+    pub fn elements(&self) -> ElementCast {
+        self.elements
+    }
+
+    /// The number of elements in the collection.
+    pub fn len(&self) -> usize {
+        match self.kind {
+            CollectionKind::List(list) => list.len(),
+            CollectionKind::Option(option) => option.is_some() as usize,
+            CollectionKind::Map(map) => map.len(),
+        }
+    }
+
+    /// Determines if the collection has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Gets the element for a segment of a path: the index of an element of
+    /// a list, `0` for the value of an option or the key of a map (whose keys
+    /// must be strings, characters, booleans or integers).
+    pub fn get(&self, segment: &str) -> Option<&'a dyn Any> {
+        match self.kind {
+            CollectionKind::List(list) => list.get(segment.parse().ok()?),
+            CollectionKind::Option(option) => option.get().filter(|_| segment == "0"),
+            CollectionKind::Map(map) => map
+                .entries()
+                .find(|(key, _)| key_matches(*key, segment))
+                .map(|(_, value)| value),
+        }
+    }
+
+    /// Gets the element for a segment of a path as a reflected value.
+    pub fn get_nested(&self, segment: &str) -> Option<&'a dyn Reflect> {
+        self.elements.as_reflect(self.get(segment)?)
+    }
+}
+
+/// A mutable reference to a field which is a collection, along with the
+/// means to view its elements as reflected values.
+#[derive(Debug)]
+pub struct CollectionMut<'a> {
+    kind: CollectionKindMut<'a>,
+    elements: ElementCast,
+}
+
+impl<'a> CollectionMut<'a> {
+    /// Creates a mutable reference to a collection whose elements are viewed
+    /// by `elements`.
+    pub fn new<C: AsCollection>(collection: &'a mut C, elements: ElementCast) -> Self {
+        Self {
+            kind: collection.as_collection_mut(),
+            elements,
+        }
+    }
+
+    /// The mutable type-erased collection.
+    pub fn kind(&mut self) -> &mut CollectionKindMut<'a> {
+        &mut self.kind
+    }
+
+    /// Takes the mutable type-erased collection.
+    pub fn into_kind(self) -> CollectionKindMut<'a> {
+        self.kind
+    }
+
+    /// Views the elements of the collection as reflected values.
+    pub fn elements(&self) -> ElementCast {
+        self.elements
+    }
+
+    /// Takes the element for a segment of a path. See
+    /// [`CollectionRef::get`] for the segments which are accepted.
+    pub fn into_element(self, segment: &str) -> Option<&'a mut dyn Any> {
+        match self.kind {
+            CollectionKindMut::List(list) => list.get_mut(segment.parse().ok()?),
+            CollectionKindMut::Option(option) => option.get_mut().filter(|_| segment == "0"),
+            CollectionKindMut::Map(map) => {
+                let position = map
+                    .entries()
+                    .position(|(key, _)| key_matches(key, segment))?;
+                map.entries_mut().nth(position).map(|(_, value)| value)
+            }
+        }
+    }
+
+    /// Takes the element for a segment of a path as a reflected value.
+    pub fn into_nested(self, segment: &str) -> Option<&'a mut dyn Reflect> {
+        let elements = self.elements;
+        elements.as_reflect_mut(self.into_element(segment)?)
+    }
+}
+
+/// Determines if a type-erased key of a map is written as `segment` in a
+/// path.
+fn key_matches(key: &dyn Any, segment: &str) -> bool {
+    macro_rules! parsed_matches {
+        ($($ty:ty),*) => {
+            $(
+                if let Some(key) = key.downcast_ref::<$ty>() {
+                    return segment.parse::<$ty>().ok().as_ref() == Some(key);
+                }
+            )*
+        };
+    }
+
+    if let Some(key) = key.downcast_ref::<String>() {
+        return key == segment;
+    }
+
+    if let Some(key) = key.downcast_ref::<&'static str>() {
+        return *key == segment;
+    }
+
+    parsed_matches!(char, bool, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+    false
+}
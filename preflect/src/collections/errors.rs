@@ -0,0 +1,135 @@
+use alloc::boxed::Box;
+use core::any::Any;
+use core::fmt::Debug;
+use core::fmt::Display;
+use core::fmt::Formatter;
+use core::fmt::Result as FmtResult;
+
+/// The result of modifying a collection through reflection.
+pub type CollectionResult<T> = Result<T, CollectionError>;
+
+/// Describes the kind of error that occurred while modifying a collection.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CollectionErrorKind {
+    /// A value was not of the type of the elements (or keys) of the
+    /// collection.
+    InvalidType {
+        /// The name of the type the value was expected to be.
+        expected: &'static str,
+    },
+    /// The key or the value of an entry was not of the type of the keys or
+    /// values of a map.
+    InvalidEntry {
+        /// The name of the type of the keys of the map.
+        key: &'static str,
+        /// The name of the type of the values of the map.
+        value: &'static str,
+    },
+    /// An index was past the end of a list.
+    OutOfBounds {
+        /// The index which was requested.
+        index: usize,
+        /// The length of the list.
+        len: usize,
+    },
+    /// The collection can not change its length (e.g. an array).
+    FixedSize,
+}
+
+/// An error that occurred while modifying a collection. A value (or the key
+/// of a map entry) which could not be added to the collection is returned to
+/// the caller.
+pub struct CollectionError {
+    kind: CollectionErrorKind,
+    key: Option<Box<dyn Any>>,
+    value: Option<Box<dyn Any>>,
+}
+
+impl CollectionError {
+    pub(crate) fn invalid_type(expected: &'static str, value: Box<dyn Any>) -> Self {
+        Self {
+            kind: CollectionErrorKind::InvalidType { expected },
+            key: None,
+            value: Some(value),
+        }
+    }
+
+    pub(crate) fn invalid_entry(
+        expected_key: &'static str,
+        expected_value: &'static str,
+        key: Box<dyn Any>,
+        value: Box<dyn Any>,
+    ) -> Self {
+        Self {
+            kind: CollectionErrorKind::InvalidEntry {
+                key: expected_key,
+                value: expected_value,
+            },
+            key: Some(key),
+            value: Some(value),
+        }
+    }
+
+    pub(crate) fn out_of_bounds(index: usize, len: usize, value: Option<Box<dyn Any>>) -> Self {
+        Self {
+            kind: CollectionErrorKind::OutOfBounds { index, len },
+            key: None,
+            value,
+        }
+    }
+
+    pub(crate) fn fixed_size(value: Option<Box<dyn Any>>) -> Self {
+        Self {
+            kind: CollectionErrorKind::FixedSize,
+            key: None,
+            value,
+        }
+    }
+
+    /// The kind of error which occurred.
+    pub fn kind(&self) -> CollectionErrorKind {
+        self.kind
+    }
+
+    /// Takes back the value which could not be added, if any.
+    pub fn into_value(self) -> Option<Box<dyn Any>> {
+        self.value
+    }
+
+    /// Takes back the key and the value of a map entry which could not be
+    /// added, if any.
+    pub fn into_entry(self) -> Option<(Box<dyn Any>, Box<dyn Any>)> {
+        Some((self.key?, self.value?))
+    }
+}
+
+impl Debug for CollectionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("CollectionError")
+            .field("kind", &self.kind)
+            .finish()
+    }
+}
+
+impl Display for CollectionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self.kind {
+            CollectionErrorKind::InvalidType { expected } => {
+                write!(f, "The value is not of type '{}'.", expected)
+            }
+            CollectionErrorKind::InvalidEntry { key, value } => write!(
+                f,
+                "The entry does not have a key of type '{}' and a value of type '{}'.",
+                key, value
+            ),
+            CollectionErrorKind::OutOfBounds { index, len } => write!(
+                f,
+                "The index {} is out of bounds for a list of length {}.",
+                index, len
+            ),
+            CollectionErrorKind::FixedSize => {
+                write!(f, "The collection can not change its length.")
+            }
+        }
+    }
+}
@@ -0,0 +1,158 @@
+use crate::collections::downcast_value;
+use crate::collections::CollectionError;
+use crate::collections::CollectionResult;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::any::type_name;
+use core::any::Any;
+
+/// A list whose elements are accessed by their index as type-erased values.
+///
+/// ```
+/// use preflect::collections::ReflectList;
+///
+/// let mut list: Vec<u32> = vec![1, 2];
+/// let erased: &mut dyn ReflectList = &mut list;
+/// erased.push(Box::new(3u32)).unwrap();
+/// assert!(erased.push(Box::new("four")).is_err());
+/// assert_eq!(Some(&2), erased.get(1).and_then(|e| e.downcast_ref::<u32>()));
+/// assert_eq!(vec![1, 2, 3], list);
+/// ```
+pub trait ReflectList {
+    /// The name of the type of the elements.
+    fn element_type_name(&self) -> &'static str;
+
+    /// The number of elements in the list.
+    fn len(&self) -> usize;
+
+    /// Determines if the list has no elements.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Gets an immutable reference to the element at `index`.
+    fn get(&self, index: usize) -> Option<&dyn Any>;
+
+    /// Gets a mutable reference to the element at `index`.
+    fn get_mut(&mut self, index: usize) -> Option<&mut dyn Any>;
+
+    /// Adds an element to the end of the list.
+    fn push(&mut self, value: Box<dyn Any>) -> CollectionResult<()>;
+
+    /// Inserts an element at `index`, shifting the elements after it.
+    fn insert(&mut self, index: usize, value: Box<dyn Any>) -> CollectionResult<()>;
+
+    /// Removes the element at `index`, shifting the elements after it.
+    fn remove(&mut self, index: usize) -> CollectionResult<Box<dyn Any>>;
+}
+
+impl dyn ReflectList + '_ {
+    /// Iterates over the elements of the list in order.
+    pub fn iter(&self) -> impl Iterator<Item = &dyn Any> {
+        (0..self.len()).filter_map(move |index| self.get(index))
+    }
+}
+
+impl<T: Any> ReflectList for Vec<T> {
+    fn element_type_name(&self) -> &'static str {
+        type_name::<T>()
+    }
+
+    fn len(&self) -> usize {
+        Vec::len(self)
+    }
+
+    fn get(&self, index: usize) -> Option<&dyn Any> {
+        <[T]>::get(self, index).map(|element| element as &dyn Any)
+    }
+
+    fn get_mut(&mut self, index: usize) -> Option<&mut dyn Any> {
+        <[T]>::get_mut(self, index).map(|element| element as &mut dyn Any)
+    }
+
+    fn push(&mut self, value: Box<dyn Any>) -> CollectionResult<()> {
+        Vec::push(self, downcast_value(value)?);
+        Ok(())
+    }
+
+    fn insert(&mut self, index: usize, value: Box<dyn Any>) -> CollectionResult<()> {
+        if index > Vec::len(self) {
+            return Err(CollectionError::out_of_bounds(
+                index,
+                Vec::len(self),
+                Some(value),
+            ));
+        }
+
+        Vec::insert(self, index, downcast_value(value)?);
+        Ok(())
+    }
+
+    fn remove(&mut self, index: usize) -> CollectionResult<Box<dyn Any>> {
+        if index >= Vec::len(self) {
+            return Err(CollectionError::out_of_bounds(index, Vec::len(self), None));
+        }
+
+        Ok(Box::new(Vec::remove(self, index)))
+    }
+}
+
+impl<T: Any> ReflectList for [T] {
+    fn element_type_name(&self) -> &'static str {
+        type_name::<T>()
+    }
+
+    fn len(&self) -> usize {
+        <[T]>::len(self)
+    }
+
+    fn get(&self, index: usize) -> Option<&dyn Any> {
+        <[T]>::get(self, index).map(|element| element as &dyn Any)
+    }
+
+    fn get_mut(&mut self, index: usize) -> Option<&mut dyn Any> {
+        <[T]>::get_mut(self, index).map(|element| element as &mut dyn Any)
+    }
+
+    fn push(&mut self, value: Box<dyn Any>) -> CollectionResult<()> {
+        Err(CollectionError::fixed_size(Some(value)))
+    }
+
+    fn insert(&mut self, _: usize, value: Box<dyn Any>) -> CollectionResult<()> {
+        Err(CollectionError::fixed_size(Some(value)))
+    }
+
+    fn remove(&mut self, _: usize) -> CollectionResult<Box<dyn Any>> {
+        Err(CollectionError::fixed_size(None))
+    }
+}
+
+impl<T: Any, const N: usize> ReflectList for [T; N] {
+    fn element_type_name(&self) -> &'static str {
+        type_name::<T>()
+    }
+
+    fn len(&self) -> usize {
+        N
+    }
+
+    fn get(&self, index: usize) -> Option<&dyn Any> {
+        ReflectList::get(&self[..], index)
+    }
+
+    fn get_mut(&mut self, index: usize) -> Option<&mut dyn Any> {
+        ReflectList::get_mut(&mut self[..], index)
+    }
+
+    fn push(&mut self, value: Box<dyn Any>) -> CollectionResult<()> {
+        Err(CollectionError::fixed_size(Some(value)))
+    }
+
+    fn insert(&mut self, _: usize, value: Box<dyn Any>) -> CollectionResult<()> {
+        Err(CollectionError::fixed_size(Some(value)))
+    }
+
+    fn remove(&mut self, _: usize) -> CollectionResult<Box<dyn Any>> {
+        Err(CollectionError::fixed_size(None))
+    }
+}
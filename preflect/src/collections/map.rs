@@ -0,0 +1,195 @@
+use crate::collections::downcast_value;
+use crate::collections::CollectionError;
+use crate::collections::CollectionResult;
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use core::any::type_name;
+use core::any::Any;
+#[cfg(feature = "std")]
+use core::hash::BuildHasher;
+#[cfg(feature = "std")]
+use core::hash::Hash;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+/// A map whose keys and values are accessed as type-erased values. Looking up
+/// a key of a different type than the keys of the map finds nothing.
+///
+/// ```
+/// use preflect::collections::ReflectMap;
+/// use std::collections::BTreeMap;
+///
+/// let mut scores = BTreeMap::<String, u32>::new();
+/// let erased: &mut dyn ReflectMap = &mut scores;
+/// erased
+///     .insert(Box::new(String::from("alice")), Box::new(7u32))
+///     .unwrap();
+/// let key = String::from("alice");
+/// assert_eq!(Some(&7), erased.get(&key).and_then(|v| v.downcast_ref::<u32>()));
+/// assert_eq!(None, erased.get(&"alice").and_then(|v| v.downcast_ref::<u32>()));
+/// ```
+pub trait ReflectMap {
+    /// The name of the type of the keys.
+    fn key_type_name(&self) -> &'static str;
+
+    /// The name of the type of the values.
+    fn value_type_name(&self) -> &'static str;
+
+    /// The number of entries in the map.
+    fn len(&self) -> usize;
+
+    /// Determines if the map has no entries.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Gets an immutable reference to the value for a key.
+    fn get(&self, key: &dyn Any) -> Option<&dyn Any>;
+
+    /// Gets a mutable reference to the value for a key.
+    fn get_mut(&mut self, key: &dyn Any) -> Option<&mut dyn Any>;
+
+    /// Sets the value for a key, returning the previous value (if any). If
+    /// the key or the value is not of the expected type, both are returned in
+    /// the error.
+    fn insert(
+        &mut self,
+        key: Box<dyn Any>,
+        value: Box<dyn Any>,
+    ) -> CollectionResult<Option<Box<dyn Any>>>;
+
+    /// Removes the entry for a key, returning its value.
+    fn remove(&mut self, key: &dyn Any) -> Option<Box<dyn Any>>;
+
+    /// Iterates over the keys and values of the map in the order of the map.
+    fn entries(&self) -> Box<dyn Iterator<Item = (&dyn Any, &dyn Any)> + '_>;
+
+    /// Iterates over the keys and mutable values of the map in the same order
+    /// as [`entries`](Self::entries).
+    fn entries_mut(&mut self) -> Box<dyn Iterator<Item = (&dyn Any, &mut dyn Any)> + '_>;
+}
+
+/// Unboxes a key and a value, returning both in the error if either does not
+/// have the expected type.
+fn downcast_entry<K: Any, V: Any>(
+    key: Box<dyn Any>,
+    value: Box<dyn Any>,
+) -> CollectionResult<(K, V)> {
+    if !key.is::<K>() || !value.is::<V>() {
+        return Err(CollectionError::invalid_entry(
+            type_name::<K>(),
+            type_name::<V>(),
+            key,
+            value,
+        ));
+    }
+
+    Ok((downcast_value(key)?, downcast_value(value)?))
+}
+
+impl<K: Any + Ord, V: Any> ReflectMap for BTreeMap<K, V> {
+    fn key_type_name(&self) -> &'static str {
+        type_name::<K>()
+    }
+
+    fn value_type_name(&self) -> &'static str {
+        type_name::<V>()
+    }
+
+    fn len(&self) -> usize {
+        BTreeMap::len(self)
+    }
+
+    fn get(&self, key: &dyn Any) -> Option<&dyn Any> {
+        let value = BTreeMap::get(self, key.downcast_ref::<K>()?)?;
+        Some(value)
+    }
+
+    fn get_mut(&mut self, key: &dyn Any) -> Option<&mut dyn Any> {
+        let value = BTreeMap::get_mut(self, key.downcast_ref::<K>()?)?;
+        Some(value)
+    }
+
+    fn insert(
+        &mut self,
+        key: Box<dyn Any>,
+        value: Box<dyn Any>,
+    ) -> CollectionResult<Option<Box<dyn Any>>> {
+        let (key, value) = downcast_entry::<K, V>(key, value)?;
+        let previous = BTreeMap::insert(self, key, value);
+        Ok(previous.map(|previous| Box::new(previous) as Box<dyn Any>))
+    }
+
+    fn remove(&mut self, key: &dyn Any) -> Option<Box<dyn Any>> {
+        let value = BTreeMap::remove(self, key.downcast_ref::<K>()?)?;
+        Some(Box::new(value))
+    }
+
+    fn entries(&self) -> Box<dyn Iterator<Item = (&dyn Any, &dyn Any)> + '_> {
+        Box::new(
+            self.iter()
+                .map(|(key, value)| (key as &dyn Any, value as &dyn Any)),
+        )
+    }
+
+    fn entries_mut(&mut self) -> Box<dyn Iterator<Item = (&dyn Any, &mut dyn Any)> + '_> {
+        Box::new(
+            self.iter_mut()
+                .map(|(key, value)| (key as &dyn Any, value as &mut dyn Any)),
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K: Any + Eq + Hash, V: Any, S: BuildHasher + 'static> ReflectMap for HashMap<K, V, S> {
+    fn key_type_name(&self) -> &'static str {
+        type_name::<K>()
+    }
+
+    fn value_type_name(&self) -> &'static str {
+        type_name::<V>()
+    }
+
+    fn len(&self) -> usize {
+        HashMap::len(self)
+    }
+
+    fn get(&self, key: &dyn Any) -> Option<&dyn Any> {
+        let value = HashMap::get(self, key.downcast_ref::<K>()?)?;
+        Some(value)
+    }
+
+    fn get_mut(&mut self, key: &dyn Any) -> Option<&mut dyn Any> {
+        let value = HashMap::get_mut(self, key.downcast_ref::<K>()?)?;
+        Some(value)
+    }
+
+    fn insert(
+        &mut self,
+        key: Box<dyn Any>,
+        value: Box<dyn Any>,
+    ) -> CollectionResult<Option<Box<dyn Any>>> {
+        let (key, value) = downcast_entry::<K, V>(key, value)?;
+        let previous = HashMap::insert(self, key, value);
+        Ok(previous.map(|previous| Box::new(previous) as Box<dyn Any>))
+    }
+
+    fn remove(&mut self, key: &dyn Any) -> Option<Box<dyn Any>> {
+        let value = HashMap::remove(self, key.downcast_ref::<K>()?)?;
+        Some(Box::new(value))
+    }
+
+    fn entries(&self) -> Box<dyn Iterator<Item = (&dyn Any, &dyn Any)> + '_> {
+        Box::new(
+            self.iter()
+                .map(|(key, value)| (key as &dyn Any, value as &dyn Any)),
+        )
+    }
+
+    fn entries_mut(&mut self) -> Box<dyn Iterator<Item = (&dyn Any, &mut dyn Any)> + '_> {
+        Box::new(
+            self.iter_mut()
+                .map(|(key, value)| (key as &dyn Any, value as &mut dyn Any)),
+        )
+    }
+}
@@ -0,0 +1,68 @@
+use crate::collections::downcast_value;
+use crate::collections::CollectionResult;
+use alloc::boxed::Box;
+use core::any::type_name;
+use core::any::Any;
+
+/// An optional value which is accessed as a type-erased value.
+///
+/// ```
+/// use preflect::collections::ReflectOption;
+///
+/// let mut nickname: Option<String> = None;
+/// let erased: &mut dyn ReflectOption = &mut nickname;
+/// erased.insert(Box::new(String::from("Bo"))).unwrap();
+/// assert!(erased.is_some());
+/// assert_eq!(Some("Bo"), nickname.as_deref());
+/// ```
+pub trait ReflectOption {
+    /// The name of the type of the value.
+    fn value_type_name(&self) -> &'static str;
+
+    /// Determines if there is a value.
+    fn is_some(&self) -> bool;
+
+    /// Determines if there is no value.
+    fn is_none(&self) -> bool {
+        !self.is_some()
+    }
+
+    /// Gets an immutable reference to the value, if there is one.
+    fn get(&self) -> Option<&dyn Any>;
+
+    /// Gets a mutable reference to the value, if there is one.
+    fn get_mut(&mut self) -> Option<&mut dyn Any>;
+
+    /// Sets the value, returning the previous value (if any).
+    fn insert(&mut self, value: Box<dyn Any>) -> CollectionResult<Option<Box<dyn Any>>>;
+
+    /// Takes the value out, leaving nothing in its place.
+    fn remove(&mut self) -> Option<Box<dyn Any>>;
+}
+
+impl<T: Any> ReflectOption for Option<T> {
+    fn value_type_name(&self) -> &'static str {
+        type_name::<T>()
+    }
+
+    fn is_some(&self) -> bool {
+        Option::is_some(self)
+    }
+
+    fn get(&self) -> Option<&dyn Any> {
+        self.as_ref().map(|value| value as &dyn Any)
+    }
+
+    fn get_mut(&mut self) -> Option<&mut dyn Any> {
+        self.as_mut().map(|value| value as &mut dyn Any)
+    }
+
+    fn insert(&mut self, value: Box<dyn Any>) -> CollectionResult<Option<Box<dyn Any>>> {
+        let previous = self.replace(downcast_value(value)?);
+        Ok(previous.map(|previous| Box::new(previous) as Box<dyn Any>))
+    }
+
+    fn remove(&mut self) -> Option<Box<dyn Any>> {
+        self.take().map(|value| Box::new(value) as Box<dyn Any>)
+    }
+}
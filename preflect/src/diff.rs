@@ -0,0 +1,352 @@
+//! Provides facilities for comparing two values through reflection, which
+//! produces the paths of the fields that changed along with their old and new
+//! values (e.g. for audit logs).
+//!
+//! The values of fields are compared by comparators which are looked up by
+//! the `TypeId` of the value. Fields which can themselves be reflected (those
+//! marked with `#[preflect(nested)]`) are compared recursively unless a
+//! comparator is registered for their type.
+
+#![cfg(feature = "alloc")]
+
+use crate::fields::FieldInfo;
+use crate::fields::Reflect;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::any::Any;
+use core::any::TypeId;
+use core::fmt::Debug;
+use core::fmt::Display;
+use core::fmt::Formatter;
+use core::fmt::Result as FmtResult;
+
+/// A function which compares two type-erased values for equality. Both values
+/// are always of the type the function was registered for.
+pub type EqFn = fn(&dyn Any, &dyn Any) -> bool;
+
+/// The result of comparing two values through reflection.
+pub type DiffResult<'a> = Result<Vec<FieldChange<'a>>, DiffError>;
+
+/// Compares the values of a single type when they are compared through
+/// reflection.
+#[derive(Clone, Copy)]
+pub struct ValueComparator {
+    type_id: fn() -> TypeId,
+    eq: EqFn,
+}
+
+impl ValueComparator {
+    /// Creates a comparator for values of type `T` which uses the provided
+    /// function.
+    pub const fn new<T: 'static>(eq: EqFn) -> Self {
+        Self {
+            type_id: TypeId::of::<T>,
+            eq,
+        }
+    }
+
+    /// Creates a comparator which compares values of type `T` using their
+    /// `PartialEq` implementation.
+    pub const fn partial_eq<T: PartialEq + 'static>() -> Self {
+        Self::new::<T>(erased_eq::<T>)
+    }
+
+    /// The [`TypeId`] of the values which are compared by the comparator.
+    pub fn type_id(&self) -> TypeId {
+        (self.type_id)()
+    }
+
+    /// Determines if the provided values are equal.
+    pub fn eq(&self, a: &dyn Any, b: &dyn Any) -> bool {
+        (self.eq)(a, b)
+    }
+}
+
+impl Debug for ValueComparator {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("ValueComparator")
+            .field("type_id", &self.type_id())
+            .finish()
+    }
+}
+
+/// The comparators which are used for the values of fields. A comparator is
+/// looked up by the `TypeId` of a value, starting with the registered
+/// comparators and falling back to those for the primitive types and `String`,
+/// which compare values using `PartialEq`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Comparators<'c> {
+    comparators: &'c [ValueComparator],
+}
+
+impl<'c> Comparators<'c> {
+    /// Registers the provided comparators. Earlier comparators take
+    /// precedence over later comparators for the same type.
+    pub const fn new(comparators: &'c [ValueComparator]) -> Self {
+        Self { comparators }
+    }
+
+    /// Finds the comparator for values with the provided `TypeId`.
+    pub fn find(&self, type_id: TypeId) -> Option<&'c ValueComparator> {
+        self.comparators
+            .iter()
+            .chain(DEFAULT_COMPARATORS)
+            .find(|comparator| ValueComparator::type_id(comparator) == type_id)
+    }
+
+    /// Compares two values of the same type, returning the fields which
+    /// changed in the order they are declared.
+    ///
+    /// Values are compared as a whole when a comparator is registered for
+    /// their type or when they hold different enum variants, even if the
+    /// variants have the same fields or none at all. Such changes are
+    /// reported at the path of the value, which is empty for the values which
+    /// were passed in.
+    pub fn diff<'a>(&self, old: &'a dyn Reflect, new: &'a dyn Reflect) -> DiffResult<'a> {
+        if <dyn Any>::type_id(old.as_any()) != <dyn Any>::type_id(new.as_any()) {
+            return Err(DiffError::TypeMismatch {
+                old: old.type_name(),
+                new: new.type_name(),
+            });
+        }
+
+        let mut changes = Vec::new();
+        self.diff_values(&mut String::new(), old, new, &mut changes)?;
+        Ok(changes)
+    }
+
+    fn diff_values<'a>(
+        &self,
+        path: &mut String,
+        old: &'a dyn Reflect,
+        new: &'a dyn Reflect,
+        changes: &mut Vec<FieldChange<'a>>,
+    ) -> Result<(), DiffError> {
+        let same_fields =
+            old.active_variant() == new.active_variant() && old.field_names() == new.field_names();
+        let equal = match self.find(<dyn Any>::type_id(old.as_any())) {
+            Some(comparator) => Some(comparator.eq(old.as_any(), new.as_any())),
+            None if !same_fields => Some(false),
+            None => None,
+        };
+
+        if let Some(equal) = equal {
+            if !equal {
+                changes.push(FieldChange {
+                    path: path.clone(),
+                    type_name: old.type_name(),
+                    redacted: false,
+                    old: old.as_any(),
+                    new: new.as_any(),
+                });
+            }
+
+            return Ok(());
+        }
+
+        for name in old.field_names() {
+            let parent_len = path.len();
+            if !path.is_empty() {
+                path.push('.');
+            }
+
+            path.push_str(name);
+            if let (Ok(old_nested), Ok(new_nested)) =
+                (old.get_nested_raw(name), new.get_nested_raw(name))
+            {
+                self.diff_values(path, old_nested, new_nested, changes)?;
+            } else {
+                self.diff_fields(path, old, new, name, changes)?;
+            }
+
+            path.truncate(parent_len);
+        }
+
+        Ok(())
+    }
+
+    fn diff_fields<'a>(
+        &self,
+        path: &str,
+        old: &'a dyn Reflect,
+        new: &'a dyn Reflect,
+        name: &'static str,
+        changes: &mut Vec<FieldChange<'a>>,
+    ) -> Result<(), DiffError> {
+        let (old_field, new_field) = match (old.get_field_raw(name), new.get_field_raw(name)) {
+            (Ok(old_field), Ok(new_field)) => (old_field, new_field),
+            _ => return Ok(()),
+        };
+
+        let field_info = old.get_field_info(name).ok();
+        let type_name = field_info.map_or("unknown", FieldInfo::type_name);
+        let comparator =
+            self.find(<dyn Any>::type_id(old_field))
+                .ok_or_else(|| DiffError::Unsupported {
+                    path: path.into(),
+                    type_name,
+                })?;
+
+        if !comparator.eq(old_field, new_field) {
+            let redacted = field_info.is_some_and(FieldInfo::is_redacted);
+            changes.push(FieldChange {
+                path: path.into(),
+                type_name,
+                redacted,
+                old: old_field,
+                new: new_field,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Compares two values of the same type using the default comparators,
+/// returning the fields which changed.
+///
+/// ```
+/// use preflect::diff::diff;
+/// use preflect::fields::HasFields;
+///
+/// #[derive(HasFields)]
+/// struct Settings {
+///     theme: &'static str,
+///     volume: u8,
+/// }
+///
+/// let old = Settings { theme: "light", volume: 3 };
+/// let new = Settings { theme: "dark", volume: 3 };
+/// let changes = diff(&old, &new).unwrap();
+/// assert_eq!(1, changes.len());
+/// assert_eq!("theme", changes[0].path());
+/// assert_eq!(Some(&"dark"), changes[0].downcast_new::<&str>());
+/// ```
+pub fn diff<'a, T: Reflect>(old: &'a T, new: &'a T) -> DiffResult<'a> {
+    Comparators::default().diff(old, new)
+}
+
+/// A field whose value differs between two values.
+#[derive(Clone)]
+pub struct FieldChange<'a> {
+    path: String,
+    type_name: &'static str,
+    redacted: bool,
+    old: &'a dyn Any,
+    new: &'a dyn Any,
+}
+
+impl<'a> FieldChange<'a> {
+    /// The path of the field (e.g. `server.port`), which can be passed to
+    /// `get_path`. The path is empty if the values differ as a whole.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// The name of the type of the field.
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// Determines if the field is marked with `#[preflect(redact)]`, in which
+    /// case its values should not be shown.
+    pub fn is_redacted(&self) -> bool {
+        self.redacted
+    }
+
+    /// The type-erased old value of the field.
+    pub fn old_value(&self) -> &'a dyn Any {
+        self.old
+    }
+
+    /// The type-erased new value of the field.
+    pub fn new_value(&self) -> &'a dyn Any {
+        self.new
+    }
+
+    /// The old value of the field if it is of type `T`.
+    pub fn downcast_old<T: 'static>(&self) -> Option<&'a T> {
+        self.old.downcast_ref::<T>()
+    }
+
+    /// The new value of the field if it is of type `T`.
+    pub fn downcast_new<T: 'static>(&self) -> Option<&'a T> {
+        self.new.downcast_ref::<T>()
+    }
+}
+
+impl Debug for FieldChange<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("FieldChange")
+            .field("path", &self.path)
+            .field("type_name", &self.type_name)
+            .field("redacted", &self.redacted)
+            .finish()
+    }
+}
+
+/// An error that occurred while comparing two values.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DiffError {
+    /// The values were of different types.
+    TypeMismatch {
+        /// The name of the type of the old value.
+        old: &'static str,
+        /// The name of the type of the new value.
+        new: &'static str,
+    },
+    /// A field has no comparator for its type.
+    Unsupported {
+        /// The path of the field.
+        path: String,
+        /// The name of the type of the field.
+        type_name: &'static str,
+    },
+}
+
+impl Display for DiffError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            DiffError::TypeMismatch { old, new } => write!(
+                f,
+                "Values of type '{}' and '{}' can not be compared.",
+                old, new
+            ),
+            DiffError::Unsupported { path, type_name } => write!(
+                f,
+                "The field '{}' is of type '{}', which has no comparator.",
+                path, type_name
+            ),
+        }
+    }
+}
+
+/// The comparators used for the primitive types and `String`.
+const DEFAULT_COMPARATORS: &[ValueComparator] = &[
+    ValueComparator::partial_eq::<bool>(),
+    ValueComparator::partial_eq::<char>(),
+    ValueComparator::partial_eq::<i8>(),
+    ValueComparator::partial_eq::<i16>(),
+    ValueComparator::partial_eq::<i32>(),
+    ValueComparator::partial_eq::<i64>(),
+    ValueComparator::partial_eq::<i128>(),
+    ValueComparator::partial_eq::<isize>(),
+    ValueComparator::partial_eq::<u8>(),
+    ValueComparator::partial_eq::<u16>(),
+    ValueComparator::partial_eq::<u32>(),
+    ValueComparator::partial_eq::<u64>(),
+    ValueComparator::partial_eq::<u128>(),
+    ValueComparator::partial_eq::<usize>(),
+    ValueComparator::partial_eq::<f32>(),
+    ValueComparator::partial_eq::<f64>(),
+    ValueComparator::partial_eq::<&'static str>(),
+    ValueComparator::partial_eq::<()>(),
+    ValueComparator::partial_eq::<String>(),
+];
+
+fn erased_eq<T: PartialEq + 'static>(a: &dyn Any, b: &dyn Any) -> bool {
+    match (a.downcast_ref::<T>(), b.downcast_ref::<T>()) {
+        (Some(a), Some(b)) => a == b,
+        _ => false,
+    }
+}
@@ -1,15 +1,101 @@
+//! Provides facilities for dropping and moving out a subset of the fields of
+//! a value.
+
 #![cfg(feature = "partial-drop")]
 
+mod checked;
+mod destructure;
+
+use crate::fields::FieldName;
+#[cfg(feature = "checked-drop")]
+use core::any::Any;
+
+#[cfg(feature = "checked-drop")]
+pub use self::checked::*;
+pub use self::destructure::*;
+pub use preflect_macros::partial_drop;
 pub use preflect_macros::PartialDrop;
 
-/// A trait that allows portions of a struct to be dropped.
-pub trait PartialDrop {
-    /// Drops all fields within the struct except for those provided to the
-    /// function.
+/// A type which has a field that can be preserved by `PartialDrop` using
+/// `Name`, either as the name of the field or one of its aliases. This is
+/// implemented by the `PartialDrop` derive so that `partial_drop!` can check
+/// the names while the code is type checked.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` has no droppable field with this name",
+    label = "unknown field name",
+    note = "the accepted names are listed by `<{Self} as PartialDrop>::ACCEPTED_NAMES`"
+)]
+pub trait HasDroppableField<Name: FieldName> {}
+
+/// Ensures that the type of `value` has a droppable field called `Name`. This
+/// is used by the `partial_drop!` macro.
+pub fn check_droppable_field<Name: FieldName, T: HasDroppableField<Name> + ?Sized>(value: &T) {
+    let _ = value;
+}
+
+/// A trait that allows portions of a struct or enum to be dropped.
+///
+/// # Safety
+/// [`Destructure`] and `CheckedPartialDrop` move fields out of a value and
+/// rely on these methods to drop every other field exactly once. The names
+/// must therefore identify the same fields as the `HasField` descriptors of
+/// the type, `drop_all_fields_except` must drop precisely the fields which
+/// are not preserved and `drop_ignored_fields` must drop the ignored fields.
+/// `drop_field` and `droppable_field_mut` must refer to the same field for
+/// each position.
+/// Implementations should be generated with the `PartialDrop` derive.
+pub unsafe trait PartialDrop {
+    /// The names and aliases by which the fields which can be dropped are
+    /// preserved.
+    const ACCEPTED_NAMES: &'static [&'static str];
+
+    /// Drops all fields within the struct (or the active variant of the enum)
+    /// except for those provided to the function. Fields can be preserved by
+    /// their name or any of their aliases. Fields marked with
+    /// `#[preflect(ignore)]` are never dropped, and can be dropped with
+    /// `drop_ignored_fields` instead.
     ///
     /// # Safety
     /// It is the responsibility of the caller to ensure the preserved fields
     /// are dropped as well and that the fields which were dropped are not
     /// dropped again.
+    ///
+    /// With the `checked-drop` feature, a name which does not belong to any
+    /// field causes a panic before anything is dropped.
     unsafe fn drop_all_fields_except(&mut self, field_names: &[&'static str]);
+
+    /// Drops the fields within the struct (or the active variant of the enum)
+    /// which are marked with `#[preflect(ignore)]`.
+    ///
+    /// # Safety
+    /// The ignored fields must not have been dropped already and must not be
+    /// used again.
+    unsafe fn drop_ignored_fields(&mut self);
+
+    /// Gets the names of the fields which can be dropped, in the order they
+    /// are declared.
+    #[cfg(feature = "checked-drop")]
+    fn droppable_field_names(&self) -> &'static [&'static str];
+
+    /// Gets the position of a field within `droppable_field_names` using its
+    /// name or one of its aliases.
+    #[cfg(feature = "checked-drop")]
+    fn droppable_field_index(&self, name: &str) -> Option<usize>;
+
+    /// Drops the field at the provided position within
+    /// `droppable_field_names`.
+    ///
+    /// # Safety
+    /// The field must not have been dropped already and must not be used
+    /// again.
+    #[cfg(feature = "checked-drop")]
+    unsafe fn drop_field(&mut self, index: usize);
+
+    /// Gets the field at the provided position within
+    /// `droppable_field_names`. `CheckedPartialDrop` moves fields out through
+    /// this reference.
+    #[cfg(feature = "checked-drop")]
+    fn droppable_field_mut(&mut self, index: usize) -> Option<&mut dyn Any>
+    where
+        Self: 'static;
 }
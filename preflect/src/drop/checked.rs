@@ -0,0 +1,191 @@
+#![cfg(feature = "checked-drop")]
+
+use crate::drop::PartialDrop;
+use crate::fields::errors::field_type_name;
+use crate::fields::BaseHasFields;
+use crate::fields::FieldAccessError;
+use core::fmt::Display;
+use core::fmt::Formatter;
+use core::fmt::Result as FmtResult;
+use core::mem::ManuallyDrop;
+use core::ptr::read;
+
+/// The most fields a value can have while its drops are being checked.
+const MAX_CHECKED_FIELDS: usize = 64;
+
+/// An error that occurred while partially dropping a value with
+/// `CheckedPartialDrop`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PartialDropError<'n> {
+    /// The field could not be accessed (e.g. because no field has the
+    /// provided name).
+    Field(FieldAccessError<'n>),
+    /// The field has already been dropped or moved out.
+    AlreadyDropped(&'static str),
+}
+
+impl<'n> From<FieldAccessError<'n>> for PartialDropError<'n> {
+    fn from(error: FieldAccessError<'n>) -> Self {
+        Self::Field(error)
+    }
+}
+
+impl Display for PartialDropError<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            PartialDropError::Field(error) => Display::fmt(error, f),
+            PartialDropError::AlreadyDropped(name) => {
+                write!(f, "The field '{}' has already been dropped.", name)
+            }
+        }
+    }
+}
+
+/// A value whose fields can be dropped (or moved out) individually. It keeps
+/// track of which fields have been dropped so that no field is dropped twice
+/// and every remaining field is dropped along with it. Fields marked with
+/// `#[preflect(ignore)]` can not be dropped individually, and are only dropped
+/// along with the value.
+pub struct CheckedPartialDrop<T: PartialDrop> {
+    value: ManuallyDrop<T>,
+    dropped: u64,
+}
+
+impl<T: PartialDrop> CheckedPartialDrop<T> {
+    /// Wraps a value so that its fields can be dropped individually.
+    ///
+    /// # Panics
+    /// Panics if the value has more than 64 fields which can be dropped.
+    pub fn new(value: T) -> Self {
+        let field_count = value.droppable_field_names().len();
+        if field_count > MAX_CHECKED_FIELDS {
+            panic!(
+                "Only values with up to {} fields can be checked, but the value has {}.",
+                MAX_CHECKED_FIELDS, field_count
+            );
+        }
+
+        Self {
+            value: ManuallyDrop::new(value),
+            dropped: 0,
+        }
+    }
+
+    /// Determines if a field has been dropped using its name or one of its
+    /// aliases.
+    pub fn is_dropped<'n>(&self, name: &'n str) -> Result<bool, PartialDropError<'n>> {
+        let index = self.find_field(name)?;
+        Ok(self.dropped & (1 << index) != 0)
+    }
+
+    /// Drops all fields except for those provided to the function. Every name
+    /// must belong to a field which has not been dropped and none of the
+    /// fields which would be dropped may have been dropped already. Nothing is
+    /// dropped if any of these checks fail.
+    pub fn drop_all_fields_except<'n>(
+        &mut self,
+        field_names: &[&'n str],
+    ) -> Result<(), PartialDropError<'n>> {
+        let mut preserved = 0u64;
+        for name in field_names {
+            preserved |= 1 << self.find_alive_field(name)?;
+        }
+
+        let field_names = self.value.droppable_field_names();
+        for (index, name) in field_names.iter().enumerate() {
+            if preserved & (1 << index) == 0 && self.dropped & (1 << index) != 0 {
+                return Err(PartialDropError::AlreadyDropped(name));
+            }
+        }
+
+        for index in 0..field_names.len() {
+            if preserved & (1 << index) == 0 {
+                self.drop_index(index);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Drops a single field using its name or one of its aliases.
+    pub fn drop_field<'n>(&mut self, name: &'n str) -> Result<(), PartialDropError<'n>> {
+        let index = self.find_alive_field(name)?;
+        self.drop_index(index);
+        Ok(())
+    }
+
+    fn find_field<'n>(&self, name: &'n str) -> Result<usize, PartialDropError<'n>> {
+        self.value.droppable_field_index(name).ok_or_else(|| {
+            let field_names = self.value.droppable_field_names();
+            FieldAccessError::missing_field(name, field_names).into()
+        })
+    }
+
+    fn find_alive_field<'n>(&self, name: &'n str) -> Result<usize, PartialDropError<'n>> {
+        let index = self.find_field(name)?;
+        if self.dropped & (1 << index) != 0 {
+            let field_name = self.value.droppable_field_names()[index];
+            return Err(PartialDropError::AlreadyDropped(field_name));
+        }
+
+        Ok(index)
+    }
+
+    fn drop_index(&mut self, index: usize) {
+        // Marking the field first ensures it is never dropped again, even if
+        // dropping it panics.
+        self.dropped |= 1 << index;
+        unsafe { self.value.drop_field(index) };
+    }
+}
+
+impl<T: PartialDrop + BaseHasFields + 'static> CheckedPartialDrop<T> {
+    /// Moves a field out of the value using its name or one of its aliases.
+    pub fn take_field<'n, U: 'static>(&mut self, name: &'n str) -> Result<U, PartialDropError<'n>> {
+        let index = self.find_alive_field(name)?;
+        let actual_type = field_type_name(&*self.value, name);
+        // The field is located through `PartialDrop`, which is what drops the
+        // remaining fields, so it can not be moved out and dropped again.
+        let field = match self.value.droppable_field_mut(index) {
+            Some(field) => field,
+            None => {
+                let field_names = self.value.droppable_field_names();
+                return Err(FieldAccessError::missing_field(name, field_names).into());
+            }
+        };
+        if !field.is::<U>() {
+            return Err(FieldAccessError::invalid_type::<U>(name, actual_type).into());
+        }
+
+        let field = field.downcast_mut::<U>().unwrap() as *mut U;
+        self.dropped |= 1 << index;
+        Ok(unsafe { read(field) })
+    }
+}
+
+impl<T: PartialDrop> Drop for CheckedPartialDrop<T> {
+    fn drop(&mut self) {
+        let field_count = self.value.droppable_field_names().len();
+        for index in 0..field_count {
+            if self.dropped & (1 << index) == 0 {
+                self.drop_index(index);
+            }
+        }
+
+        unsafe { self.value.drop_ignored_fields() };
+    }
+}
+
+/// Ensures that every name belongs to a field which can be dropped. This is
+/// used by `drop_all_fields_except` in checked mode.
+///
+/// # Panics
+/// Panics if any of the names does not belong to a field.
+pub fn check_field_names<T: PartialDrop + ?Sized>(value: &T, field_names: &[&str]) {
+    for name in field_names {
+        if value.droppable_field_index(name).is_none() {
+            let error = FieldAccessError::missing_field(name, value.droppable_field_names());
+            panic!("{}", error);
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use crate::drop::PartialDrop;
+use crate::fields::BaseHasNamedField;
+use crate::fields::FieldName;
+use core::mem::ManuallyDrop;
+use core::ptr::read;
+
+/// A type whose fields can be moved out by name without writing any unsafe
+/// code. This is available for every type which derives both `PartialDrop`
+/// and `HasField`.
+///
+/// ```ignore
+/// let (first, third) = data.destructure::<(field!(first), field!(third))>();
+/// ```
+///
+/// The fields which are not moved out are dropped exactly once, including the
+/// fields marked with `#[preflect(ignore)]`, which can not be moved out. If the
+/// type implements `Drop` itself, that implementation is not run.
+pub trait Destructure: PartialDrop + Sized {
+    /// Moves the fields named by `Names` out of the value and drops the rest.
+    ///
+    /// # Panics
+    /// Panics if the same field is named more than once (including through
+    /// one of its aliases).
+    fn destructure<Names: FieldList<Self>>(self) -> Names::Fields {
+        Names::take(self)
+    }
+}
+
+impl<T: PartialDrop + Sized> Destructure for T {}
+
+/// A list of field names, written as a tuple of `field!` types, which can be
+/// moved out of a value of type `T`.
+pub trait FieldList<T> {
+    /// The values of the fields, in the order they were named.
+    type Fields;
+
+    /// Moves the fields out of `value` and drops the rest.
+    fn take(value: T) -> Self::Fields;
+}
+
+/// Ensures that no field is moved out more than once.
+fn assert_distinct(names: &[&'static str]) {
+    for (index, name) in names.iter().enumerate() {
+        if names[..index].contains(name) {
+            panic!("The field '{}' can not be moved out more than once.", name);
+        }
+    }
+}
+
+macro_rules! impl_field_list {
+    ($($name:ident),+) => {
+        impl<T, $($name),+> FieldList<T> for ($($name,)+)
+        where
+            T: PartialDrop $(+ BaseHasNamedField<$name>)+,
+            $($name: FieldName,)+
+        {
+            type Fields = ($(<T as BaseHasNamedField<$name>>::FieldType,)+);
+
+            fn take(value: T) -> Self::Fields {
+                let names = [$(<T as BaseHasNamedField<$name>>::INFO.name()),+];
+                assert_distinct(&names);
+
+                let mut value = ManuallyDrop::new(value);
+                let base_address = &mut *value as *mut T as usize;
+
+                // Each named field is distinct, so every field is either read
+                // out exactly once or dropped in place exactly once. Both
+                // traits are unsafe to implement, which guarantees that the
+                // offsets and names they provide describe the same fields.
+                unsafe {
+                    let fields = ($(
+                        read((base_address + <T as BaseHasNamedField<$name>>::offset())
+                            as *const <T as BaseHasNamedField<$name>>::FieldType),
+                    )+);
+                    value.drop_all_fields_except(&names);
+                    value.drop_ignored_fields();
+                    fields
+                }
+            }
+        }
+    };
+}
+
+impl_field_list!(A);
+impl_field_list!(A, B);
+impl_field_list!(A, B, C);
+impl_field_list!(A, B, C, D);
+impl_field_list!(A, B, C, D, E);
+impl_field_list!(A, B, C, D, E, F);
+impl_field_list!(A, B, C, D, E, F, G);
+impl_field_list!(A, B, C, D, E, F, G, H);
@@ -1,7 +1,31 @@
 //! Provides facilities for using reflection to access fields and their values.
 
 mod aot;
+mod construct;
+mod dynamic;
+pub(crate) mod errors;
+mod info;
+mod iter;
+mod named;
+mod patch;
+mod path;
+mod reflect;
 mod run_time;
+mod variants;
 
+#[cfg(feature = "nightly")]
 pub use self::aot::*;
+#[cfg(feature = "alloc")]
+pub use self::construct::*;
+#[cfg(feature = "alloc")]
+pub use self::dynamic::*;
+pub use self::errors::*;
+pub use self::info::*;
+pub use self::iter::*;
+pub use self::named::*;
+#[cfg(feature = "alloc")]
+pub use self::patch::*;
+pub use self::path::*;
+pub use self::reflect::*;
 pub use self::run_time::*;
+pub use self::variants::*;
@@ -1,10 +1,21 @@
-pub use preflect_macros::HasField;
+#![cfg(feature = "nightly")]
+
+use crate::fields::FieldInfo;
 
 /// A type who has a field that can be accessed through reflection.
-pub trait BaseHasField<const NAME: &'static str> {
+///
+/// # Safety
+///
+/// `offset` must return the offset in bytes of a field of the implementing
+/// type whose type is `FieldType`, since [`HasField`] reads the field through
+/// that offset. Implementations should be generated with the `HasField` derive.
+pub unsafe trait BaseHasField<const NAME: &'static str> {
     /// The type of the field's value.
     type FieldType: 'static;
 
+    /// The descriptor of the field.
+    const INFO: FieldInfo;
+
     /// Calculates the number of bytes from the pointer to the struct at which
     /// the field resides.
     fn offset() -> usize;
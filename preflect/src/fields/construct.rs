@@ -0,0 +1,117 @@
+#![cfg(feature = "alloc")]
+
+use crate::fields::FieldAccessError;
+use crate::fields::FieldAccessErrorKind;
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use core::any::Any;
+use core::fmt::Display;
+use core::fmt::Formatter;
+use core::fmt::Result as FmtResult;
+pub use preflect_macros::FromVariant;
+
+/// The values of the fields used to construct a value at runtime, keyed by
+/// the names of the fields.
+pub type FieldMap = BTreeMap<String, Box<dyn Any>>;
+
+/// A trait for enums which can be constructed at runtime from the name of a
+/// variant and the values of its fields.
+pub trait FromVariant: Sized {
+    /// Constructs the variant with the provided name using the values in
+    /// `fields`. Each reflected field of the variant must have a value in the
+    /// map (under its name or one of its aliases) and the map must not contain
+    /// any other values. Fields which are ignored are set to their default.
+    fn from_variant(variant: &str, fields: FieldMap) -> Result<Self, FromVariantError<'_>>;
+}
+
+/// An error that occurred while constructing a value from the name of a
+/// variant and a map of field values. The value which was rejected (a value of
+/// the wrong type or the value of an unknown field) is returned to the caller.
+#[derive(Debug)]
+pub struct FromVariantError<'n> {
+    error: FieldAccessError<'n>,
+    field: Option<String>,
+    value: Option<Box<dyn Any>>,
+}
+
+impl<'n> FromVariantError<'n> {
+    /// Gets the reason the value could not be constructed. The name of an
+    /// unknown field is available from [`field`](Self::field).
+    pub fn error(&self) -> &FieldAccessError<'n> {
+        &self.error
+    }
+
+    /// The kind of error which occurred.
+    pub fn kind(&self) -> FieldAccessErrorKind {
+        self.error.kind()
+    }
+
+    /// The name of the field which was provided but does not exist for the
+    /// variant.
+    pub fn field(&self) -> Option<&str> {
+        self.field.as_deref()
+    }
+
+    /// Takes back the value which was rejected, if any.
+    pub fn into_value(self) -> Option<Box<dyn Any>> {
+        self.value
+    }
+}
+
+impl<'n> From<FieldAccessError<'n>> for FromVariantError<'n> {
+    fn from(error: FieldAccessError<'n>) -> Self {
+        Self {
+            error,
+            field: None,
+            value: None,
+        }
+    }
+}
+
+impl Display for FromVariantError<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match &self.field {
+            Some(field) => Display::fmt(&self.error.with_name(Some(field)), f),
+            None => Display::fmt(&self.error, f),
+        }
+    }
+}
+
+/// Removes the value of a field from `fields` and downcasts it to the type of
+/// the field. The first of `names` which has a value in the map is used. The
+/// first of `names` is reported as the field in any error, and a value of the
+/// wrong type is returned in the error.
+pub fn remove_field<T: 'static>(
+    fields: &mut FieldMap,
+    names: &[&'static str],
+) -> Result<T, FromVariantError<'static>> {
+    let name = names.first().copied().unwrap_or_default();
+    let value = names
+        .iter()
+        .find_map(|name| fields.remove(*name))
+        .ok_or_else(|| FieldAccessError::new(FieldAccessErrorKind::MissingField, name))?;
+
+    value
+        .downcast::<T>()
+        .map(|value| *value)
+        .map_err(|value| FromVariantError {
+            error: FieldAccessError::invalid_type::<T>(name, None),
+            field: None,
+            value: Some(value),
+        })
+}
+
+/// Ensures that every value in `fields` has been used to construct a value.
+/// Otherwise the first unknown field is named by the error, which also
+/// returns its value.
+pub fn ensure_fields_taken(fields: FieldMap) -> Result<(), FromVariantError<'static>> {
+    match fields.into_iter().next() {
+        None => Ok(()),
+        Some((field, value)) => Err(FromVariantError {
+            error: FieldAccessErrorKind::UnknownField.into(),
+            field: Some(field),
+            value: Some(value),
+        }),
+    }
+}
@@ -0,0 +1,471 @@
+#![cfg(feature = "alloc")]
+
+use crate::fields::errors::field_type_name;
+use crate::fields::BaseHasFields;
+use crate::fields::FieldAccessError;
+use crate::fields::FieldAccessResult;
+use crate::fields::FieldInfo;
+use crate::fields::Reflect;
+use crate::fmt::ReflectFormat;
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::any::Any;
+use core::any::TypeId;
+use core::fmt::Debug;
+use core::fmt::Formatter;
+use core::fmt::Result as FmtResult;
+use core::hint::spin_loop;
+use core::ptr::eq;
+use core::ptr::null_mut;
+use core::sync::atomic::AtomicBool;
+use core::sync::atomic::AtomicPtr;
+use core::sync::atomic::Ordering;
+
+/// Describes the fields of a [`DynamicStruct`]. A layout is built once for
+/// each kind of struct (e.g. each record type defined by a user) and is shared
+/// by all of its values.
+///
+/// Reflected fields are described with `'static` names, so a layout and its
+/// names are leaked when it is built. Layouts are interned: building a layout
+/// with the same name and fields as one which was already built returns the
+/// existing layout, so each distinct layout is only leaked once.
+pub struct DynamicLayout {
+    name: &'static str,
+    field_names: &'static [&'static str],
+    field_info: &'static [FieldInfo],
+    field_ops: &'static [FieldOps],
+}
+
+impl DynamicLayout {
+    /// Starts building a layout for structs with the provided name.
+    pub fn builder(name: impl Into<String>) -> DynamicLayoutBuilder {
+        DynamicLayoutBuilder {
+            name: name.into(),
+            fields: Vec::new(),
+        }
+    }
+
+    /// The name of the structs which have the layout.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// The names of the fields in the order they were added.
+    pub fn field_names(&self) -> &'static [&'static str] {
+        self.field_names
+    }
+
+    /// The descriptors of the fields in the order they were added.
+    pub fn field_info(&self) -> &'static [FieldInfo] {
+        self.field_info
+    }
+
+    fn index_of(&self, name: &str) -> Option<usize> {
+        self.field_names.iter().position(|field| *field == name)
+    }
+}
+
+impl Debug for DynamicLayout {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("DynamicLayout")
+            .field("name", &self.name)
+            .field("field_info", &self.field_info)
+            .finish()
+    }
+}
+
+/// Builds a [`DynamicLayout`] one field at a time.
+pub struct DynamicLayoutBuilder {
+    name: String,
+    fields: Vec<FieldEntry>,
+}
+
+struct FieldEntry {
+    name: String,
+    type_id: TypeId,
+    make_info: fn(&'static str, usize) -> FieldInfo,
+    ops: FieldOps,
+}
+
+impl DynamicLayoutBuilder {
+    /// Adds a field whose value is of type `T`. New structs start with the
+    /// default value of each field.
+    ///
+    /// # Panics
+    /// Panics if the layout already has a field with the same name.
+    pub fn field<T: Any + Clone + Default>(self, name: impl Into<String>) -> Self {
+        let ops = FieldOps::of::<T>(DefaultValue::Of(|| Box::new(T::default())));
+        self.add::<T>(name.into(), FieldInfo::new_dynamic::<T>, ops)
+    }
+
+    /// Adds a nested field whose value is a [`DynamicStruct`] with the
+    /// provided layout. New structs start with a nested struct whose fields
+    /// have their default values.
+    ///
+    /// # Panics
+    /// Panics if the layout already has a field with the same name.
+    pub fn nested(self, name: impl Into<String>, layout: &'static DynamicLayout) -> Self {
+        let ops = FieldOps::of::<DynamicStruct>(DefaultValue::Nested(layout));
+
+        self.add::<DynamicStruct>(name.into(), FieldInfo::new_dynamic::<DynamicStruct>, ops)
+    }
+
+    fn add<T: Any>(
+        mut self,
+        name: String,
+        make_info: fn(&'static str, usize) -> FieldInfo,
+        ops: FieldOps,
+    ) -> Self {
+        if self.fields.iter().any(|field| field.name == name) {
+            panic!("The field '{}' was already added to the layout.", name);
+        }
+
+        self.fields.push(FieldEntry {
+            name,
+            type_id: TypeId::of::<T>(),
+            make_info,
+            ops,
+        });
+        self
+    }
+
+    /// Builds the layout so that it can be shared by any number of structs.
+    /// If an identical layout was built before, that layout is returned
+    /// instead of leaking another copy.
+    pub fn build(self) -> &'static DynamicLayout {
+        let _lock = InternedLayouts::lock();
+
+        // Layouts are only added while the lock is held and are never freed.
+        let latest = unsafe { INTERNED_LAYOUTS.load(Ordering::Acquire).as_ref() };
+        let mut interned = latest;
+        while let Some(existing) = interned {
+            if self.matches(&existing.layout) {
+                return &existing.layout;
+            }
+
+            interned = existing.previous;
+        }
+
+        let interned = Box::leak(Box::new(InternedLayout {
+            layout: self.leak(),
+            previous: latest,
+        }));
+        INTERNED_LAYOUTS.store(interned, Ordering::Release);
+        &interned.layout
+    }
+
+    /// Determines if the layout being built has the same name and fields as
+    /// an existing layout.
+    fn matches(&self, layout: &DynamicLayout) -> bool {
+        self.name == layout.name
+            && self.fields.len() == layout.field_names.len()
+            && self.fields.iter().enumerate().all(|(index, field)| {
+                field.name == layout.field_names[index]
+                    && field.type_id == FieldInfo::type_id(&layout.field_info[index])
+                    && field.ops.default.matches(&layout.field_ops[index].default)
+            })
+    }
+
+    /// Leaks the names and descriptors of the fields so that they can be
+    /// reflected with `'static` lifetimes.
+    fn leak(self) -> DynamicLayout {
+        let mut field_names = Vec::with_capacity(self.fields.len());
+        let mut field_info = Vec::with_capacity(self.fields.len());
+        let mut field_ops = Vec::with_capacity(self.fields.len());
+        for (index, field) in self.fields.into_iter().enumerate() {
+            let name: &'static str = Box::leak(field.name.into_boxed_str());
+            field_names.push(name);
+            field_info.push((field.make_info)(name, index));
+            field_ops.push(field.ops);
+        }
+
+        DynamicLayout {
+            name: Box::leak(self.name.into_boxed_str()),
+            field_names: field_names.leak(),
+            field_info: field_info.leak(),
+            field_ops: field_ops.leak(),
+        }
+    }
+}
+
+/// A layout which has been built, linked to the layout built before it.
+struct InternedLayout {
+    layout: DynamicLayout,
+    previous: Option<&'static InternedLayout>,
+}
+
+/// The most recently built layout.
+static INTERNED_LAYOUTS: AtomicPtr<InternedLayout> = AtomicPtr::new(null_mut());
+
+/// Whether a layout is being looked up or added.
+static INTERNED_LAYOUTS_LOCKED: AtomicBool = AtomicBool::new(false);
+
+/// Holds the lock on the interned layouts, releasing it when dropped. Looking
+/// up and adding a layout happens while the lock is held so that the same
+/// layout is never added twice.
+struct InternedLayouts;
+
+impl InternedLayouts {
+    fn lock() -> Self {
+        while INTERNED_LAYOUTS_LOCKED
+            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            spin_loop();
+        }
+
+        Self
+    }
+}
+
+impl Drop for InternedLayouts {
+    fn drop(&mut self) {
+        INTERNED_LAYOUTS_LOCKED.store(false, Ordering::Release);
+    }
+}
+
+/// The operations on the value of a field which require its type.
+#[derive(Clone, Copy)]
+struct FieldOps {
+    default: DefaultValue,
+    clone: fn(&dyn Any) -> Box<dyn Any>,
+    clone_from: fn(&mut dyn Any, &dyn Any),
+}
+
+impl FieldOps {
+    fn of<T: Any + Clone>(default: DefaultValue) -> Self {
+        Self {
+            default,
+            clone: |value| Box::new(value.downcast_ref::<T>().unwrap().clone()),
+            clone_from: |target, source| {
+                let source = source.downcast_ref::<T>().unwrap();
+                target.downcast_mut::<T>().unwrap().clone_from(source);
+            },
+        }
+    }
+
+    fn default_value(&self) -> Box<dyn Any> {
+        match self.default {
+            DefaultValue::Of(default) => default(),
+            DefaultValue::Nested(layout) => Box::new(DynamicStruct::new(layout)),
+        }
+    }
+}
+
+/// How the initial value of a field is created.
+#[derive(Clone, Copy)]
+enum DefaultValue {
+    /// The value is created by a function (usually `Default::default`).
+    Of(fn() -> Box<dyn Any>),
+    /// The value is a [`DynamicStruct`] with the layout.
+    Nested(&'static DynamicLayout),
+}
+
+impl DefaultValue {
+    /// Determines if two fields of the same type start with the same value.
+    /// The default value of a type is always the same, and nested layouts are
+    /// interned, so they match only if they are the same layout.
+    fn matches(&self, other: &DefaultValue) -> bool {
+        match (self, other) {
+            (DefaultValue::Of(_), DefaultValue::Of(_)) => true,
+            (DefaultValue::Nested(layout), DefaultValue::Nested(other)) => eq(*layout, *other),
+            _ => false,
+        }
+    }
+}
+
+/// A struct whose fields are defined at run time by a [`DynamicLayout`]. It
+/// implements [`BaseHasFields`], so it can be accessed in the same way as a
+/// struct which derives `HasFields`. Fields whose values are themselves
+/// dynamic structs are treated as nested fields.
+///
+/// ```
+/// use preflect::fields::DynamicLayout;
+/// use preflect::fields::DynamicStruct;
+/// use preflect::fields::HasFields;
+///
+/// let layout = DynamicLayout::builder("Record")
+///     .field::<String>("name")
+///     .field::<u32>("age")
+///     .build();
+///
+/// let mut record = DynamicStruct::new(layout);
+/// *record.get_field_mut::<u32>("age").unwrap() = 31;
+/// assert_eq!(Ok(&31), record.get_field::<u32>("age"));
+/// ```
+pub struct DynamicStruct {
+    layout: &'static DynamicLayout,
+    values: Vec<Box<dyn Any>>,
+}
+
+impl DynamicStruct {
+    /// Creates a struct with the provided layout whose fields have their
+    /// default values.
+    pub fn new(layout: &'static DynamicLayout) -> Self {
+        let values = layout
+            .field_ops
+            .iter()
+            .map(FieldOps::default_value)
+            .collect();
+        Self { layout, values }
+    }
+
+    /// The layout which defines the fields of the struct.
+    pub fn layout(&self) -> &'static DynamicLayout {
+        self.layout
+    }
+
+    /// Copies the value of every field of this struct into the field of the
+    /// target with the same name. Fields whose values are dynamic structs are
+    /// applied to nested fields of the target. Nothing is copied unless every
+    /// field exists on the target with the same type.
+    pub fn apply_to(&self, target: &mut dyn Reflect) -> FieldAccessResult<'static, ()> {
+        self.check_matches(target)?;
+        self.copy_to(target);
+        Ok(())
+    }
+
+    /// Copies the value of every field of this struct from the field of the
+    /// source with the same name. Fields whose values are dynamic structs are
+    /// copied from nested fields of the source. Nothing is copied unless every
+    /// field exists on the source with the same type.
+    pub fn clone_from_reflect(&mut self, source: &dyn Reflect) -> FieldAccessResult<'static, ()> {
+        self.check_matches(source)?;
+        self.copy_from(source);
+        Ok(())
+    }
+
+    /// Ensures that every field of this struct has a counterpart of the same
+    /// type (or a nested counterpart) in `other`.
+    fn check_matches(&self, other: &dyn Reflect) -> FieldAccessResult<'static, ()> {
+        for (index, name) in self.layout.field_names.iter().enumerate() {
+            let other_field = other
+                .get_field_raw(name)
+                .map_err(|_| FieldAccessError::missing_field(name, other.field_names()))?;
+
+            if let Some(dynamic) = self.values[index].downcast_ref::<DynamicStruct>() {
+                if let Ok(nested) = other.get_nested_raw(name) {
+                    dynamic.check_matches(nested)?;
+                    continue;
+                }
+            }
+
+            let info = &self.layout.field_info[index];
+            if <dyn Any>::type_id(other_field) != FieldInfo::type_id(info) {
+                let actual_type = field_type_name(other, name);
+                return Err(FieldAccessError::mismatched_value(name, actual_type));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn copy_to(&self, target: &mut dyn Reflect) {
+        for (index, name) in self.layout.field_names.iter().enumerate() {
+            let value = &*self.values[index];
+            if let Some(dynamic) = value.downcast_ref::<DynamicStruct>() {
+                if target.get_nested_raw(name).is_ok() {
+                    if let Ok(nested) = target.get_nested_mut_raw(name) {
+                        dynamic.copy_to(nested);
+                    }
+
+                    continue;
+                }
+            }
+
+            if let Ok(field) = target.get_field_mut_raw(name) {
+                (self.layout.field_ops[index].clone_from)(field, value);
+            }
+        }
+    }
+
+    fn copy_from(&mut self, source: &dyn Reflect) {
+        for (index, name) in self.layout.field_names.iter().enumerate() {
+            let value = &mut *self.values[index];
+            if let Some(dynamic) = value.downcast_mut::<DynamicStruct>() {
+                if let Ok(nested) = source.get_nested_raw(name) {
+                    dynamic.copy_from(nested);
+                    continue;
+                }
+            }
+
+            if let Ok(field) = source.get_field_raw(name) {
+                (self.layout.field_ops[index].clone_from)(value, field);
+            }
+        }
+    }
+}
+
+impl Clone for DynamicStruct {
+    fn clone(&self) -> Self {
+        let values = self
+            .layout
+            .field_ops
+            .iter()
+            .zip(&self.values)
+            .map(|(ops, value)| (ops.clone)(&**value))
+            .collect();
+
+        Self {
+            layout: self.layout,
+            values,
+        }
+    }
+}
+
+impl Debug for DynamicStruct {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        Debug::fmt(&ReflectFormat::new(self), f)
+    }
+}
+
+impl BaseHasFields for DynamicStruct {
+    fn field_names(&self) -> &'static [&'static str] {
+        self.layout.field_names
+    }
+
+    fn field_info(&self) -> &'static [FieldInfo] {
+        self.layout.field_info
+    }
+
+    fn get_field_raw<'s, 'n>(&'s self, name: &'n str) -> FieldAccessResult<'n, &'s dyn Any> {
+        match self.layout.index_of(name) {
+            Some(index) => Ok(&*self.values[index]),
+            None => Err(FieldAccessError::missing_field(name, self.field_names())),
+        }
+    }
+
+    fn get_field_mut_raw<'s, 'n>(
+        &'s mut self,
+        name: &'n str,
+    ) -> FieldAccessResult<'n, &'s mut dyn Any> {
+        match self.layout.index_of(name) {
+            Some(index) => Ok(&mut *self.values[index]),
+            None => Err(FieldAccessError::missing_field(name, self.field_names())),
+        }
+    }
+
+    fn visit_fields_mut<'s>(&'s mut self, visit: &mut dyn FnMut(&'static str, &'s mut dyn Any)) {
+        for (name, value) in self.layout.field_names.iter().zip(&mut self.values) {
+            visit(name, &mut **value);
+        }
+    }
+
+    fn get_nested_raw<'s, 'n>(&'s self, name: &'n str) -> FieldAccessResult<'n, &'s dyn Reflect> {
+        self.get_field_raw(name)?
+            .downcast_ref::<DynamicStruct>()
+            .map(|nested| nested as &dyn Reflect)
+            .ok_or_else(|| FieldAccessError::not_nested(name))
+    }
+
+    fn get_nested_mut_raw<'s, 'n>(
+        &'s mut self,
+        name: &'n str,
+    ) -> FieldAccessResult<'n, &'s mut dyn Reflect> {
+        self.get_field_mut_raw(name)?
+            .downcast_mut::<DynamicStruct>()
+            .map(|nested| nested as &mut dyn Reflect)
+            .ok_or_else(|| FieldAccessError::not_nested(name))
+    }
+}
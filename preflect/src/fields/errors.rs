@@ -1,4 +1,288 @@
+use crate::fields::BaseHasFields;
+use crate::fields::FieldInfo;
+#[cfg(feature = "alloc")]
+use crate::pointers::PointerError;
+use core::any::type_name;
 use core::fmt::Display;
 use core::fmt::Formatter;
 use core::fmt::Result as FmtResult;
 
+/// The result of accessing a field dynamically
+pub type FieldAccessResult<'n, T> = Result<T, FieldAccessError<'n>>;
+
+/// The longest name (in characters) for which a suggestion will be made.
+const MAX_SUGGESTION_LEN: usize = 64;
+
+/// The reason a field could not be accessed through the preflect API. Some
+/// kinds only exist when a feature is enabled (e.g. `Pointer` needs `alloc`),
+/// so matches must handle kinds they do not know about.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum FieldAccessErrorKind {
+    /// A specified field was not found for the type.
+    MissingField,
+    /// A field was provided which does not exist for the type.
+    UnknownField,
+    /// A specified variant was not found for the enum.
+    MissingVariant,
+    /// The type of the field was not of the expected type.
+    InvalidType,
+    /// The specified field belongs to a variant of the enum which is not the
+    /// active variant.
+    InactiveVariant,
+    /// The specified field can not be reflected itself, so it can not be
+    /// traversed by a path.
+    NotNested,
+    /// The specified field is not a collection (e.g. a field which is not
+    /// marked with `#[preflect(collection)]`).
+    NotCollection,
+    /// The specified field is not a pointer (e.g. a field which is not
+    /// marked with `#[preflect(pointer)]`).
+    NotPointer,
+    /// The target of the specified pointer could not be accessed (e.g. the
+    /// value inside a `RefCell` which was accessed immutably).
+    #[cfg(feature = "alloc")]
+    Pointer(PointerError),
+}
+
+/// An error that occurred while accessing a field through the preflect API.
+/// Along with the kind of error, it describes the field which was requested
+/// whenever that information is available.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct FieldAccessError<'n> {
+    kind: FieldAccessErrorKind,
+    name: Option<&'n str>,
+    expected_type: Option<&'static str>,
+    actual_type: Option<&'static str>,
+    suggestion: Option<&'static str>,
+}
+
+impl<'n> FieldAccessError<'n> {
+    /// Creates an error of the provided kind for the field with the
+    /// provided name.
+    pub fn new(kind: FieldAccessErrorKind, name: &'n str) -> Self {
+        Self {
+            name: Some(name),
+            ..Self::from(kind)
+        }
+    }
+
+    /// Creates an error for a field which does not exist. The closest of the
+    /// `candidates` to `name` is suggested in its place.
+    pub fn missing_field(name: &'n str, candidates: &[&'static str]) -> Self {
+        Self {
+            suggestion: find_suggestion(name, candidates),
+            ..Self::new(FieldAccessErrorKind::MissingField, name)
+        }
+    }
+
+    /// Creates an error for a field which is not of the type `T`.
+    pub fn invalid_type<T: ?Sized>(name: &'n str, actual_type: Option<&'static str>) -> Self {
+        Self {
+            expected_type: Some(type_name::<T>()),
+            actual_type,
+            ..Self::new(FieldAccessErrorKind::InvalidType, name)
+        }
+    }
+
+    /// Creates an error for a type-erased value which is not of the same type
+    /// as the field it was assigned to.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn mismatched_value(name: &'n str, actual_type: Option<&'static str>) -> Self {
+        Self {
+            actual_type,
+            ..Self::new(FieldAccessErrorKind::InvalidType, name)
+        }
+    }
+
+    /// Copies the error for a field with a different name (e.g. a copy of
+    /// the name which lives longer).
+    #[cfg(feature = "alloc")]
+    pub(crate) fn with_name<'m>(self, name: Option<&'m str>) -> FieldAccessError<'m> {
+        FieldAccessError {
+            kind: self.kind,
+            name,
+            expected_type: self.expected_type,
+            actual_type: self.actual_type,
+            suggestion: self.suggestion,
+        }
+    }
+
+    /// Creates an error for a field which belongs to a variant which is not
+    /// active.
+    pub fn inactive_variant(name: &'n str) -> Self {
+        Self::new(FieldAccessErrorKind::InactiveVariant, name)
+    }
+
+    /// Creates an error for a field which can not be traversed by a path.
+    pub fn not_nested(name: &'n str) -> Self {
+        Self::new(FieldAccessErrorKind::NotNested, name)
+    }
+
+    /// Creates an error for a field which can not be accessed as a collection.
+    pub fn not_collection(name: &'n str) -> Self {
+        Self::new(FieldAccessErrorKind::NotCollection, name)
+    }
+
+    /// Creates an error for a field which can not be accessed as a pointer.
+    pub fn not_pointer(name: &'n str) -> Self {
+        Self::new(FieldAccessErrorKind::NotPointer, name)
+    }
+
+    /// Creates an error for a pointer whose target could not be accessed.
+    #[cfg(feature = "alloc")]
+    pub fn pointer(name: &'n str, error: PointerError) -> Self {
+        Self::new(FieldAccessErrorKind::Pointer(error), name)
+    }
+
+    /// Creates an error for a variant which does not exist.
+    pub fn missing_variant(name: &'n str) -> Self {
+        Self::new(FieldAccessErrorKind::MissingVariant, name)
+    }
+
+    /// The kind of error which occurred.
+    pub fn kind(&self) -> FieldAccessErrorKind {
+        self.kind
+    }
+
+    /// The name of the field (or variant) which was requested.
+    pub fn name(&self) -> Option<&'n str> {
+        self.name
+    }
+
+    /// The name of the type which the field was expected to be.
+    pub fn expected_type(&self) -> Option<&'static str> {
+        self.expected_type
+    }
+
+    /// The name of the type which the field actually is.
+    pub fn actual_type(&self) -> Option<&'static str> {
+        self.actual_type
+    }
+
+    /// The name of an existing field which is similar to the requested name.
+    pub fn suggestion(&self) -> Option<&'static str> {
+        self.suggestion
+    }
+}
+
+impl From<FieldAccessErrorKind> for FieldAccessError<'_> {
+    fn from(kind: FieldAccessErrorKind) -> Self {
+        Self {
+            kind,
+            name: None,
+            expected_type: None,
+            actual_type: None,
+            suggestion: None,
+        }
+    }
+}
+
+impl Display for FieldAccessError<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let subject = match self.kind {
+            FieldAccessErrorKind::MissingVariant => "variant",
+            _ => "field",
+        };
+
+        match self.name {
+            Some(name) => write!(f, "The {} '{}'", subject, name)?,
+            None => write!(f, "The specified {}", subject)?,
+        }
+
+        match self.kind {
+            FieldAccessErrorKind::MissingField => {
+                write!(f, " did not exist for the object.")?;
+                if let Some(suggestion) = self.suggestion {
+                    write!(f, " Did you mean '{}'?", suggestion)?;
+                }
+
+                Ok(())
+            }
+            FieldAccessErrorKind::UnknownField => {
+                write!(f, " was provided but did not exist for the object.")
+            }
+            FieldAccessErrorKind::MissingVariant => {
+                write!(f, " did not exist for the enum.")
+            }
+            FieldAccessErrorKind::InvalidType => match (self.actual_type, self.expected_type) {
+                (Some(actual), Some(expected)) => {
+                    write!(f, " is of type '{}', not '{}'.", actual, expected)
+                }
+                (None, Some(expected)) => write!(f, " is not of type '{}'.", expected),
+                (Some(actual), None) => {
+                    write!(
+                        f,
+                        " is of type '{}', which the value does not match.",
+                        actual
+                    )
+                }
+                _ => write!(f, " is of a different type."),
+            },
+            FieldAccessErrorKind::InactiveVariant => {
+                write!(f, " belongs to a variant which is not active.")
+            }
+            FieldAccessErrorKind::NotNested => {
+                write!(f, " can not be traversed through reflection.")
+            }
+            FieldAccessErrorKind::NotCollection => {
+                write!(f, " can not be accessed as a collection.")
+            }
+            FieldAccessErrorKind::NotPointer => write!(f, " can not be accessed as a pointer."),
+            #[cfg(feature = "alloc")]
+            FieldAccessErrorKind::Pointer(error) => {
+                write!(f, " could not be accessed as a pointer. {}", error)
+            }
+        }
+    }
+}
+
+/// Gets the name of the type of the field called `name`, provided that the
+/// field exists.
+pub(crate) fn field_type_name<T: ?Sized + BaseHasFields>(
+    owner: &T,
+    name: &str,
+) -> Option<&'static str> {
+    owner.get_field_info(name).ok().map(FieldInfo::type_name)
+}
+
+/// Finds the candidate which is closest to `name`, provided that it is close
+/// enough to be a likely typo.
+fn find_suggestion(name: &str, candidates: &[&'static str]) -> Option<&'static str> {
+    let max_distance = core::cmp::max(name.chars().count(), 3) / 3;
+    candidates
+        .iter()
+        .filter_map(|candidate| {
+            edit_distance(name, candidate)
+                .filter(|distance| *distance <= max_distance)
+                .map(|distance| (distance, *candidate))
+        })
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// Calculates the Levenshtein distance between two names. Names which are too
+/// long to compare without allocating are skipped.
+fn edit_distance(a: &str, b: &str) -> Option<usize> {
+    let b_len = b.chars().count();
+    if a.chars().count() > MAX_SUGGESTION_LEN || b_len > MAX_SUGGESTION_LEN {
+        return None;
+    }
+
+    let mut row = [0usize; MAX_SUGGESTION_LEN + 1];
+    for (j, cell) in row.iter_mut().enumerate().take(b_len + 1) {
+        *cell = j;
+    }
+
+    for (i, a_char) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, b_char) in b.chars().enumerate() {
+            let substitution = diagonal + if a_char == b_char { 0 } else { 1 };
+            diagonal = row[j + 1];
+            row[j + 1] = core::cmp::min(substitution, core::cmp::min(row[j], row[j + 1]) + 1);
+        }
+    }
+
+    Some(row[b_len])
+}
@@ -0,0 +1,171 @@
+use core::any::type_name;
+use core::any::TypeId;
+use core::mem::align_of;
+use core::mem::size_of;
+
+/// A type whose reflected fields can be described without an instance.
+pub trait HasFieldInfo {
+    /// The descriptors of the reflected fields in the order they are declared.
+    const FIELD_INFO: &'static [FieldInfo];
+
+    /// The names and aliases by which the reflected fields can be accessed.
+    const ACCEPTED_NAMES: &'static [&'static str];
+}
+
+/// Describes a field that can be accessed through reflection.
+#[derive(Clone, Copy, Debug)]
+pub struct FieldInfo {
+    name: &'static str,
+    index: usize,
+    variant: Option<&'static str>,
+    visibility: Visibility,
+    size: usize,
+    align: usize,
+    type_name: fn() -> &'static str,
+    type_id: fn() -> TypeId,
+    offset: Option<fn() -> usize>,
+    redacted: bool,
+}
+
+impl FieldInfo {
+    /// Creates the descriptor of a field whose value is of type `T`. This is
+    /// used by the derive macros and should rarely be needed elsewhere.
+    pub const fn new<T: 'static>(
+        name: &'static str,
+        index: usize,
+        visibility: Visibility,
+        offset: fn() -> usize,
+    ) -> Self {
+        Self {
+            name,
+            index,
+            variant: None,
+            visibility,
+            size: size_of::<T>(),
+            align: align_of::<T>(),
+            type_name: type_name::<T>,
+            type_id: TypeId::of::<T>,
+            offset: Some(offset),
+            redacted: false,
+        }
+    }
+
+    /// Creates the descriptor of a field of an enum variant whose value is of
+    /// type `T`. This is used by the derive macros and should rarely be needed
+    /// elsewhere.
+    pub const fn new_variant_field<T: 'static>(
+        variant: &'static str,
+        name: &'static str,
+        index: usize,
+    ) -> Self {
+        Self {
+            name,
+            index,
+            variant: Some(variant),
+            visibility: Visibility::Public,
+            size: size_of::<T>(),
+            align: align_of::<T>(),
+            type_name: type_name::<T>,
+            type_id: TypeId::of::<T>,
+            offset: None,
+            redacted: false,
+        }
+    }
+
+    /// Creates the descriptor of a field of a struct built at run time whose
+    /// value is of type `T`.
+    #[cfg(feature = "alloc")]
+    pub(crate) const fn new_dynamic<T: 'static>(name: &'static str, index: usize) -> Self {
+        Self {
+            name,
+            index,
+            variant: None,
+            visibility: Visibility::Public,
+            size: size_of::<T>(),
+            align: align_of::<T>(),
+            type_name: type_name::<T>,
+            type_id: TypeId::of::<T>,
+            offset: None,
+            redacted: false,
+        }
+    }
+
+    /// Marks the field as redacted so that its value is hidden when it is
+    /// formatted through reflection. This is used by the derive macros for
+    /// fields marked with `#[preflect(redact)]`.
+    pub const fn redact(self) -> Self {
+        Self {
+            redacted: true,
+            ..self
+        }
+    }
+
+    /// The name of the field.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// The position of the field within the declaration of its type (or
+    /// variant), counting fields which are not reflected.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// The name of the enum variant the field belongs to, or `None` if the
+    /// field belongs to a struct.
+    pub fn variant(&self) -> Option<&'static str> {
+        self.variant
+    }
+
+    /// The visibility the field was declared with. The fields of enum variants
+    /// are always public.
+    pub fn visibility(&self) -> Visibility {
+        self.visibility
+    }
+
+    /// The name of the field's type as reported by [`core::any::type_name`].
+    pub fn type_name(&self) -> &'static str {
+        (self.type_name)()
+    }
+
+    /// The [`TypeId`] of the field's type.
+    pub fn type_id(&self) -> TypeId {
+        (self.type_id)()
+    }
+
+    /// The size of the field's type in bytes.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// The alignment of the field's type in bytes.
+    pub fn align(&self) -> usize {
+        self.align
+    }
+
+    /// Whether the value of the field is hidden when it is formatted through
+    /// reflection.
+    pub fn is_redacted(&self) -> bool {
+        self.redacted
+    }
+
+    /// The number of bytes from the start of the struct at which the field
+    /// resides. This is `None` for the fields of enum variants, whose layout is
+    /// unspecified, and for the fields of dynamic structs.
+    pub fn offset(&self) -> Option<usize> {
+        self.offset.map(|offset| offset())
+    }
+}
+
+/// The visibility of a reflected field.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Visibility {
+    /// The field is declared with `pub`.
+    Public,
+    /// The field is declared with `pub(crate)` or `crate`.
+    Crate,
+    /// The field is declared with `pub(super)`, `pub(self)` or `pub(in path)`.
+    Restricted,
+    /// The field has no visibility modifier.
+    Inherited,
+}
@@ -0,0 +1,68 @@
+use crate::fields::BaseHasFields;
+#[cfg(feature = "alloc")]
+use alloc::vec::IntoIter as VecIntoIter;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::any::Any;
+#[cfg(feature = "alloc")]
+use core::marker::PhantomData;
+use core::slice::Iter as SliceIter;
+
+/// An iterator over the names and values of the reflected fields of a struct.
+///
+/// This is created by [`HasFields::fields`](crate::fields::HasFields::fields).
+pub struct Fields<'a, T: ?Sized> {
+    target: &'a T,
+    names: SliceIter<'static, &'static str>,
+}
+
+impl<'a, T: ?Sized + BaseHasFields> Fields<'a, T> {
+    pub(crate) fn new(target: &'a T) -> Self {
+        let names = target.field_names().iter();
+        Self { target, names }
+    }
+}
+
+impl<'a, T: ?Sized + BaseHasFields> Iterator for Fields<'a, T> {
+    type Item = (&'static str, &'a dyn Any);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let target = self.target;
+        self.names
+            .by_ref()
+            .find_map(|name| target.get_field_raw(name).ok().map(|value| (*name, value)))
+    }
+}
+
+/// An iterator over the names and mutable values of the reflected fields of a
+/// struct. The fields are borrowed all at once through
+/// [`BaseHasFields::visit_fields_mut`], so the references never alias.
+///
+/// This is created by
+/// [`HasFields::fields_mut`](crate::fields::HasFields::fields_mut).
+#[cfg(feature = "alloc")]
+pub struct FieldsMut<'a, T: ?Sized> {
+    fields: VecIntoIter<(&'static str, &'a mut dyn Any)>,
+    _marker: PhantomData<&'a mut T>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T: ?Sized + BaseHasFields> FieldsMut<'a, T> {
+    pub(crate) fn new(target: &'a mut T) -> Self {
+        let mut fields = Vec::with_capacity(target.field_names().len());
+        target.visit_fields_mut(&mut |name, value| fields.push((name, value)));
+        Self {
+            fields: fields.into_iter(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T: ?Sized> Iterator for FieldsMut<'a, T> {
+    type Item = (&'static str, &'a mut dyn Any);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.fields.next()
+    }
+}
@@ -0,0 +1,84 @@
+use crate::fields::FieldInfo;
+use core::marker::PhantomData;
+pub use preflect_macros::field;
+pub use preflect_macros::HasField;
+
+/// A field name which has been encoded into the type system. Names are built
+/// from [`NameChar`] and [`NameEnd`], and are typically spelled using the
+/// [`field!`] macro (e.g. `field!(name)`).
+pub trait FieldName {}
+
+/// A character of a field name which is followed by the rest of the name.
+pub struct NameChar<const C: char, Rest: FieldName>(PhantomData<Rest>);
+
+/// The end of a field name.
+pub struct NameEnd;
+
+impl<const C: char, Rest: FieldName> FieldName for NameChar<C, Rest> {}
+
+impl FieldName for NameEnd {}
+
+/// A type which has a reflected field that can be accessed using `Name`,
+/// either as the name of the field or one of its aliases. This is implemented
+/// by the `HasFields` derive so that `field!(value.name)` can check the name
+/// while the code is type checked.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` has no reflected field with this name",
+    label = "unknown field name",
+    note = "the accepted names are listed by `<{Self} as HasFieldInfo>::ACCEPTED_NAMES`"
+)]
+pub trait HasFieldName<Name: FieldName> {}
+
+/// Ensures that the type of `value` has a reflected field called `Name`. This
+/// is used by the `field!` macro.
+pub fn check_field_name<Name: FieldName, T: HasFieldName<Name> + ?Sized>(value: &T) {
+    let _ = value;
+}
+
+/// A type who has a field that can be accessed through reflection. This is the
+/// counterpart of `BaseHasField` which is available on stable Rust.
+///
+/// # Safety
+///
+/// `offset` must return the offset in bytes of a field of the implementing
+/// type whose type is `FieldType`, since [`HasNamedField`] reads the field
+/// through that offset. Implementations should be generated with the
+/// `HasField` derive.
+pub unsafe trait BaseHasNamedField<Name: FieldName> {
+    /// The type of the field's value.
+    type FieldType: 'static;
+
+    /// The descriptor of the field.
+    const INFO: FieldInfo;
+
+    /// Calculates the number of bytes from the pointer to the struct at which
+    /// the field resides.
+    fn offset() -> usize;
+}
+
+/// Represents a struct that has a field with a specific name and type.
+pub trait HasNamedField<Name: FieldName>: BaseHasNamedField<Name> {
+    /// Gets an immutable reference to the field.
+    fn get_field(&self) -> &Self::FieldType;
+
+    /// Gets a mutable reference to the field.
+    fn get_field_mut(&mut self) -> &mut Self::FieldType;
+}
+
+impl<T, Name> HasNamedField<Name> for T
+where
+    T: BaseHasNamedField<Name>,
+    Name: FieldName,
+{
+    fn get_field(&self) -> &Self::FieldType {
+        let base_address = self as *const Self as usize;
+        let ptr = (base_address + Self::offset()) as *const Self::FieldType;
+        unsafe { ptr.as_ref().unwrap() }
+    }
+
+    fn get_field_mut(&mut self) -> &mut Self::FieldType {
+        let base_address = self as *mut Self as usize;
+        let ptr = (base_address + Self::offset()) as *mut Self::FieldType;
+        unsafe { ptr.as_mut().unwrap() }
+    }
+}
@@ -0,0 +1,251 @@
+#![cfg(feature = "alloc")]
+
+use crate::fields::path::get_path_mut_raw;
+use crate::fields::path::PathGuards;
+use crate::fields::run_time::swap_value;
+use crate::fields::BaseHasFields;
+use crate::fields::FieldAccessError;
+use crate::fields::PathAccessError;
+use crate::fields::PathAccessResult;
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::any::Any;
+use core::fmt::Debug;
+use core::fmt::Display;
+use core::fmt::Formatter;
+use core::fmt::Result as FmtResult;
+use core::iter::FromIterator;
+
+/// A set of updates to the fields of a value, each of which sets the field at
+/// a path (e.g. `"server.port"`) to a type-erased value. A patch is applied
+/// with all-or-nothing semantics: every path and the type of every value is
+/// checked before anything is written.
+///
+/// Applying a patch produces its inverse, which undoes the changes when it is
+/// applied to the same value.
+///
+/// ```
+/// use preflect::fields::HasFields;
+/// use preflect::fields::Patch;
+///
+/// #[derive(HasFields)]
+/// struct Settings {
+///     theme: &'static str,
+///     volume: u8,
+/// }
+///
+/// let mut settings = Settings { theme: "light", volume: 3 };
+/// let undo = Patch::new()
+///     .with("theme", "dark")
+///     .with("volume", 7u8)
+///     .apply(&mut settings)
+///     .unwrap();
+/// assert_eq!(("dark", 7), (settings.theme, settings.volume));
+///
+/// undo.apply(&mut settings).unwrap();
+/// assert_eq!(("light", 3), (settings.theme, settings.volume));
+/// ```
+#[derive(Default)]
+pub struct Patch {
+    updates: Vec<(String, Box<dyn Any>)>,
+}
+
+impl Patch {
+    /// Creates a patch without any updates.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an update which sets the field at `path` to `value`.
+    pub fn with(mut self, path: impl Into<String>, value: impl Any) -> Self {
+        self.set(path, Box::new(value));
+        self
+    }
+
+    /// Adds an update which sets the field at `path` to a type-erased value.
+    /// An earlier update to the same path is replaced.
+    pub fn set(&mut self, path: impl Into<String>, value: Box<dyn Any>) {
+        let path = path.into();
+        match self.updates.iter_mut().find(|(update, _)| *update == path) {
+            Some((_, update)) => *update = value,
+            None => self.updates.push((path, value)),
+        }
+    }
+
+    /// Removes the update to the field at `path`, returning its value.
+    pub fn remove(&mut self, path: &str) -> Option<Box<dyn Any>> {
+        let index = self.updates.iter().position(|(update, _)| update == path)?;
+        Some(self.updates.remove(index).1)
+    }
+
+    /// Iterates over the paths and values of the updates in the order they
+    /// are applied.
+    pub fn updates(&self) -> impl Iterator<Item = (&str, &dyn Any)> {
+        self.updates
+            .iter()
+            .map(|(path, value)| (path.as_str(), &**value))
+    }
+
+    /// The number of updates in the patch.
+    pub fn len(&self) -> usize {
+        self.updates.len()
+    }
+
+    /// Determines if the patch has no updates.
+    pub fn is_empty(&self) -> bool {
+        self.updates.is_empty()
+    }
+
+    /// Ensures that every path of the patch exists on the target and that
+    /// every value is of the same type as the field it is assigned to. The
+    /// paths are resolved the same way as when the patch is applied, which
+    /// borrows the target mutably (e.g. a shared `Rc` is only passed through
+    /// to the cell or lock it shares).
+    pub fn check<T: ?Sized + BaseHasFields>(&self, target: &mut T) -> PathAccessResult<'_, ()> {
+        self.check_updates(target).map_err(|(_, error)| error)
+    }
+
+    /// Applies every update of the patch to the target, returning the
+    /// inverse patch which holds the previous values of the fields. Nothing
+    /// is written unless every update can be applied, in which case the patch
+    /// is returned in the error.
+    pub fn apply<T: ?Sized + BaseHasFields>(self, target: &mut T) -> Result<Patch, PatchError> {
+        if let Err((update, error)) = self.check_updates(target) {
+            let (segment, error) = error.detach();
+            return Err(PatchError::new(self, update, segment, error));
+        }
+
+        let mut patch = self;
+        for index in 0..patch.updates.len() {
+            let (path, value) = &mut patch.updates[index];
+            // The field is only used while its guards are kept.
+            let mut guards = unsafe { PathGuards::kept() };
+            let error = match get_path_mut_raw(target, path, &mut guards) {
+                Ok((field, segment)) => {
+                    if swap_value(field, &mut **value) {
+                        continue;
+                    }
+
+                    segment.into_mismatched_value().detach()
+                }
+                Err(error) => error.detach(),
+            };
+            drop(guards);
+
+            // An earlier update changed the shape of the target (e.g. the
+            // variant of a nested enum), so the applied updates are reverted.
+            patch.revert(target, index);
+            let (segment, error) = error;
+            return Err(PatchError::new(patch, index, segment, error));
+        }
+
+        patch.updates.reverse();
+        Ok(patch)
+    }
+
+    /// Swaps the values of the first `count` updates back into the patch.
+    fn revert<T: ?Sized + BaseHasFields>(&mut self, target: &mut T, count: usize) {
+        for (path, value) in self.updates[..count].iter_mut().rev() {
+            // The field is only used while its guards are kept.
+            let mut guards = unsafe { PathGuards::kept() };
+            if let Ok((field, _)) = get_path_mut_raw(target, path, &mut guards) {
+                swap_value(field, &mut **value);
+            }
+        }
+    }
+
+    /// Checks the updates in order with the same traversal which applies
+    /// them, returning the position of the first update which can not be
+    /// applied along with the reason.
+    fn check_updates<T: ?Sized + BaseHasFields>(
+        &self,
+        target: &mut T,
+    ) -> Result<(), (usize, PathAccessError<'_>)> {
+        for (index, (path, value)) in self.updates.iter().enumerate() {
+            // The field is only used while its guards are kept.
+            let mut guards = unsafe { PathGuards::kept() };
+            let (field, segment) =
+                get_path_mut_raw(target, path, &mut guards).map_err(|error| (index, error))?;
+            if <dyn Any>::type_id(field) != <dyn Any>::type_id(&**value) {
+                return Err((index, segment.into_mismatched_value()));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Debug for Patch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_list()
+            .entries(self.updates.iter().map(|(path, _)| path))
+            .finish()
+    }
+}
+
+impl<P: Into<String>> FromIterator<(P, Box<dyn Any>)> for Patch {
+    fn from_iter<I: IntoIterator<Item = (P, Box<dyn Any>)>>(iter: I) -> Self {
+        let mut patch = Patch::new();
+        patch.extend(iter);
+        patch
+    }
+}
+
+impl<P: Into<String>> Extend<(P, Box<dyn Any>)> for Patch {
+    fn extend<I: IntoIterator<Item = (P, Box<dyn Any>)>>(&mut self, iter: I) {
+        for (path, value) in iter {
+            self.set(path, value);
+        }
+    }
+}
+
+/// An error that occurred while applying a [`Patch`]. None of the updates
+/// were applied, and the patch is returned to the caller.
+pub struct PatchError {
+    patch: Patch,
+    update: usize,
+    segment: usize,
+    error: FieldAccessError<'static>,
+}
+
+impl PatchError {
+    fn new(patch: Patch, update: usize, segment: usize, error: FieldAccessError<'static>) -> Self {
+        Self {
+            patch,
+            update,
+            segment,
+            error,
+        }
+    }
+
+    /// The path of the update which could not be applied.
+    pub fn path(&self) -> &str {
+        &self.patch.updates[self.update].0
+    }
+
+    /// The reason the update could not be applied.
+    pub fn error(&self) -> PathAccessError<'_> {
+        PathAccessError::attach(self.path(), self.segment, self.error)
+    }
+
+    /// Takes back the patch which could not be applied.
+    pub fn into_patch(self) -> Patch {
+        self.patch
+    }
+}
+
+impl Debug for PatchError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("PatchError")
+            .field("patch", &self.patch)
+            .field("error", &self.error())
+            .finish()
+    }
+}
+
+impl Display for PatchError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        Display::fmt(&self.error(), f)
+    }
+}
@@ -0,0 +1,644 @@
+#[cfg(feature = "alloc")]
+use crate::collections::CollectionMut;
+#[cfg(feature = "alloc")]
+use crate::collections::CollectionRef;
+use crate::fields::errors::field_type_name;
+use crate::fields::BaseHasFields;
+use crate::fields::FieldAccessError;
+#[cfg(feature = "alloc")]
+use crate::fields::FieldAccessErrorKind;
+#[cfg(feature = "alloc")]
+use crate::fields::Reflect;
+#[cfg(feature = "alloc")]
+use crate::pointers::PointerError;
+#[cfg(feature = "alloc")]
+use crate::pointers::PointerGuard;
+#[cfg(feature = "alloc")]
+use crate::pointers::PointerGuardMut;
+#[cfg(feature = "alloc")]
+use crate::pointers::PointerMut;
+#[cfg(feature = "alloc")]
+use crate::pointers::PointerRef;
+#[cfg(feature = "alloc")]
+use crate::pointers::PointerResult;
+#[cfg(feature = "alloc")]
+use crate::pointers::TargetCast;
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::any::Any;
+#[cfg(feature = "alloc")]
+use core::fmt::Debug;
+use core::fmt::Display;
+use core::fmt::Formatter;
+use core::fmt::Result as FmtResult;
+#[cfg(feature = "alloc")]
+use core::ops::Deref;
+#[cfg(feature = "alloc")]
+use core::ops::DerefMut;
+#[cfg(feature = "alloc")]
+use core::ptr::NonNull;
+
+/// The result of accessing a field dynamically by its path.
+pub type PathAccessResult<'p, T> = Result<T, PathAccessError<'p>>;
+
+/// An error that occurred while accessing a field by its path.
+#[derive(Debug, Eq, PartialEq)]
+pub struct PathAccessError<'p> {
+    path: &'p str,
+    segment: &'p str,
+    index: usize,
+    error: FieldAccessError<'p>,
+}
+
+impl<'p> PathAccessError<'p> {
+    /// The full path which was being accessed.
+    pub fn path(&self) -> &'p str {
+        self.path
+    }
+
+    /// The segment of the path which could not be accessed.
+    pub fn segment(&self) -> &'p str {
+        self.segment
+    }
+
+    /// The position of the segment which could not be accessed within the
+    /// path, starting from zero.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// The reason the segment could not be accessed.
+    pub fn error(&self) -> &FieldAccessError<'p> {
+        &self.error
+    }
+
+    /// Detaches the error from the path so that it can outlive it, returning
+    /// the position of the segment along with the error.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn detach(self) -> (usize, FieldAccessError<'static>) {
+        (self.index, self.error.with_name(None))
+    }
+
+    /// Attaches an error which was detached by [`detach`](Self::detach) to
+    /// the path it occurred for.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn attach(path: &'p str, index: usize, error: FieldAccessError<'static>) -> Self {
+        let segment = path.split('.').nth(index).unwrap_or(path);
+        Self {
+            path,
+            segment,
+            index,
+            error: error.with_name(Some(segment)),
+        }
+    }
+}
+
+impl Display for PathAccessError<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(
+            f,
+            "Failed to access '{}' of the path '{}': {}",
+            self.segment, self.path, self.error
+        )
+    }
+}
+
+/// Identifies the segment of a path which is being accessed.
+pub(crate) struct Segment<'p> {
+    path: &'p str,
+    segment: &'p str,
+    index: usize,
+    /// The name of the type of the field at this segment, if it is known.
+    type_name: Option<&'static str>,
+}
+
+impl<'p> Segment<'p> {
+    pub(crate) fn into_error(self, error: FieldAccessError<'p>) -> PathAccessError<'p> {
+        PathAccessError {
+            path: self.path,
+            segment: self.segment,
+            index: self.index,
+            error,
+        }
+    }
+
+    /// Creates the error for a field at this segment which is not of the type
+    /// `T`.
+    pub(crate) fn into_invalid_type<T>(self) -> PathAccessError<'p> {
+        let error = FieldAccessError::invalid_type::<T>(self.segment, self.type_name);
+        self.into_error(error)
+    }
+
+    /// Creates the error for a field at this segment which is not of the same
+    /// type as a type-erased value.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn into_mismatched_value(self) -> PathAccessError<'p> {
+        let error = FieldAccessError::mismatched_value(self.segment, self.type_name);
+        self.into_error(error)
+    }
+}
+
+/// Splits the first segment from `path`, returning it along with the
+/// remaining segments (if any).
+fn split_path<'p>(
+    full_path: &'p str,
+    path: &'p str,
+    index: usize,
+) -> (Segment<'p>, Option<&'p str>) {
+    let (segment, rest) = match path.find('.') {
+        Some(dot) => (&path[..dot], Some(&path[dot + 1..])),
+        None => (path, None),
+    };
+
+    let segment = Segment {
+        path: full_path,
+        segment,
+        index,
+        type_name: None,
+    };
+
+    (segment, rest)
+}
+
+/// A field reached by a path which passes through cells or locks, which stay
+/// borrowed (or locked) until the guard is dropped.
+#[cfg(feature = "alloc")]
+pub struct PathGuard<'s, T: ?Sized> {
+    field: NonNull<T>,
+    /// Keeps the field borrowed until they are dropped along with the guard.
+    _guards: PathGuards<'s>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'s, T: ?Sized> PathGuard<'s, T> {
+    pub(crate) fn new(field: &'s T, guards: PathGuards<'s>) -> Self {
+        Self {
+            field: NonNull::from(field),
+            _guards: guards,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: ?Sized> Deref for PathGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // The guards which keep the field borrowed live as long as `self`.
+        unsafe { self.field.as_ref() }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: ?Sized + Debug> Debug for PathGuard<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        Debug::fmt(&**self, f)
+    }
+}
+
+/// A mutable field reached by a path which passes through cells or locks,
+/// which stay borrowed (or locked) until the guard is dropped. The value of a
+/// `Cell` is written back when the guard is dropped.
+#[cfg(feature = "alloc")]
+pub struct PathGuardMut<'s, T: ?Sized> {
+    field: NonNull<T>,
+    /// Keeps the field borrowed until they are dropped along with the guard.
+    _guards: PathGuards<'s>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'s, T: ?Sized> PathGuardMut<'s, T> {
+    pub(crate) fn new(field: &'s mut T, guards: PathGuards<'s>) -> Self {
+        Self {
+            field: NonNull::from(field),
+            _guards: guards,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: ?Sized> Deref for PathGuardMut<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // The guards which keep the field borrowed live as long as `self`.
+        unsafe { self.field.as_ref() }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: ?Sized> DerefMut for PathGuardMut<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        // The guards which keep the field borrowed live as long as `self`.
+        unsafe { self.field.as_mut() }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: ?Sized + Debug> Debug for PathGuardMut<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        Debug::fmt(&**self, f)
+    }
+}
+
+/// The guards of the cells and locks which a path passes through. Paths only
+/// pass through cells and locks (and through a shared `Rc` or `Arc` when
+/// accessed mutably) when the guards are kept.
+#[cfg(feature = "alloc")]
+pub(crate) struct PathGuards<'s> {
+    kept: bool,
+    guards: Vec<HeldGuard<'s>>,
+}
+
+/// A guard which was moved to the heap so that the references to its target
+/// stay valid while the guards are moved around.
+#[cfg(feature = "alloc")]
+enum HeldGuard<'s> {
+    Read(NonNull<PointerGuard<'s>>),
+    Write(NonNull<PointerGuardMut<'s>>),
+}
+
+#[cfg(feature = "alloc")]
+impl<'s> PathGuards<'s> {
+    /// Paths stop at cells and locks, since their guards are not kept.
+    pub(crate) fn none() -> Self {
+        Self {
+            kept: false,
+            guards: Vec::new(),
+        }
+    }
+
+    /// Paths pass through cells and locks, whose guards are kept.
+    ///
+    /// # Safety
+    ///
+    /// The fields which are reached by a path with these guards must not be
+    /// used after the guards are dropped.
+    pub(crate) unsafe fn kept() -> Self {
+        Self {
+            kept: true,
+            guards: Vec::new(),
+        }
+    }
+
+    fn is_kept(&self) -> bool {
+        self.kept
+    }
+
+    /// Keeps a guard for reading, returning its target for as long as the
+    /// guards are kept.
+    fn keep(&mut self, guard: PointerGuard<'s>) -> (&'s dyn Any, TargetCast) {
+        let guard = NonNull::from(Box::leak(Box::new(guard)));
+        self.guards.push(HeldGuard::Read(guard));
+
+        // The guard is only freed when the guards are dropped, which the
+        // creator of kept guards ensures happens after the target is used.
+        let guard = unsafe { &*guard.as_ptr() };
+        (guard.value(), guard.cast())
+    }
+
+    /// Keeps a guard for writing, returning its target for as long as the
+    /// guards are kept.
+    fn keep_mut(&mut self, guard: PointerGuardMut<'s>) -> (&'s mut dyn Any, TargetCast) {
+        let guard = NonNull::from(Box::leak(Box::new(guard)));
+        self.guards.push(HeldGuard::Write(guard));
+
+        // The guard is only freed when the guards are dropped, which the
+        // creator of kept guards ensures happens after the target is used.
+        let guard = unsafe { &mut *guard.as_ptr() };
+        let cast = guard.cast();
+        (guard.value_mut(), cast)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Drop for PathGuards<'_> {
+    fn drop(&mut self) {
+        // A guard may borrow the target of the guard before it (e.g. the
+        // `RefCell` inside a `Mutex`), so the guards are released in reverse.
+        while let Some(guard) = self.guards.pop() {
+            match guard {
+                HeldGuard::Read(guard) => drop(unsafe { Box::from_raw(guard.as_ptr()) }),
+                HeldGuard::Write(guard) => drop(unsafe { Box::from_raw(guard.as_ptr()) }),
+            }
+        }
+    }
+}
+
+/// Resolves `path` against `target`, returning the field at the end of the
+/// path and the final segment.
+pub(crate) fn get_path_raw<'s, 'p, T: ?Sized + BaseHasFields>(
+    target: &'s T,
+    path: &'p str,
+    #[cfg(feature = "alloc")] guards: &mut PathGuards<'s>,
+) -> Result<(&'s dyn Any, Segment<'p>), PathAccessError<'p>> {
+    get_path_raw_from(
+        target,
+        path,
+        path,
+        0,
+        #[cfg(feature = "alloc")]
+        guards,
+    )
+}
+
+fn get_path_raw_from<'s, 'p, T: ?Sized + BaseHasFields>(
+    target: &'s T,
+    full_path: &'p str,
+    path: &'p str,
+    index: usize,
+    #[cfg(feature = "alloc")] guards: &mut PathGuards<'s>,
+) -> Result<(&'s dyn Any, Segment<'p>), PathAccessError<'p>> {
+    let (mut segment, rest) = split_path(full_path, path, index);
+    segment.type_name = field_type_name(target, segment.segment);
+    match rest {
+        None => match target.get_field_raw(segment.segment) {
+            Ok(field) => Ok((field, segment)),
+            Err(err) => Err(segment.into_error(err)),
+        },
+        Some(rest) => match target.get_nested_raw(segment.segment) {
+            Ok(nested) => get_path_raw_from(
+                nested,
+                full_path,
+                rest,
+                index + 1,
+                #[cfg(feature = "alloc")]
+                guards,
+            ),
+            #[cfg(feature = "alloc")]
+            Err(err) if err.kind() == FieldAccessErrorKind::NotNested => {
+                if let Ok(collection) = target.get_collection_raw(segment.segment) {
+                    return get_element_raw(collection, full_path, rest, index + 1, guards);
+                }
+
+                match target.get_pointer_raw(segment.segment) {
+                    Ok(pointer) => {
+                        get_target_raw(pointer, segment, full_path, rest, index + 1, guards)
+                    }
+                    Err(_) => Err(segment.into_error(err)),
+                }
+            }
+            Err(err) => Err(segment.into_error(err)),
+        },
+    }
+}
+
+/// Resolves `path` against the elements of a collection, where the first
+/// segment selects an element.
+#[cfg(feature = "alloc")]
+fn get_element_raw<'s, 'p>(
+    collection: CollectionRef<'s>,
+    full_path: &'p str,
+    path: &'p str,
+    index: usize,
+    guards: &mut PathGuards<'s>,
+) -> Result<(&'s dyn Any, Segment<'p>), PathAccessError<'p>> {
+    let (segment, rest) = split_path(full_path, path, index);
+    let element = match collection.get(segment.segment) {
+        Some(element) => element,
+        None => {
+            let error = FieldAccessError::missing_field(segment.segment, &[]);
+            return Err(segment.into_error(error));
+        }
+    };
+
+    match rest {
+        None => Ok((element, segment)),
+        Some(rest) => match collection.elements().as_reflect(element) {
+            Some(nested) => get_path_raw_from(nested, full_path, rest, index + 1, guards),
+            None => {
+                let error = FieldAccessError::not_nested(segment.segment);
+                Err(segment.into_error(error))
+            }
+        },
+    }
+}
+
+/// Resolves `path` against the target of a pointer, which is reached through
+/// `segment` without consuming a segment of its own.
+#[cfg(feature = "alloc")]
+fn get_target_raw<'s, 'p>(
+    pointer: PointerRef<'s>,
+    segment: Segment<'p>,
+    full_path: &'p str,
+    path: &'p str,
+    index: usize,
+    guards: &mut PathGuards<'s>,
+) -> Result<(&'s dyn Any, Segment<'p>), PathAccessError<'p>> {
+    match get_nested(pointer, guards) {
+        Ok(Some(nested)) => get_path_raw_from(nested, full_path, path, index, guards),
+        Ok(None) => {
+            let error = FieldAccessError::not_nested(segment.segment);
+            Err(segment.into_error(error))
+        }
+        Err(error) => {
+            let error = FieldAccessError::pointer(segment.segment, error);
+            Err(segment.into_error(error))
+        }
+    }
+}
+
+/// Gets the target of a pointer as a reflected value, passing through a
+/// target which is itself a pointer. Cells and locks are borrowed for reading
+/// when the guards are kept.
+#[cfg(feature = "alloc")]
+fn get_nested<'s>(
+    pointer: PointerRef<'s>,
+    guards: &mut PathGuards<'s>,
+) -> PointerResult<Option<&'s dyn Reflect>> {
+    let (target, cast) = match pointer.get() {
+        Some(target) => (target, pointer.cast()),
+        None if guards.is_kept() => guards.keep(pointer.lock()?),
+        None => return Err(PointerError::Guarded),
+    };
+
+    match cast.as_pointer(target) {
+        Some(pointer) => get_nested(pointer, guards),
+        None => Ok(cast.as_reflect(target)),
+    }
+}
+
+/// Resolves `path` against `target`, returning the field at the end of the
+/// path and the final segment.
+pub(crate) fn get_path_mut_raw<'s, 'p, T: ?Sized + BaseHasFields>(
+    target: &'s mut T,
+    path: &'p str,
+    #[cfg(feature = "alloc")] guards: &mut PathGuards<'s>,
+) -> Result<(&'s mut dyn Any, Segment<'p>), PathAccessError<'p>> {
+    get_path_mut_raw_from(
+        target,
+        path,
+        path,
+        0,
+        #[cfg(feature = "alloc")]
+        guards,
+    )
+}
+
+fn get_path_mut_raw_from<'s, 'p, T: ?Sized + BaseHasFields>(
+    target: &'s mut T,
+    full_path: &'p str,
+    path: &'p str,
+    index: usize,
+    #[cfg(feature = "alloc")] guards: &mut PathGuards<'s>,
+) -> Result<(&'s mut dyn Any, Segment<'p>), PathAccessError<'p>> {
+    let (mut segment, rest) = split_path(full_path, path, index);
+    segment.type_name = field_type_name(&*target, segment.segment);
+    match rest {
+        None => match target.get_field_mut_raw(segment.segment) {
+            Ok(field) => Ok((field, segment)),
+            Err(err) => Err(segment.into_error(err)),
+        },
+        Some(rest) => {
+            #[cfg(feature = "alloc")]
+            match find_wrapper(target, segment.segment) {
+                Some(Wrapper::Collection) => {
+                    return match target.get_collection_mut_raw(segment.segment) {
+                        Ok(collection) => {
+                            get_element_mut_raw(collection, full_path, rest, index + 1, guards)
+                        }
+                        Err(err) => Err(segment.into_error(err)),
+                    };
+                }
+                Some(Wrapper::Pointer) => {
+                    return match target.get_pointer_mut_raw(segment.segment) {
+                        Ok(pointer) => {
+                            get_target_mut_raw(pointer, segment, full_path, rest, index + 1, guards)
+                        }
+                        Err(err) => Err(segment.into_error(err)),
+                    };
+                }
+                None => {}
+            }
+
+            match target.get_nested_mut_raw(segment.segment) {
+                Ok(nested) => get_path_mut_raw_from(
+                    nested,
+                    full_path,
+                    rest,
+                    index + 1,
+                    #[cfg(feature = "alloc")]
+                    guards,
+                ),
+                Err(err) => Err(segment.into_error(err)),
+            }
+        }
+    }
+}
+
+/// The kinds of fields which are traversed through a wrapper type rather than
+/// as nested fields.
+#[cfg(feature = "alloc")]
+enum Wrapper {
+    Collection,
+    Pointer,
+}
+
+/// Determines if a field is traversed through a wrapper type rather than as a
+/// nested field.
+#[cfg(feature = "alloc")]
+fn find_wrapper<T: ?Sized + BaseHasFields>(target: &T, name: &str) -> Option<Wrapper> {
+    match target.get_nested_raw(name) {
+        Err(err) if err.kind() == FieldAccessErrorKind::NotNested => {
+            if target.get_collection_raw(name).is_ok() {
+                Some(Wrapper::Collection)
+            } else if target.get_pointer_raw(name).is_ok() {
+                Some(Wrapper::Pointer)
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Resolves `path` against the elements of a collection, where the first
+/// segment selects an element.
+#[cfg(feature = "alloc")]
+fn get_element_mut_raw<'s, 'p>(
+    collection: CollectionMut<'s>,
+    full_path: &'p str,
+    path: &'p str,
+    index: usize,
+    guards: &mut PathGuards<'s>,
+) -> Result<(&'s mut dyn Any, Segment<'p>), PathAccessError<'p>> {
+    let (segment, rest) = split_path(full_path, path, index);
+    let elements = collection.elements();
+    let element = match collection.into_element(segment.segment) {
+        Some(element) => element,
+        None => {
+            let error = FieldAccessError::missing_field(segment.segment, &[]);
+            return Err(segment.into_error(error));
+        }
+    };
+
+    match rest {
+        None => Ok((element, segment)),
+        Some(rest) => match elements.as_reflect_mut(element) {
+            Some(nested) => get_path_mut_raw_from(nested, full_path, rest, index + 1, guards),
+            None => {
+                let error = FieldAccessError::not_nested(segment.segment);
+                Err(segment.into_error(error))
+            }
+        },
+    }
+}
+
+/// Resolves `path` against the target of a pointer, which is reached through
+/// `segment` without consuming a segment of its own.
+#[cfg(feature = "alloc")]
+fn get_target_mut_raw<'s, 'p>(
+    pointer: PointerMut<'s>,
+    segment: Segment<'p>,
+    full_path: &'p str,
+    path: &'p str,
+    index: usize,
+    guards: &mut PathGuards<'s>,
+) -> Result<(&'s mut dyn Any, Segment<'p>), PathAccessError<'p>> {
+    match into_nested(pointer, guards) {
+        Ok(Some(nested)) => get_path_mut_raw_from(nested, full_path, path, index, guards),
+        Ok(None) => {
+            let error = FieldAccessError::not_nested(segment.segment);
+            Err(segment.into_error(error))
+        }
+        Err(error) => {
+            let error = FieldAccessError::pointer(segment.segment, error);
+            Err(segment.into_error(error))
+        }
+    }
+}
+
+/// Takes the target of a pointer as a mutable reflected value, passing
+/// through a target which is itself a pointer. When the guards are kept, a
+/// shared `Rc` or `Arc` is passed through to the cell or lock it shares,
+/// which is borrowed for writing.
+#[cfg(feature = "alloc")]
+fn into_nested<'s>(
+    pointer: PointerMut<'s>,
+    guards: &mut PathGuards<'s>,
+) -> PointerResult<Option<&'s mut dyn Reflect>> {
+    let cast = pointer.cast();
+    let (target, cast) = match pointer.into_target_or_shared()? {
+        Ok(target) => (target, cast),
+        Err(shared) if guards.is_kept() => {
+            let cell = cast.as_pointer(shared).ok_or(PointerError::Shared)?;
+            match cell.lock_mut() {
+                Ok(guard) => guards.keep_mut(guard),
+                Err(PointerError::NotGuarded) => return Err(PointerError::Shared),
+                Err(error) => return Err(error),
+            }
+        }
+        Err(_) => return Err(PointerError::Shared),
+    };
+
+    if cast.is_pointer() {
+        match cast.as_pointer_mut(target) {
+            Some(pointer) => into_nested(pointer, guards),
+            None => Ok(None),
+        }
+    } else {
+        Ok(cast.as_reflect_mut(target))
+    }
+}
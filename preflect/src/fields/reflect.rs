@@ -0,0 +1,87 @@
+use crate::fields::BaseHasFields;
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+use core::any::type_name;
+use core::any::Any;
+
+/// An object-safe trait which allows values of different types to be
+/// inspected uniformly through reflection (e.g. as `Vec<Box<dyn Reflect>>`).
+///
+/// This is implemented for every type which implements [`BaseHasFields`].
+/// The typed accessors of [`HasFields`](crate::fields::HasFields) are also
+/// available on `dyn Reflect`.
+pub trait Reflect: BaseHasFields + Any {
+    /// Gets the name of the concrete type of the value.
+    fn type_name(&self) -> &'static str;
+
+    /// Views the value as a `dyn Any`.
+    fn as_any(&self) -> &dyn Any;
+
+    /// Views the value as a mutable `dyn Any`.
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+
+    /// Converts the boxed value into a `Box<dyn Any>`.
+    #[cfg(feature = "alloc")]
+    fn into_any(self: Box<Self>) -> Box<dyn Any>;
+
+    /// Views the value as a `dyn Reflect`.
+    fn as_reflect(&self) -> &dyn Reflect;
+
+    /// Views the value as a mutable `dyn Reflect`.
+    fn as_reflect_mut(&mut self) -> &mut dyn Reflect;
+}
+
+impl<T: BaseHasFields + Any> Reflect for T {
+    fn type_name(&self) -> &'static str {
+        type_name::<T>()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    #[cfg(feature = "alloc")]
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+
+    fn as_reflect(&self) -> &dyn Reflect {
+        self
+    }
+
+    fn as_reflect_mut(&mut self) -> &mut dyn Reflect {
+        self
+    }
+}
+
+impl dyn Reflect {
+    /// Checks if the concrete type of the value is `T`.
+    pub fn is<T: Reflect>(&self) -> bool {
+        self.as_any().is::<T>()
+    }
+
+    /// Gets an immutable reference to the value if its concrete type is `T`.
+    pub fn downcast_ref<T: Reflect>(&self) -> Option<&T> {
+        self.as_any().downcast_ref::<T>()
+    }
+
+    /// Gets a mutable reference to the value if its concrete type is `T`.
+    pub fn downcast_mut<T: Reflect>(&mut self) -> Option<&mut T> {
+        self.as_any_mut().downcast_mut::<T>()
+    }
+
+    /// Converts the boxed value into a `Box<T>` if its concrete type is `T`.
+    /// Otherwise the original box is returned.
+    #[cfg(feature = "alloc")]
+    pub fn downcast<T: Reflect>(self: Box<Self>) -> Result<Box<T>, Box<dyn Reflect>> {
+        if self.is::<T>() {
+            Ok(self.into_any().downcast::<T>().unwrap())
+        } else {
+            Err(self)
+        }
+    }
+}
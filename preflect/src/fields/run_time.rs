@@ -1,64 +1,413 @@
+#[cfg(feature = "alloc")]
+use crate::collections::CollectionMut;
+#[cfg(feature = "alloc")]
+use crate::collections::CollectionRef;
+use crate::fields::errors::field_type_name;
+use crate::fields::path::get_path_mut_raw;
+use crate::fields::path::get_path_raw;
+#[cfg(feature = "alloc")]
+use crate::fields::path::PathGuards;
+use crate::fields::FieldAccessError;
+use crate::fields::FieldAccessResult;
+use crate::fields::FieldInfo;
+use crate::fields::Fields;
+#[cfg(feature = "alloc")]
+use crate::fields::FieldsMut;
+use crate::fields::PathAccessResult;
+#[cfg(feature = "alloc")]
+use crate::fields::PathGuard;
+#[cfg(feature = "alloc")]
+use crate::fields::PathGuardMut;
+use crate::fields::Reflect;
+#[cfg(feature = "alloc")]
+use crate::pointers::PointerMut;
+#[cfg(feature = "alloc")]
+use crate::pointers::PointerRef;
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
 use core::any::Any;
+#[cfg(feature = "alloc")]
 use core::fmt::Display;
+#[cfg(feature = "alloc")]
 use core::fmt::Formatter;
+#[cfg(feature = "alloc")]
 use core::fmt::Result as FmtResult;
+#[cfg(feature = "alloc")]
+use core::mem::size_of_val;
+use core::mem::take;
+#[cfg(feature = "alloc")]
+use core::ptr::swap_nonoverlapping;
 pub use preflect_macros::HasFields;
 
-/// The result of accessing a field dynamically
-pub type FieldAccessResult<T> = Result<T, FieldAccessError>;
-
 /// A trait that can be used to dynamically access the fields of a struct at
 /// runtime.
 pub trait BaseHasFields {
+    /// Gets the names of the reflected fields in the order they are declared.
+    fn field_names(&self) -> &'static [&'static str];
+
+    /// Gets the descriptors of the reflected fields in the order they are
+    /// declared.
+    fn field_info(&self) -> &'static [FieldInfo];
+
+    /// Gets the name of the enum variant the value currently holds, or `None`
+    /// if the value is not an enum.
+    fn active_variant(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// Gets the descriptor of a field using the name of the field.
+    fn get_field_info<'n>(&self, name: &'n str) -> FieldAccessResult<'n, &'static FieldInfo> {
+        let field_names = self.field_names();
+        match field_names
+            .iter()
+            .position(|field_name| *field_name == name)
+        {
+            Some(index) => Ok(&self.field_info()[index]),
+            None => Err(FieldAccessError::missing_field(name, field_names)),
+        }
+    }
+
     /// Gets an immutable reference to a field using the name of the field.
-    fn get_field_raw<'s>(&'s self, name: &str) -> FieldAccessResult<&'s dyn Any>;
+    fn get_field_raw<'s, 'n>(&'s self, name: &'n str) -> FieldAccessResult<'n, &'s dyn Any>;
 
     /// Gets a mutable reference to a field using the name of the field.
-    fn get_field_mut_raw<'s>(&'s mut self, name: &str) -> FieldAccessResult<&'s mut dyn Any>;
+    fn get_field_mut_raw<'s, 'n>(
+        &'s mut self,
+        name: &'n str,
+    ) -> FieldAccessResult<'n, &'s mut dyn Any>;
+
+    /// Borrows every reflected field mutably at once and passes each one to
+    /// `visit` along with its name, in the order they are declared. Each
+    /// field must be visited at most once.
+    fn visit_fields_mut<'s>(&'s mut self, visit: &mut dyn FnMut(&'static str, &'s mut dyn Any));
+
+    /// Gets an immutable reference to a field which can itself be reflected
+    /// (e.g. a field marked with `#[preflect(nested)]`) using the name of the
+    /// field.
+    fn get_nested_raw<'s, 'n>(&'s self, name: &'n str) -> FieldAccessResult<'n, &'s dyn Reflect> {
+        match self.get_field_raw(name) {
+            Ok(_) => Err(FieldAccessError::not_nested(name)),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Gets a mutable reference to a field which can itself be reflected
+    /// (e.g. a field marked with `#[preflect(nested)]`) using the name of the
+    /// field.
+    fn get_nested_mut_raw<'s, 'n>(
+        &'s mut self,
+        name: &'n str,
+    ) -> FieldAccessResult<'n, &'s mut dyn Reflect> {
+        match self.get_field_raw(name) {
+            Ok(_) => Err(FieldAccessError::not_nested(name)),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Gets an immutable reference to a field which is a collection (e.g. a
+    /// field marked with `#[preflect(collection)]`) using the name of the
+    /// field.
+    #[cfg(feature = "alloc")]
+    fn get_collection_raw<'s, 'n>(
+        &'s self,
+        name: &'n str,
+    ) -> FieldAccessResult<'n, CollectionRef<'s>> {
+        match self.get_field_raw(name) {
+            Ok(_) => Err(FieldAccessError::not_collection(name)),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Gets a mutable reference to a field which is a collection (e.g. a
+    /// field marked with `#[preflect(collection)]`) using the name of the
+    /// field.
+    #[cfg(feature = "alloc")]
+    fn get_collection_mut_raw<'s, 'n>(
+        &'s mut self,
+        name: &'n str,
+    ) -> FieldAccessResult<'n, CollectionMut<'s>> {
+        match self.get_field_raw(name) {
+            Ok(_) => Err(FieldAccessError::not_collection(name)),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Gets an immutable reference to a field which is a pointer, cell or
+    /// lock (e.g. a field marked with `#[preflect(pointer)]`) using the name
+    /// of the field.
+    #[cfg(feature = "alloc")]
+    fn get_pointer_raw<'s, 'n>(&'s self, name: &'n str) -> FieldAccessResult<'n, PointerRef<'s>> {
+        match self.get_field_raw(name) {
+            Ok(_) => Err(FieldAccessError::not_pointer(name)),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Gets a mutable reference to a field which is a pointer, cell or lock
+    /// (e.g. a field marked with `#[preflect(pointer)]`) using the name of
+    /// the field.
+    #[cfg(feature = "alloc")]
+    fn get_pointer_mut_raw<'s, 'n>(
+        &'s mut self,
+        name: &'n str,
+    ) -> FieldAccessResult<'n, PointerMut<'s>> {
+        match self.get_field_raw(name) {
+            Ok(_) => Err(FieldAccessError::not_pointer(name)),
+            Err(err) => Err(err),
+        }
+    }
 }
 
-/// An error that occurred while accessing a field through the preflect API.
-#[derive(Debug, Eq, PartialEq)]
-pub enum FieldAccessError {
-    /// A specified field was not found for the type.
-    MissingField,
-    /// The type of the field was not of the expected type.
-    InvalidType,
+/// An error that occurred while setting a field to a type-erased value. The
+/// value which could not be set is returned to the caller.
+#[cfg(feature = "alloc")]
+#[derive(Debug)]
+pub struct SetFieldError<'n> {
+    error: FieldAccessError<'n>,
+    value: Box<dyn Any>,
 }
 
-impl Display for FieldAccessError {
+#[cfg(feature = "alloc")]
+impl<'n> SetFieldError<'n> {
+    fn new(error: FieldAccessError<'n>, value: Box<dyn Any>) -> Self {
+        Self { error, value }
+    }
+
+    /// Gets the reason the value could not be set.
+    pub fn error(&self) -> &FieldAccessError<'n> {
+        &self.error
+    }
+
+    /// Takes back the value which could not be set.
+    pub fn into_value(self) -> Box<dyn Any> {
+        self.value
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Display for SetFieldError<'_> {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
-        match self {
-            FieldAccessError::MissingField => {
-                write!(f, "The specified field did not exist for the object.")
-            }
-            FieldAccessError::InvalidType => {
-                write!(f, "The specified field is of a different type.")
-            }
-        }
+        Display::fmt(&self.error, f)
     }
 }
 
 /// A trait that provides useful extension methods that make dynamically
-/// accessing struct fields at runtime easier.
+/// accessing struct fields at runtime easier. These are also available on
+/// `dyn BaseHasFields` and `dyn Reflect`.
 pub trait HasFields: BaseHasFields {
     /// Gets an immutable reference to a field using the name of the field.
-    fn get_field<'s, T: 'static>(&'s self, name: &str) -> FieldAccessResult<&'s T>;
+    fn get_field<'s, 'n, T: 'static>(&'s self, name: &'n str) -> FieldAccessResult<'n, &'s T>;
 
     /// Gets a mutable reference to a field using the name of the field.
-    fn get_field_mut<'s, T: 'static>(&'s mut self, name: &str) -> FieldAccessResult<&'s mut T>;
+    fn get_field_mut<'s, 'n, T: 'static>(
+        &'s mut self,
+        name: &'n str,
+    ) -> FieldAccessResult<'n, &'s mut T>;
+
+    /// Sets the value of a field using the name of the field. If the field
+    /// does not exist or the value is of a different type, the value is
+    /// returned in the error.
+    #[cfg(feature = "alloc")]
+    fn set_field<'n>(
+        &mut self,
+        name: &'n str,
+        value: Box<dyn Any>,
+    ) -> Result<(), SetFieldError<'n>>;
+
+    /// Replaces the value of a field using the name of the field and returns
+    /// the previous value. If the field does not exist or the value is of a
+    /// different type, the value is returned in the error.
+    #[cfg(feature = "alloc")]
+    fn replace_field<'n>(
+        &mut self,
+        name: &'n str,
+        value: Box<dyn Any>,
+    ) -> Result<Box<dyn Any>, SetFieldError<'n>>;
+
+    /// Gets an immutable reference to a field using a path of field names
+    /// separated by `.` (e.g. `"address.city"`). Every field along the path
+    /// except the last must be a nested reflected field.
+    fn get_path<'s, 'p, T: 'static>(&'s self, path: &'p str) -> PathAccessResult<'p, &'s T>;
+
+    /// Gets a mutable reference to a field using a path of field names
+    /// separated by `.` (e.g. `"address.city"`). Every field along the path
+    /// except the last must be a nested reflected field.
+    fn get_path_mut<'s, 'p, T: 'static>(
+        &'s mut self,
+        path: &'p str,
+    ) -> PathAccessResult<'p, &'s mut T>;
+
+    /// Gets an immutable reference to a field using a path of field names
+    /// separated by `.`, like [`get_path`](Self::get_path). The path also
+    /// passes through cells and locks, which stay borrowed (or locked) until
+    /// the guard is dropped.
+    #[cfg(feature = "alloc")]
+    fn lock_path<'s, 'p, T: 'static>(
+        &'s self,
+        path: &'p str,
+    ) -> PathAccessResult<'p, PathGuard<'s, T>>;
+
+    /// Gets a mutable reference to a field using a path of field names
+    /// separated by `.`, like [`get_path_mut`](Self::get_path_mut). The path
+    /// also passes through a shared `Rc` or `Arc` to the cell or lock it
+    /// shares, which stays borrowed (or locked) until the guard is dropped.
+    #[cfg(feature = "alloc")]
+    fn lock_path_mut<'s, 'p, T: 'static>(
+        &'s mut self,
+        path: &'p str,
+    ) -> PathAccessResult<'p, PathGuardMut<'s, T>>;
+
+    /// Takes the value of a field using the name of the field, leaving the
+    /// default value in its place.
+    fn take_field<'n, T: Default + 'static>(&mut self, name: &'n str) -> FieldAccessResult<'n, T>;
+
+    /// Iterates over the names and values of the reflected fields in the order
+    /// they are declared.
+    fn fields(&self) -> Fields<'_, Self>;
+
+    /// Iterates over the names and mutable values of the reflected fields in
+    /// the order they are declared. Without the `alloc` feature, use
+    /// [`visit_fields_mut`](BaseHasFields::visit_fields_mut) instead.
+    #[cfg(feature = "alloc")]
+    fn fields_mut(&mut self) -> FieldsMut<'_, Self>;
 }
 
-impl<T: BaseHasFields> HasFields for T {
-    fn get_field<'s, U: 'static>(&'s self, name: &str) -> FieldAccessResult<&'s U> {
-        self.get_field_raw(name)?
+impl<T: ?Sized + BaseHasFields> HasFields for T {
+    fn get_field<'s, 'n, U: 'static>(&'s self, name: &'n str) -> FieldAccessResult<'n, &'s U> {
+        let field = self.get_field_raw(name)?;
+        field.downcast_ref::<U>().ok_or_else(|| {
+            let actual_type = field_type_name(self, name);
+            FieldAccessError::invalid_type::<U>(name, actual_type)
+        })
+    }
+
+    fn get_field_mut<'s, 'n, U: 'static>(
+        &'s mut self,
+        name: &'n str,
+    ) -> FieldAccessResult<'n, &'s mut U> {
+        let actual_type = field_type_name(self, name);
+        let field = self.get_field_mut_raw(name)?;
+        if !field.is::<U>() {
+            return Err(FieldAccessError::invalid_type::<U>(name, actual_type));
+        }
+
+        Ok(field.downcast_mut::<U>().unwrap())
+    }
+
+    #[cfg(feature = "alloc")]
+    fn set_field<'n>(
+        &mut self,
+        name: &'n str,
+        value: Box<dyn Any>,
+    ) -> Result<(), SetFieldError<'n>> {
+        self.replace_field(name, value).map(drop)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn replace_field<'n>(
+        &mut self,
+        name: &'n str,
+        mut value: Box<dyn Any>,
+    ) -> Result<Box<dyn Any>, SetFieldError<'n>> {
+        let actual_type = field_type_name(self, name);
+        let field = match self.get_field_mut_raw(name) {
+            Ok(field) => field,
+            Err(error) => return Err(SetFieldError::new(error, value)),
+        };
+
+        if !swap_value(field, &mut *value) {
+            let error = FieldAccessError::mismatched_value(name, actual_type);
+            return Err(SetFieldError::new(error, value));
+        }
+
+        // Afterwards the box holds the previous value of the field.
+        Ok(value)
+    }
+
+    fn get_path<'s, 'p, U: 'static>(&'s self, path: &'p str) -> PathAccessResult<'p, &'s U> {
+        let (field, segment) = get_path_raw(
+            self,
+            path,
+            #[cfg(feature = "alloc")]
+            &mut PathGuards::none(),
+        )?;
+        field
             .downcast_ref::<U>()
-            .ok_or(FieldAccessError::InvalidType)
+            .ok_or_else(|| segment.into_invalid_type::<U>())
     }
 
-    fn get_field_mut<'s, U: 'static>(&'s mut self, name: &str) -> FieldAccessResult<&'s mut U> {
-        self.get_field_mut_raw(name)?
-            .downcast_mut::<U>()
-            .ok_or(FieldAccessError::InvalidType)
+    fn get_path_mut<'s, 'p, U: 'static>(
+        &'s mut self,
+        path: &'p str,
+    ) -> PathAccessResult<'p, &'s mut U> {
+        let (field, segment) = get_path_mut_raw(
+            self,
+            path,
+            #[cfg(feature = "alloc")]
+            &mut PathGuards::none(),
+        )?;
+        if !field.is::<U>() {
+            return Err(segment.into_invalid_type::<U>());
+        }
+
+        Ok(field.downcast_mut::<U>().unwrap())
+    }
+
+    #[cfg(feature = "alloc")]
+    fn lock_path<'s, 'p, U: 'static>(
+        &'s self,
+        path: &'p str,
+    ) -> PathAccessResult<'p, PathGuard<'s, U>> {
+        // The field is moved into the guard along with the guards it needs.
+        let mut guards = unsafe { PathGuards::kept() };
+        let (field, segment) = get_path_raw(self, path, &mut guards)?;
+        match field.downcast_ref::<U>() {
+            Some(field) => Ok(PathGuard::new(field, guards)),
+            None => Err(segment.into_invalid_type::<U>()),
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    fn lock_path_mut<'s, 'p, U: 'static>(
+        &'s mut self,
+        path: &'p str,
+    ) -> PathAccessResult<'p, PathGuardMut<'s, U>> {
+        // The field is moved into the guard along with the guards it needs.
+        let mut guards = unsafe { PathGuards::kept() };
+        let (field, segment) = get_path_mut_raw(self, path, &mut guards)?;
+        match field.downcast_mut::<U>() {
+            Some(field) => Ok(PathGuardMut::new(field, guards)),
+            None => Err(segment.into_invalid_type::<U>()),
+        }
+    }
+
+    fn take_field<'n, U: Default + 'static>(&mut self, name: &'n str) -> FieldAccessResult<'n, U> {
+        self.get_field_mut::<U>(name).map(take)
+    }
+
+    fn fields(&self) -> Fields<'_, Self> {
+        Fields::new(self)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn fields_mut(&mut self) -> FieldsMut<'_, Self> {
+        FieldsMut::new(self)
     }
 }
+
+/// Exchanges the values of a field and a type-erased value, provided that they
+/// are of the same type. Returns `false` without changing either value if the
+/// types differ.
+#[cfg(feature = "alloc")]
+pub(crate) fn swap_value(field: &mut dyn Any, value: &mut dyn Any) -> bool {
+    if <dyn Any>::type_id(field) != <dyn Any>::type_id(value) {
+        return false;
+    }
+
+    // Both values are of the same type so their bytes can be exchanged.
+    let size = size_of_val(field);
+    let field_ptr = field as *mut dyn Any as *mut u8;
+    let value_ptr = value as *mut dyn Any as *mut u8;
+    unsafe { swap_nonoverlapping(field_ptr, value_ptr, size) };
+    true
+}
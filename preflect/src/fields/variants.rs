@@ -0,0 +1,17 @@
+/// A trait that can be used to inspect the variants of an enum at runtime.
+pub trait HasVariants {
+    /// Gets the names of the variants in the order they are declared.
+    fn variant_names(&self) -> &'static [&'static str];
+
+    /// Gets the name of the variant the value currently holds.
+    fn variant_name(&self) -> &'static str;
+
+    /// Gets the position of the variant the value currently holds within the
+    /// declaration of the enum.
+    fn variant_index(&self) -> usize;
+
+    /// Gets the discriminant of the variant the value currently holds. This
+    /// honours explicit discriminants (e.g. `Active = 5`), and variants
+    /// without one follow the previous variant like they do in Rust.
+    fn variant_discriminant(&self) -> i128;
+}
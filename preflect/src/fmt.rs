@@ -0,0 +1,355 @@
+//! Provides facilities for formatting values through reflection, so that
+//! values can be printed consistently without implementing `Debug` by hand.
+//!
+//! The values of fields are printed by formatters which are looked up by the
+//! `TypeId` of the value. Fields which can themselves be reflected (those
+//! marked with `#[preflect(nested)]`) are printed recursively, fields marked
+//! with `#[preflect(ignore)]` are skipped and the values of fields marked with
+//! `#[preflect(redact)]` are replaced by `***`.
+
+use crate::fields::FieldInfo;
+use crate::fields::Reflect;
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+use core::any::Any;
+use core::any::TypeId;
+use core::fmt::Debug;
+use core::fmt::Display;
+use core::fmt::Error as FmtError;
+use core::fmt::Formatter;
+use core::fmt::Result as FmtResult;
+
+/// The text which is printed in place of the value of a redacted field.
+pub const REDACTED: &str = "***";
+
+/// A function which formats a type-erased value. The value is always of the
+/// type the function was registered for.
+pub type FormatFn = fn(&dyn Any, &mut Formatter<'_>) -> FmtResult;
+
+/// Formats the values of a single type when they are printed through
+/// reflection.
+#[derive(Clone, Copy)]
+pub struct ValueFormatter {
+    type_id: fn() -> TypeId,
+    format: FormatFn,
+}
+
+impl ValueFormatter {
+    /// Creates a formatter for values of type `T` which uses the provided
+    /// function.
+    pub const fn new<T: 'static>(format: FormatFn) -> Self {
+        Self {
+            type_id: TypeId::of::<T>,
+            format,
+        }
+    }
+
+    /// Creates a formatter which prints values of type `T` using their `Debug`
+    /// implementation.
+    pub const fn debug<T: Debug + 'static>() -> Self {
+        Self::new::<T>(format_debug::<T>)
+    }
+
+    /// Creates a formatter which prints values of type `T` using their
+    /// `Display` implementation.
+    pub const fn display<T: Display + 'static>() -> Self {
+        Self::new::<T>(format_display::<T>)
+    }
+
+    /// The [`TypeId`] of the values which are printed by the formatter.
+    pub fn type_id(&self) -> TypeId {
+        (self.type_id)()
+    }
+
+    /// Formats the provided value.
+    pub fn format(&self, value: &dyn Any, f: &mut Formatter<'_>) -> FmtResult {
+        (self.format)(value, f)
+    }
+}
+
+impl Debug for ValueFormatter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("ValueFormatter")
+            .field("type_id", &self.type_id())
+            .finish()
+    }
+}
+
+/// The formatters which are used for the values of fields. A formatter is
+/// looked up by the `TypeId` of a value, starting with the registered
+/// formatters and falling back to those for the primitive types (and `String`
+/// with the `alloc` feature), which print values using `Debug`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Formatters<'f> {
+    formatters: &'f [ValueFormatter],
+}
+
+impl<'f> Formatters<'f> {
+    /// Registers the provided formatters. Earlier formatters take precedence
+    /// over later formatters for the same type.
+    pub const fn new(formatters: &'f [ValueFormatter]) -> Self {
+        Self { formatters }
+    }
+
+    /// Finds the formatter for values with the provided `TypeId`.
+    pub fn find(&self, type_id: TypeId) -> Option<&'f ValueFormatter> {
+        #[cfg(feature = "alloc")]
+        let defaults = CORE_FORMATTERS.iter().chain(ALLOC_FORMATTERS);
+        #[cfg(not(feature = "alloc"))]
+        let defaults = CORE_FORMATTERS.iter();
+
+        self.formatters
+            .iter()
+            .chain(defaults)
+            .find(|formatter| ValueFormatter::type_id(formatter) == type_id)
+    }
+}
+
+/// The formatters used for the primitive types.
+const CORE_FORMATTERS: &[ValueFormatter] = &[
+    ValueFormatter::debug::<bool>(),
+    ValueFormatter::debug::<char>(),
+    ValueFormatter::debug::<i8>(),
+    ValueFormatter::debug::<i16>(),
+    ValueFormatter::debug::<i32>(),
+    ValueFormatter::debug::<i64>(),
+    ValueFormatter::debug::<i128>(),
+    ValueFormatter::debug::<isize>(),
+    ValueFormatter::debug::<u8>(),
+    ValueFormatter::debug::<u16>(),
+    ValueFormatter::debug::<u32>(),
+    ValueFormatter::debug::<u64>(),
+    ValueFormatter::debug::<u128>(),
+    ValueFormatter::debug::<usize>(),
+    ValueFormatter::debug::<f32>(),
+    ValueFormatter::debug::<f64>(),
+    ValueFormatter::debug::<&'static str>(),
+    ValueFormatter::debug::<()>(),
+];
+
+/// The formatters used for the types provided by `alloc`.
+#[cfg(feature = "alloc")]
+const ALLOC_FORMATTERS: &[ValueFormatter] = &[ValueFormatter::debug::<String>()];
+
+/// Formats a value through reflection. With `{:?}` (or `{:#?}`) the value is
+/// printed in the same way as a derived `Debug` implementation, while `{}`
+/// prints the value as a tree with one field per line:
+///
+/// ```text
+/// Config
+/// ├── name: "api"
+/// ├── token: ***
+/// └── server: Server
+///     ├── host: "localhost"
+///     └── port: 8080
+/// ```
+///
+/// Values without a formatter are printed as the name of their type in angle
+/// brackets (e.g. `<alloc::vec::Vec<u32>>`).
+#[derive(Clone, Copy)]
+pub struct ReflectFormat<'a> {
+    value: &'a dyn Reflect,
+    formatters: Formatters<'a>,
+}
+
+impl<'a> ReflectFormat<'a> {
+    /// Formats the value using the default formatters.
+    pub fn new(value: &'a dyn Reflect) -> Self {
+        Self {
+            value,
+            formatters: Formatters::default(),
+        }
+    }
+
+    /// Formats the value using the provided formatters.
+    pub fn with_formatters(value: &'a dyn Reflect, formatters: Formatters<'a>) -> Self {
+        Self { value, formatters }
+    }
+
+    fn fields(&self) -> impl Iterator<Item = FieldFormat<'a>> + 'a {
+        let value = self.value;
+        let formatters = self.formatters;
+        value.field_names().iter().filter_map(move |name| {
+            let field = value.get_field_raw(name).ok()?;
+            let info = value.get_field_info(name).ok();
+            let kind = if info.is_some_and(FieldInfo::is_redacted) {
+                FieldKind::Redacted
+            } else if let Ok(nested) = value.get_nested_raw(name) {
+                FieldKind::Nested(nested)
+            } else {
+                FieldKind::Value(field, info)
+            };
+
+            Some(FieldFormat {
+                name,
+                kind,
+                formatters,
+            })
+        })
+    }
+
+    fn fmt_tree(&self, f: &mut Formatter<'_>, indent: Option<&Indent<'_>>) -> FmtResult {
+        write!(f, "{}", value_name(self.value))?;
+
+        let mut fields = self.fields().peekable();
+        while let Some(field) = fields.next() {
+            let indent = Indent {
+                parent: indent,
+                last: fields.peek().is_none(),
+            };
+
+            writeln!(f)?;
+            indent.fmt_branch(f)?;
+            write!(f, "{}: ", field.name)?;
+            match field.kind {
+                FieldKind::Nested(nested) => {
+                    ReflectFormat::with_formatters(nested, field.formatters)
+                        .fmt_tree(f, Some(&indent))?;
+                }
+                _ => Debug::fmt(&field, f)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Debug for ReflectFormat<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let name = value_name(self.value);
+        let mut fields = self.fields().peekable();
+        if fields.peek().is_some_and(FieldFormat::is_positional) {
+            let mut debug_tuple = f.debug_tuple(name);
+            for field in fields {
+                debug_tuple.field(&field);
+            }
+
+            return debug_tuple.finish();
+        }
+
+        let mut debug_struct = f.debug_struct(name);
+        for field in fields {
+            debug_struct.field(field.name, &field);
+        }
+
+        debug_struct.finish()
+    }
+}
+
+impl Display for ReflectFormat<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        self.fmt_tree(f, None)
+    }
+}
+
+/// A field which is being formatted along with how its value is printed.
+struct FieldFormat<'a> {
+    name: &'static str,
+    kind: FieldKind<'a>,
+    formatters: Formatters<'a>,
+}
+
+impl FieldFormat<'_> {
+    /// Determines if the field belongs to a tuple struct or variant, whose
+    /// fields are named by their position.
+    fn is_positional(&self) -> bool {
+        self.name.bytes().all(|byte| byte.is_ascii_digit())
+    }
+}
+
+enum FieldKind<'a> {
+    Redacted,
+    Nested(&'a dyn Reflect),
+    Value(&'a dyn Any, Option<&'static FieldInfo>),
+}
+
+impl Debug for FieldFormat<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self.kind {
+            FieldKind::Redacted => f.write_str(REDACTED),
+            FieldKind::Nested(nested) => {
+                Debug::fmt(&ReflectFormat::with_formatters(nested, self.formatters), f)
+            }
+            FieldKind::Value(value, info) => {
+                match self.formatters.find(<dyn Any>::type_id(value)) {
+                    Some(formatter) => formatter.format(value, f),
+                    None => match info {
+                        Some(info) => write!(f, "<{}>", info.type_name()),
+                        None => f.write_str("<unknown>"),
+                    },
+                }
+            }
+        }
+    }
+}
+
+/// The position of a field within the tree printed by `ReflectFormat`. Each
+/// level refers to the level above it, so the prefix of a line can be printed
+/// without allocating.
+struct Indent<'p> {
+    parent: Option<&'p Indent<'p>>,
+    last: bool,
+}
+
+impl Indent<'_> {
+    /// Prints the prefix of the line which introduces the field.
+    fn fmt_branch(&self, f: &mut Formatter<'_>) -> FmtResult {
+        if let Some(parent) = self.parent {
+            parent.fmt_trunk(f)?;
+        }
+
+        f.write_str(if self.last {
+            "└── "
+        } else {
+            "├── "
+        })
+    }
+
+    /// Prints the prefix contributed by the field to the lines of its nested
+    /// fields.
+    fn fmt_trunk(&self, f: &mut Formatter<'_>) -> FmtResult {
+        if let Some(parent) = self.parent {
+            parent.fmt_trunk(f)?;
+        }
+
+        f.write_str(if self.last { "    " } else { "│   " })
+    }
+}
+
+/// Gets the name of a type without its path or generic arguments (e.g. `Vec`
+/// for `alloc::vec::Vec<u32>`), which is how a derived `Debug` implementation
+/// names the type.
+pub(crate) fn short_type_name(type_name: &'static str) -> &'static str {
+    let path = type_name.split('<').next().unwrap_or(type_name);
+    path.rsplit("::").next().unwrap_or(path)
+}
+
+/// Gets the name a reflected value is printed with, which is the name of its
+/// layout for a [`DynamicStruct`](crate::fields::DynamicStruct).
+pub(crate) fn struct_name(value: &dyn Reflect) -> &'static str {
+    #[cfg(feature = "alloc")]
+    if let Some(dynamic) = value.downcast_ref::<crate::fields::DynamicStruct>() {
+        return dynamic.layout().name();
+    }
+
+    short_type_name(value.type_name())
+}
+
+/// Gets the name a reflected value is printed with by `ReflectFormat`, which
+/// is the name of the active variant for an enum, like a derived `Debug`
+/// implementation prints it.
+fn value_name(value: &dyn Reflect) -> &'static str {
+    value.active_variant().unwrap_or_else(|| struct_name(value))
+}
+
+fn format_debug<T: Debug + 'static>(value: &dyn Any, f: &mut Formatter<'_>) -> FmtResult {
+    value
+        .downcast_ref::<T>()
+        .map_or(Err(FmtError), |value| Debug::fmt(value, f))
+}
+
+fn format_display<T: Display + 'static>(value: &dyn Any, f: &mut Formatter<'_>) -> FmtResult {
+    value
+        .downcast_ref::<T>()
+        .map_or(Err(FmtError), |value| Display::fmt(value, f))
+}
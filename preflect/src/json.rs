@@ -0,0 +1,26 @@
+//! Provides JSON Pointers ([RFC 6901]) for addressing the fields of reflected
+//! values and the elements within them, along with JSON Patches ([RFC 6902])
+//! for modifying reflected values. Values are exchanged as a [`Value`], which
+//! is converted to and from the values of fields by the registered
+//! [`Converters`](crate::value::Converters).
+//!
+//! Pointers pass through reflected fields (those marked with
+//! `#[preflect(nested)]`) and then through the [`Value`] of a field, so
+//! `/items/0/name` addresses the `name` of the first element of an `items`
+//! field whose converter produces a [`Value::List`].
+//!
+//! [`Value`]: crate::value::Value
+//! [`Value::List`]: crate::value::Value::List
+//! [RFC 6901]: https://www.rfc-editor.org/rfc/rfc6901
+//! [RFC 6902]: https://www.rfc-editor.org/rfc/rfc6902
+
+#![cfg(feature = "alloc")]
+
+mod errors;
+mod patch;
+mod pointer;
+mod reflect;
+
+pub use self::errors::*;
+pub use self::patch::*;
+pub use self::pointer::*;
@@ -0,0 +1,83 @@
+use crate::json::JsonPointer;
+use crate::value::ValueError;
+use alloc::string::String;
+use core::fmt::Display;
+use core::fmt::Formatter;
+use core::fmt::Result as FmtResult;
+
+/// The result of resolving a [`JsonPointer`] or applying a
+/// [`JsonPatch`](crate::json::JsonPatch).
+pub type JsonResult<T> = Result<T, JsonError>;
+
+/// An error that occurred while resolving a [`JsonPointer`] or applying a
+/// [`JsonPatch`](crate::json::JsonPatch).
+#[derive(Clone, Debug, PartialEq)]
+pub enum JsonError {
+    /// The text of a pointer was not a valid JSON Pointer.
+    InvalidPointer {
+        /// The text of the pointer.
+        pointer: String,
+    },
+    /// Nothing exists at the location of the pointer.
+    NotFound {
+        /// The pointer which could not be resolved.
+        pointer: JsonPointer,
+    },
+    /// The value at the location of the pointer can not be removed (e.g. the
+    /// field of a struct).
+    CannotRemove {
+        /// The pointer to the value.
+        pointer: JsonPointer,
+    },
+    /// A value can not be moved into one of its own children.
+    MoveIntoChild {
+        /// The pointer to the value which was being moved.
+        from: JsonPointer,
+        /// The pointer to the location it was being moved to.
+        path: JsonPointer,
+    },
+    /// A `test` operation found a different value at the location of the
+    /// pointer.
+    TestFailed {
+        /// The pointer to the value which was tested.
+        pointer: JsonPointer,
+    },
+    /// A value could not be converted to or from the value of a field.
+    Value {
+        /// The pointer to the field.
+        pointer: JsonPointer,
+        /// The reason the value could not be converted.
+        error: ValueError,
+    },
+}
+
+impl Display for JsonError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            JsonError::InvalidPointer { pointer } => {
+                write!(f, "'{}' is not a valid JSON Pointer.", pointer)
+            }
+            JsonError::NotFound { pointer } => {
+                write!(f, "Nothing exists at '{}'.", pointer)
+            }
+            JsonError::CannotRemove { pointer } => {
+                write!(f, "The value at '{}' can not be removed.", pointer)
+            }
+            JsonError::MoveIntoChild { from, path } => write!(
+                f,
+                "The value at '{}' can not be moved into its child '{}'.",
+                from, path
+            ),
+            JsonError::TestFailed { pointer } => {
+                write!(f, "The value at '{}' did not match the test.", pointer)
+            }
+            JsonError::Value { pointer, error } => {
+                write!(
+                    f,
+                    "The value at '{}' could not be converted: {}",
+                    pointer, error
+                )
+            }
+        }
+    }
+}
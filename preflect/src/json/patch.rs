@@ -0,0 +1,332 @@
+use crate::fields::Reflect;
+use crate::json::pointer::parse_index;
+use crate::json::pointer::resolve;
+use crate::json::pointer::resolve_mut;
+use crate::json::JsonError;
+use crate::json::JsonPointer;
+use crate::json::JsonResult;
+use crate::value::Converters;
+use crate::value::Value;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// A single operation of a [`JsonPatch`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum PatchOperation {
+    /// Adds a value to a map or inserts it into a list (where the token `-`
+    /// appends it). A value which already exists at the location (e.g. the
+    /// value of a field) is replaced.
+    Add {
+        /// The location of the value.
+        path: JsonPointer,
+        /// The value to add.
+        value: Value,
+    },
+    /// Removes the value at a location, which must exist.
+    Remove {
+        /// The location of the value.
+        path: JsonPointer,
+    },
+    /// Replaces the value at a location, which must exist.
+    Replace {
+        /// The location of the value.
+        path: JsonPointer,
+        /// The new value.
+        value: Value,
+    },
+    /// Removes the value at one location and adds it at another.
+    Move {
+        /// The location of the value to move.
+        from: JsonPointer,
+        /// The location the value is moved to.
+        path: JsonPointer,
+    },
+    /// Adds a copy of the value at one location at another.
+    Copy {
+        /// The location of the value to copy.
+        from: JsonPointer,
+        /// The location the copy is added at.
+        path: JsonPointer,
+    },
+    /// Ensures that the value at a location is equal to a value. Numbers are
+    /// equal if they have the same value, regardless of their type.
+    Test {
+        /// The location of the value.
+        path: JsonPointer,
+        /// The expected value.
+        value: Value,
+    },
+}
+
+/// A sequence of operations which modify a value, as described by JSON Patch
+/// ([RFC 6902]). A patch is applied atomically: if any operation fails, the
+/// value is left as it was.
+///
+/// ```
+/// use preflect::json::JsonPatch;
+/// use preflect::json::JsonPointer;
+/// use preflect::json::PatchOperation;
+/// use preflect::value::Value;
+///
+/// let mut list = Value::List(vec![Value::UInt(1)]);
+/// let patch = JsonPatch::from(vec![PatchOperation::Add {
+///     path: JsonPointer::parse("/-").unwrap(),
+///     value: Value::UInt(2),
+/// }]);
+///
+/// patch.apply_to_value(&mut list).unwrap();
+/// assert_eq!(Value::List(vec![Value::UInt(1), Value::UInt(2)]), list);
+/// ```
+///
+/// [RFC 6902]: https://www.rfc-editor.org/rfc/rfc6902
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct JsonPatch {
+    operations: Vec<PatchOperation>,
+}
+
+impl JsonPatch {
+    /// Creates a patch without any operations.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an operation to the end of the patch.
+    pub fn push(&mut self, operation: PatchOperation) {
+        self.operations.push(operation);
+    }
+
+    /// The operations of the patch in the order they are applied.
+    pub fn operations(&self) -> &[PatchOperation] {
+        &self.operations
+    }
+
+    /// Applies the patch to a [`Value`].
+    pub fn apply_to_value(&self, document: &mut Value) -> JsonResult<()> {
+        let mut patched = document.clone();
+        for operation in &self.operations {
+            apply_to_value(operation, &mut patched)?;
+        }
+
+        *document = patched;
+        Ok(())
+    }
+
+    /// Applies the patch to a reflected value using the default converters.
+    pub fn apply(&self, target: &mut dyn Reflect) -> JsonResult<()> {
+        Converters::default().apply_patch(self, target)
+    }
+}
+
+impl From<Vec<PatchOperation>> for JsonPatch {
+    fn from(operations: Vec<PatchOperation>) -> Self {
+        Self { operations }
+    }
+}
+
+fn apply_to_value(operation: &PatchOperation, document: &mut Value) -> JsonResult<()> {
+    match operation {
+        PatchOperation::Add { path, value } => add(document, path, 0, value.clone()),
+        PatchOperation::Remove { path } => remove(document, path, 0).map(drop),
+        PatchOperation::Replace { path, value } => replace(document, path, 0, value.clone()),
+        PatchOperation::Move { from, path } => {
+            check_move(from, path)?;
+            let value = remove(document, from, 0)?;
+            add(document, path, 0, value)
+        }
+        PatchOperation::Copy { from, path } => {
+            let value = get(document, from, 0)?.clone();
+            add(document, path, 0, value)
+        }
+        PatchOperation::Test { path, value } => test(get(document, path, 0)?, path, value),
+    }
+}
+
+/// Gets the value at the location of `pointer`, of which the first `skip`
+/// tokens have already been resolved to `document`.
+pub(super) fn get<'v>(
+    document: &'v Value,
+    pointer: &JsonPointer,
+    skip: usize,
+) -> JsonResult<&'v Value> {
+    resolve(document, &pointer.token_slice()[skip..]).ok_or_else(|| not_found(pointer))
+}
+
+/// Adds a value at the location of `pointer`, of which the first `skip`
+/// tokens have already been resolved to `document`.
+pub(super) fn add(
+    document: &mut Value,
+    pointer: &JsonPointer,
+    skip: usize,
+    value: Value,
+) -> JsonResult<()> {
+    let (parent, token) = match split_parent(document, pointer, skip)? {
+        Some(parent) => parent,
+        None => {
+            *document = value;
+            return Ok(());
+        }
+    };
+
+    match parent {
+        Value::Struct(struct_value) => {
+            struct_value.set(token.as_str(), value);
+        }
+        Value::Map(entries) => {
+            entries.insert(token.clone(), value);
+        }
+        Value::List(elements) => {
+            let index = match token.as_str() {
+                "-" => elements.len(),
+                token => parse_index(token)
+                    .filter(|index| *index <= elements.len())
+                    .ok_or_else(|| not_found(pointer))?,
+            };
+
+            elements.insert(index, value);
+        }
+        _ => return Err(not_found(pointer)),
+    }
+
+    Ok(())
+}
+
+/// Removes the value at the location of `pointer`, of which the first `skip`
+/// tokens have already been resolved to `document`.
+pub(super) fn remove(
+    document: &mut Value,
+    pointer: &JsonPointer,
+    skip: usize,
+) -> JsonResult<Value> {
+    let (parent, token) =
+        split_parent(document, pointer, skip)?.ok_or_else(|| JsonError::CannotRemove {
+            pointer: pointer.clone(),
+        })?;
+
+    let removed = match parent {
+        Value::Struct(struct_value) => struct_value.remove(token),
+        Value::Map(entries) => entries.remove(token),
+        Value::List(elements) => parse_index(token)
+            .filter(|index| *index < elements.len())
+            .map(|index| elements.remove(index)),
+        _ => None,
+    };
+
+    removed.ok_or_else(|| not_found(pointer))
+}
+
+/// Replaces the value at the location of `pointer`, of which the first `skip`
+/// tokens have already been resolved to `document`.
+pub(super) fn replace(
+    document: &mut Value,
+    pointer: &JsonPointer,
+    skip: usize,
+    value: Value,
+) -> JsonResult<()> {
+    let target =
+        resolve_mut(document, &pointer.token_slice()[skip..]).ok_or_else(|| not_found(pointer))?;
+    *target = value;
+    Ok(())
+}
+
+/// Ensures that a value would not be moved into one of its own children.
+pub(super) fn check_move(from: &JsonPointer, path: &JsonPointer) -> JsonResult<()> {
+    if path.is_child_of(from) {
+        return Err(JsonError::MoveIntoChild {
+            from: from.clone(),
+            path: path.clone(),
+        });
+    }
+
+    Ok(())
+}
+
+/// Ensures that the value found by a `test` operation is equal to the
+/// expected value.
+pub(super) fn test(actual: &Value, pointer: &JsonPointer, expected: &Value) -> JsonResult<()> {
+    if json_eq(actual, expected) {
+        Ok(())
+    } else {
+        Err(JsonError::TestFailed {
+            pointer: pointer.clone(),
+        })
+    }
+}
+
+pub(super) fn not_found(pointer: &JsonPointer) -> JsonError {
+    JsonError::NotFound {
+        pointer: pointer.clone(),
+    }
+}
+
+/// Splits the last token from the unresolved part of `pointer`, returning the
+/// value it belongs to. Returns `None` if the pointer addresses `document`
+/// itself.
+fn split_parent<'v, 'p>(
+    document: &'v mut Value,
+    pointer: &'p JsonPointer,
+    skip: usize,
+) -> JsonResult<Option<(&'v mut Value, &'p String)>> {
+    let tokens = &pointer.token_slice()[skip..];
+    let (token, parent_tokens) = match tokens.split_last() {
+        Some(split) => split,
+        None => return Ok(None),
+    };
+
+    let parent = resolve_mut(document, parent_tokens).ok_or_else(|| not_found(pointer))?;
+    Ok(Some((parent, token)))
+}
+
+/// Compares two values as JSON values: numbers are compared by value, and
+/// structs and maps are compared by their members regardless of their order.
+fn json_eq(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Int(_), _) | (Value::UInt(_), _) | (Value::Float(_), _) => {
+            match (as_number(a), as_number(b)) {
+                (Some(a), Some(b)) => a == b,
+                _ => false,
+            }
+        }
+        (Value::List(a), Value::List(b)) => {
+            a.len() == b.len() && a.iter().zip(b).all(|(a, b)| json_eq(a, b))
+        }
+        (Value::Struct(_), _) | (Value::Map(_), _) => match (members(a), members(b)) {
+            (Some(a), Some(b)) => {
+                a.len() == b.len()
+                    && a.iter()
+                        .all(|(name, a)| b.iter().any(|(other, b)| name == other && json_eq(a, b)))
+            }
+            _ => false,
+        },
+        (a, b) => a == b,
+    }
+}
+
+/// A number which can be compared with numbers of other types.
+#[derive(PartialEq)]
+enum Number {
+    Int(i128),
+    Float(f64),
+}
+
+fn as_number(value: &Value) -> Option<Number> {
+    match *value {
+        Value::Int(value) => Some(Number::Int(value.into())),
+        Value::UInt(value) => Some(Number::Int(value.into())),
+        Value::Float(value) if (value as i128) as f64 == value => Some(Number::Int(value as i128)),
+        Value::Float(value) => Some(Number::Float(value)),
+        _ => None,
+    }
+}
+
+fn members(value: &Value) -> Option<Vec<(&str, &Value)>> {
+    match value {
+        Value::Struct(struct_value) => Some(struct_value.fields().collect()),
+        Value::Map(entries) => Some(
+            entries
+                .iter()
+                .map(|(name, value)| (name.as_str(), value))
+                .collect(),
+        ),
+        _ => None,
+    }
+}
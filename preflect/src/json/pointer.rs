@@ -0,0 +1,182 @@
+use crate::json::JsonError;
+use crate::json::JsonResult;
+use crate::value::Value;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Display;
+use core::fmt::Formatter;
+use core::fmt::Result as FmtResult;
+use core::fmt::Write;
+use core::str::FromStr;
+
+/// A JSON Pointer ([RFC 6901]) such as `/address/city`, which addresses a
+/// location within a value by a sequence of reference tokens. Each token is
+/// the name of a field, the key of a map or the index of a list element. The
+/// empty pointer addresses the whole value.
+///
+/// ```
+/// use preflect::json::JsonPointer;
+///
+/// let pointer = JsonPointer::parse("/a~1b/0").unwrap();
+/// assert_eq!(vec!["a/b", "0"], pointer.tokens().collect::<Vec<_>>());
+/// assert_eq!("/a~1b/0", pointer.to_string());
+/// ```
+///
+/// [RFC 6901]: https://www.rfc-editor.org/rfc/rfc6901
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct JsonPointer {
+    tokens: Vec<String>,
+}
+
+impl JsonPointer {
+    /// Creates the empty pointer, which addresses the whole value.
+    pub fn root() -> Self {
+        Self::default()
+    }
+
+    /// Parses the text of a pointer, in which `~1` and `~0` stand for `/` and
+    /// `~` within a token.
+    pub fn parse(pointer: &str) -> JsonResult<Self> {
+        let invalid = || JsonError::InvalidPointer {
+            pointer: pointer.into(),
+        };
+
+        if pointer.is_empty() {
+            return Ok(Self::root());
+        }
+
+        let rest = pointer.strip_prefix('/').ok_or_else(invalid)?;
+        let tokens = rest
+            .split('/')
+            .map(|token| unescape(token).ok_or_else(invalid))
+            .collect::<JsonResult<_>>()?;
+
+        Ok(Self { tokens })
+    }
+
+    /// Creates a pointer from unescaped reference tokens.
+    pub fn from_tokens<I: IntoIterator<Item = S>, S: Into<String>>(tokens: I) -> Self {
+        Self {
+            tokens: tokens.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Iterates over the unescaped reference tokens of the pointer.
+    pub fn tokens(&self) -> impl Iterator<Item = &str> {
+        self.tokens.iter().map(String::as_str)
+    }
+
+    /// Determines if the pointer addresses the whole value.
+    pub fn is_root(&self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    /// Adds a reference token to the end of the pointer.
+    pub fn push(&mut self, token: impl Into<String>) {
+        self.tokens.push(token.into());
+    }
+
+    /// Determines if the pointer addresses a location within the location of
+    /// `other` (and not the location itself).
+    pub fn is_child_of(&self, other: &JsonPointer) -> bool {
+        self.tokens.len() > other.tokens.len() && self.tokens.starts_with(&other.tokens)
+    }
+
+    pub(crate) fn token_slice(&self) -> &[String] {
+        &self.tokens
+    }
+}
+
+impl Display for JsonPointer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        for token in &self.tokens {
+            f.write_char('/')?;
+            for c in token.chars() {
+                match c {
+                    '~' => f.write_str("~0")?,
+                    '/' => f.write_str("~1")?,
+                    c => f.write_char(c)?,
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for JsonPointer {
+    type Err = JsonError;
+
+    fn from_str(pointer: &str) -> JsonResult<Self> {
+        Self::parse(pointer)
+    }
+}
+
+impl Value {
+    /// Gets the value at the location of a pointer within this value.
+    pub fn pointer(&self, pointer: &JsonPointer) -> Option<&Value> {
+        resolve(self, pointer.token_slice())
+    }
+
+    /// Gets the mutable value at the location of a pointer within this
+    /// value.
+    pub fn pointer_mut(&mut self, pointer: &JsonPointer) -> Option<&mut Value> {
+        resolve_mut(self, pointer.token_slice())
+    }
+}
+
+pub(crate) fn resolve<'v>(mut value: &'v Value, tokens: &[String]) -> Option<&'v Value> {
+    for token in tokens {
+        value = match value {
+            Value::Struct(struct_value) => struct_value.get(token)?,
+            Value::Map(entries) => entries.get(token)?,
+            Value::List(elements) => elements.get(parse_index(token)?)?,
+            _ => return None,
+        };
+    }
+
+    Some(value)
+}
+
+pub(crate) fn resolve_mut<'v>(
+    mut value: &'v mut Value,
+    tokens: &[String],
+) -> Option<&'v mut Value> {
+    for token in tokens {
+        value = match value {
+            Value::Struct(struct_value) => struct_value.get_mut(token)?,
+            Value::Map(entries) => entries.get_mut(token)?,
+            Value::List(elements) => elements.get_mut(parse_index(token)?)?,
+            _ => return None,
+        };
+    }
+
+    Some(value)
+}
+
+/// Parses the index of a list element, which must not have leading zeros.
+pub(crate) fn parse_index(token: &str) -> Option<usize> {
+    let is_canonical = token == "0" || !token.starts_with('0');
+    if is_canonical && !token.is_empty() && token.bytes().all(|b| b.is_ascii_digit()) {
+        token.parse().ok()
+    } else {
+        None
+    }
+}
+
+fn unescape(token: &str) -> Option<String> {
+    let mut unescaped = String::with_capacity(token.len());
+    let mut chars = token.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '~' => match chars.next() {
+                Some('0') => unescaped.push('~'),
+                Some('1') => unescaped.push('/'),
+                _ => return None,
+            },
+            c => unescaped.push(c),
+        }
+    }
+
+    Some(unescaped)
+}
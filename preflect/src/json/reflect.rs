@@ -0,0 +1,241 @@
+use crate::fields::errors::field_type_name;
+use crate::fields::Reflect;
+use crate::json::patch::add;
+use crate::json::patch::check_move;
+use crate::json::patch::get;
+use crate::json::patch::not_found;
+use crate::json::patch::remove;
+use crate::json::patch::replace;
+use crate::json::patch::test;
+use crate::json::JsonError;
+use crate::json::JsonPatch;
+use crate::json::JsonPointer;
+use crate::json::JsonResult;
+use crate::json::PatchOperation;
+use crate::value::Converters;
+use crate::value::Value;
+use crate::value::ValueError;
+use alloc::vec::Vec;
+use core::any::Any;
+
+/// The previous values of the fields which were written while applying a
+/// patch, so that they can be restored if a later operation fails.
+type UndoLog = Vec<(JsonPointer, Value)>;
+
+impl Converters<'_> {
+    /// Gets the value at the location of a pointer within a reflected value.
+    pub fn get_pointer(&self, target: &dyn Reflect, pointer: &JsonPointer) -> JsonResult<Value> {
+        let depth = locate(target, pointer)?;
+        let field = self.read_field(target, pointer, depth)?;
+        get(&field, pointer, depth).cloned()
+    }
+
+    /// Replaces the value at the location of a pointer within a reflected
+    /// value. The location must already exist.
+    pub fn set_pointer(
+        &self,
+        target: &mut dyn Reflect,
+        pointer: &JsonPointer,
+        value: Value,
+    ) -> JsonResult<()> {
+        self.update(target, pointer, &mut UndoLog::new(), |field, depth| {
+            replace(field, pointer, depth, value)
+        })
+    }
+
+    /// Applies a patch to a reflected value. The fields which are modified by
+    /// an operation are converted to a [`Value`], modified and converted back,
+    /// so the fields of a struct can be replaced but not removed. If any
+    /// operation fails, the fields which were modified are restored.
+    pub fn apply_patch(&self, patch: &JsonPatch, target: &mut dyn Reflect) -> JsonResult<()> {
+        let mut undo = UndoLog::new();
+        for operation in patch.operations() {
+            if let Err(error) = self.apply_operation(operation, target, &mut undo) {
+                for (pointer, value) in undo.into_iter().rev() {
+                    let depth = pointer.token_slice().len();
+                    let _ = self.write_field(target, &pointer, depth, &value);
+                }
+
+                return Err(error);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn apply_operation(
+        &self,
+        operation: &PatchOperation,
+        target: &mut dyn Reflect,
+        undo: &mut UndoLog,
+    ) -> JsonResult<()> {
+        match operation {
+            PatchOperation::Add { path, value } => {
+                self.update(target, path, undo, |field, depth| {
+                    add(field, path, depth, value.clone())
+                })
+            }
+            PatchOperation::Remove { path } => self.update(target, path, undo, |field, depth| {
+                remove(field, path, depth).map(drop)
+            }),
+            PatchOperation::Replace { path, value } => {
+                self.update(target, path, undo, |field, depth| {
+                    replace(field, path, depth, value.clone())
+                })
+            }
+            PatchOperation::Move { from, path } => {
+                check_move(from, path)?;
+                let value = self.get_pointer(target, from)?;
+                self.update(target, from, undo, |field, depth| {
+                    remove(field, from, depth).map(drop)
+                })?;
+                self.update(target, path, undo, |field, depth| {
+                    add(field, path, depth, value)
+                })
+            }
+            PatchOperation::Copy { from, path } => {
+                let value = self.get_pointer(target, from)?;
+                self.update(target, path, undo, |field, depth| {
+                    add(field, path, depth, value)
+                })
+            }
+            PatchOperation::Test { path, value } => {
+                test(&self.get_pointer(target, path)?, path, value)
+            }
+        }
+    }
+
+    /// Converts the field which a pointer passes through to a [`Value`],
+    /// modifies it and converts it back, recording its previous value.
+    fn update(
+        &self,
+        target: &mut dyn Reflect,
+        pointer: &JsonPointer,
+        undo: &mut UndoLog,
+        modify: impl FnOnce(&mut Value, usize) -> JsonResult<()>,
+    ) -> JsonResult<()> {
+        let depth = locate(target, pointer)?;
+        let mut field = self.read_field(target, pointer, depth)?;
+        let previous = field.clone();
+        modify(&mut field, depth)?;
+        self.write_field(target, pointer, depth, &field)?;
+        let field_pointer = JsonPointer::from_tokens(&pointer.token_slice()[..depth]);
+        undo.push((field_pointer, previous));
+        Ok(())
+    }
+
+    /// Converts the field at the first `depth` tokens of a pointer to a
+    /// [`Value`].
+    fn read_field(
+        &self,
+        target: &dyn Reflect,
+        pointer: &JsonPointer,
+        depth: usize,
+    ) -> JsonResult<Value> {
+        let in_pointer = |error| value_error(pointer, error);
+        let (name, parents) = match pointer.token_slice()[..depth].split_last() {
+            Some(split) => split,
+            None => return self.to_value(target).map_err(in_pointer),
+        };
+
+        let mut owner = target;
+        for parent in parents {
+            owner = owner
+                .get_nested_raw(parent)
+                .map_err(|_| not_found(pointer))?;
+        }
+
+        if let Ok(nested) = owner.get_nested_raw(name) {
+            return self.to_value(nested).map_err(in_pointer);
+        }
+
+        let field = owner.get_field_raw(name).map_err(|_| not_found(pointer))?;
+        match self.find(<dyn Any>::type_id(field)) {
+            Some(converter) => Ok(converter.to_value(field)),
+            None => Err(in_pointer(unsupported(owner, name))),
+        }
+    }
+
+    /// Overwrites the field at the first `depth` tokens of a pointer with a
+    /// [`Value`]. The field is left unchanged if the value does not match.
+    fn write_field(
+        &self,
+        target: &mut dyn Reflect,
+        pointer: &JsonPointer,
+        depth: usize,
+        value: &Value,
+    ) -> JsonResult<()> {
+        let in_pointer = |error| value_error(pointer, error);
+        let (name, parents) = match pointer.token_slice()[..depth].split_last() {
+            Some(split) => split,
+            None => return self.apply(value, target).map_err(in_pointer),
+        };
+
+        let mut owner = target;
+        for parent in parents {
+            owner = owner
+                .get_nested_mut_raw(parent)
+                .map_err(|_| not_found(pointer))?;
+        }
+
+        if owner.get_nested_raw(name).is_ok() {
+            let nested = owner
+                .get_nested_mut_raw(name)
+                .map_err(|_| not_found(pointer))?;
+            return self.apply(value, nested).map_err(in_pointer);
+        }
+
+        let type_name = field_type_name(owner, name).unwrap_or("unknown");
+        let field = owner
+            .get_field_mut_raw(name)
+            .map_err(|_| not_found(pointer))?;
+        match self.find(<dyn Any>::type_id(field)) {
+            Some(converter) => converter.from_value(value, field).map_err(in_pointer),
+            None => Err(in_pointer(ValueError::Unsupported { type_name })),
+        }
+    }
+}
+
+impl JsonPointer {
+    /// Gets the value at the location of the pointer within a reflected value
+    /// using the default converters.
+    pub fn get(&self, target: &dyn Reflect) -> JsonResult<Value> {
+        Converters::default().get_pointer(target, self)
+    }
+
+    /// Replaces the value at the location of the pointer within a reflected
+    /// value using the default converters.
+    pub fn set(&self, target: &mut dyn Reflect, value: Value) -> JsonResult<()> {
+        Converters::default().set_pointer(target, self, value)
+    }
+}
+
+/// Finds the number of tokens of a pointer which pass through reflected
+/// fields. The remaining tokens address a location within the value of the
+/// last of those fields.
+fn locate(target: &dyn Reflect, pointer: &JsonPointer) -> JsonResult<usize> {
+    let tokens = pointer.token_slice();
+    let mut owner = target;
+    for (index, token) in tokens.iter().enumerate() {
+        owner.get_field_raw(token).map_err(|_| not_found(pointer))?;
+        match owner.get_nested_raw(token) {
+            Ok(nested) if index + 1 < tokens.len() => owner = nested,
+            _ => return Ok(index + 1),
+        }
+    }
+
+    Ok(0)
+}
+
+fn value_error(pointer: &JsonPointer, error: ValueError) -> JsonError {
+    JsonError::Value {
+        pointer: pointer.clone(),
+        error,
+    }
+}
+
+fn unsupported(owner: &dyn Reflect, name: &str) -> ValueError {
+    ValueError::Unsupported {
+        type_name: field_type_name(owner, name).unwrap_or("unknown"),
+    }
+}
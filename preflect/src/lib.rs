@@ -1,13 +1,25 @@
 //! A crate that allows for compile time reflection of tagged types.
 
 #![no_std]
-#![allow(incomplete_features)]
-#![feature(const_generics)]
-#![feature(generic_associated_types)]
+#![cfg_attr(feature = "nightly", allow(incomplete_features))]
+#![cfg_attr(feature = "nightly", feature(adt_const_params, unsized_const_params))]
 #![warn(clippy::all)]
 #![warn(missing_docs)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+pub mod collections;
+pub mod diff;
 pub mod drop;
 pub mod fields;
+pub mod fmt;
+pub mod json;
+pub mod pointers;
+#[cfg(feature = "serde")]
+pub mod serde;
+pub mod value;
 
 pub use memoffset;
@@ -0,0 +1,30 @@
+//! Provides reflection for smart pointers (`Box`, `Rc` and `Arc`) and for
+//! types with interior mutability (`RefCell`, `Cell` and, when the `std`
+//! feature is enabled, `Mutex`), whose targets are accessed as type-erased
+//! values.
+//!
+//! The target of a pointer can be read directly, while the value inside a
+//! cell or lock is read through a guard which keeps it borrowed (or locked)
+//! until the guard is dropped. Any of them can be modified through a mutable
+//! reference to the pointer, except for an `Rc` or `Arc` which is shared.
+//!
+//! A field marked with `#[preflect(pointer)]` can be accessed as a pointer
+//! through [`BaseHasFields`](crate::fields::BaseHasFields), and paths pass
+//! through it to the fields of its target: `config.theme` addresses the
+//! `theme` of a `config: Rc<Config>` field. Paths only pass through a cell or
+//! a lock when it is accessed mutably, or when the path is locked with
+//! [`lock_path`](crate::fields::HasFields::lock_path). A path locked with
+//! [`lock_path_mut`](crate::fields::HasFields::lock_path_mut) also passes
+//! through a shared `Rc` or `Arc` to the cell or lock it shares.
+
+#![cfg(feature = "alloc")]
+
+mod access;
+mod errors;
+mod guard;
+mod pointer;
+
+pub use self::access::*;
+pub use self::errors::*;
+pub use self::guard::*;
+pub use self::pointer::*;
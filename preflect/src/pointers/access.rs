@@ -0,0 +1,317 @@
+use crate::collections::ElementCast;
+use crate::fields::Reflect;
+use crate::pointers::AsPointer;
+use crate::pointers::PointerError;
+use crate::pointers::PointerGuard;
+use crate::pointers::PointerGuardMut;
+use crate::pointers::PointerResult;
+use crate::pointers::ReflectPointer;
+use core::any::Any;
+use core::fmt::Debug;
+use core::fmt::Formatter;
+use core::fmt::Result as FmtResult;
+use core::marker::PhantomData;
+
+/// Views the type-erased target of a pointer as a reflected value when its
+/// type can be reflected, or as a pointer when the target is itself a pointer
+/// (e.g. the `RefCell` of an `Rc<RefCell<T>>`).
+#[derive(Clone, Copy)]
+pub struct TargetCast {
+    target: ElementCast,
+    pointer: Option<PointerCasts>,
+}
+
+/// Views a target as a pointer, along with the cast for the target of that
+/// pointer.
+#[derive(Clone, Copy)]
+struct PointerCasts {
+    as_pointer: fn(&dyn Any) -> Option<&dyn ReflectPointer>,
+    as_pointer_mut: fn(&mut dyn Any) -> Option<&mut dyn ReflectPointer>,
+    target: ElementCast,
+}
+
+impl TargetCast {
+    /// Used for targets which can not be reflected.
+    pub fn opaque() -> Self {
+        Self {
+            target: ElementCast::opaque(),
+            pointer: None,
+        }
+    }
+
+    /// Views targets of type `T` as reflected values.
+    pub fn reflect<T: Reflect>() -> Self {
+        Self {
+            target: ElementCast::reflect::<T>(),
+            pointer: None,
+        }
+    }
+
+    /// Views targets of type `P` as pointers whose own targets are viewed by
+    /// `target`.
+    pub fn pointer<P: AsPointer + Any>(target: ElementCast) -> Self {
+        Self {
+            target: ElementCast::opaque(),
+            pointer: Some(PointerCasts {
+                as_pointer: |value| value.downcast_ref::<P>().map(|p| p as &dyn ReflectPointer),
+                as_pointer_mut: |value| {
+                    value
+                        .downcast_mut::<P>()
+                        .map(|p| p as &mut dyn ReflectPointer)
+                },
+                target,
+            }),
+        }
+    }
+
+    /// Determines if the targets can be reflected.
+    pub fn is_reflect(&self) -> bool {
+        self.target.is_reflect()
+    }
+
+    /// Determines if the targets are themselves pointers.
+    pub fn is_pointer(&self) -> bool {
+        self.pointer.is_some()
+    }
+
+    /// Views a target as a reflected value.
+    pub fn as_reflect<'a>(&self, target: &'a dyn Any) -> Option<&'a dyn Reflect> {
+        self.target.as_reflect(target)
+    }
+
+    /// Views a target as a mutable reflected value.
+    pub fn as_reflect_mut<'a>(&self, target: &'a mut dyn Any) -> Option<&'a mut dyn Reflect> {
+        self.target.as_reflect_mut(target)
+    }
+
+    /// Views a target as a pointer.
+    pub fn as_pointer<'a>(&self, target: &'a dyn Any) -> Option<PointerRef<'a>> {
+        let casts = self.pointer?;
+        let pointer = (casts.as_pointer)(target)?;
+        Some(PointerRef {
+            pointer,
+            cast: TargetCast {
+                target: casts.target,
+                pointer: None,
+            },
+        })
+    }
+
+    /// Views a target as a mutable pointer.
+    pub fn as_pointer_mut<'a>(&self, target: &'a mut dyn Any) -> Option<PointerMut<'a>> {
+        let casts = self.pointer?;
+        let pointer = (casts.as_pointer_mut)(target)?;
+        Some(PointerMut {
+            pointer,
+            cast: TargetCast {
+                target: casts.target,
+                pointer: None,
+            },
+        })
+    }
+}
+
+impl Debug for TargetCast {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("TargetCast")
+            .field("is_reflect", &self.is_reflect())
+            .field("is_pointer", &self.is_pointer())
+            .finish()
+    }
+}
+
+/// Selects the [`TargetCast`] for the target type `T` of a pointer through
+/// autoref specialization: `(&&&&Target::<T>::new()).cast()` prefers viewing
+/// the target as a reflected value, then as a pointer to a reflected value,
+/// then as any pointer. This is used by the code generated for
+/// `#[preflect(pointer)]`.
+#[doc(hidden)]
+pub struct Target<T: ?Sized>(PhantomData<fn() -> *const T>);
+
+impl<T: ?Sized> Target<T> {
+    #[doc(hidden)]
+    pub fn new() -> Self {
+        Target(PhantomData)
+    }
+}
+
+impl<T: ?Sized> Default for Target<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[doc(hidden)]
+pub trait ReflectTarget {
+    fn cast(&self) -> TargetCast;
+}
+
+impl<T: Reflect> ReflectTarget for &&&Target<T> {
+    fn cast(&self) -> TargetCast {
+        TargetCast::reflect::<T>()
+    }
+}
+
+#[doc(hidden)]
+pub trait ReflectPointerTarget {
+    fn cast(&self) -> TargetCast;
+}
+
+impl<P: AsPointer + Any> ReflectPointerTarget for &&Target<P>
+where
+    P::Target: Reflect,
+{
+    fn cast(&self) -> TargetCast {
+        TargetCast::pointer::<P>(ElementCast::reflect::<P::Target>())
+    }
+}
+
+#[doc(hidden)]
+pub trait PointerTarget {
+    fn cast(&self) -> TargetCast;
+}
+
+impl<P: AsPointer + Any> PointerTarget for &Target<P> {
+    fn cast(&self) -> TargetCast {
+        TargetCast::pointer::<P>(ElementCast::opaque())
+    }
+}
+
+#[doc(hidden)]
+pub trait OpaqueTarget {
+    fn cast(&self) -> TargetCast;
+}
+
+impl<T: ?Sized> OpaqueTarget for Target<T> {
+    fn cast(&self) -> TargetCast {
+        TargetCast::opaque()
+    }
+}
+
+/// An immutable reference to a field which is a pointer, along with the
+/// means to view its target as a reflected value.
+#[derive(Clone, Copy)]
+pub struct PointerRef<'a> {
+    pointer: &'a dyn ReflectPointer,
+    cast: TargetCast,
+}
+
+impl<'a> PointerRef<'a> {
+    /// Creates a reference to a pointer whose target is viewed by `cast`.
+    pub fn new<P: AsPointer>(pointer: &'a P, cast: TargetCast) -> Self {
+        Self { pointer, cast }
+    }
+
+    /// The type-erased pointer.
+    pub fn pointer(&self) -> &'a dyn ReflectPointer {
+        self.pointer
+    }
+
+    /// Views the target of the pointer as a reflected value.
+    pub fn cast(&self) -> TargetCast {
+        self.cast
+    }
+
+    /// Gets the target of a pointer. Returns `None` for cells and locks.
+    pub fn get(&self) -> Option<&'a dyn Any> {
+        self.pointer.get()
+    }
+
+    /// Borrows (or locks) the target for reading.
+    pub fn lock(&self) -> PointerResult<PointerGuard<'a>> {
+        Ok(self.pointer.lock()?.with_cast(self.cast))
+    }
+
+    /// Borrows (or locks) the target for writing.
+    pub fn lock_mut(&self) -> PointerResult<PointerGuardMut<'a>> {
+        Ok(self.pointer.lock_mut()?.with_cast(self.cast))
+    }
+
+    /// Gets the target as a reflected value, passing through a target which
+    /// is itself a pointer. Returns `None` if the target can not be reflected.
+    pub fn get_nested(&self) -> PointerResult<Option<&'a dyn Reflect>> {
+        let target = self.get().ok_or(PointerError::Guarded)?;
+        match self.cast.as_pointer(target) {
+            Some(pointer) => pointer.get_nested(),
+            None => Ok(self.cast.as_reflect(target)),
+        }
+    }
+}
+
+impl Debug for PointerRef<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("PointerRef")
+            .field("target_type", &self.pointer.target_type_name())
+            .field("cast", &self.cast)
+            .finish()
+    }
+}
+
+/// A mutable reference to a field which is a pointer, along with the means to
+/// view its target as a reflected value.
+pub struct PointerMut<'a> {
+    pointer: &'a mut dyn ReflectPointer,
+    cast: TargetCast,
+}
+
+impl<'a> PointerMut<'a> {
+    /// Creates a mutable reference to a pointer whose target is viewed by
+    /// `cast`.
+    pub fn new<P: AsPointer>(pointer: &'a mut P, cast: TargetCast) -> Self {
+        Self { pointer, cast }
+    }
+
+    /// The mutable type-erased pointer.
+    pub fn pointer(&mut self) -> &mut dyn ReflectPointer {
+        &mut *self.pointer
+    }
+
+    /// Views the target of the pointer as a reflected value.
+    pub fn cast(&self) -> TargetCast {
+        self.cast
+    }
+
+    /// Takes the mutable target. This fails for an `Rc` or `Arc` which
+    /// shares its target with other pointers.
+    pub fn into_target(self) -> PointerResult<&'a mut dyn Any> {
+        self.pointer.get_mut()
+    }
+
+    /// Takes the mutable target, or the immutable target if it is shared
+    /// with other pointers (e.g. the `RefCell` of a shared `Rc<RefCell<T>>`).
+    pub(crate) fn into_target_or_shared(
+        self,
+    ) -> PointerResult<Result<&'a mut dyn Any, &'a dyn Any>> {
+        if self.pointer.get_mut().err() == Some(PointerError::Shared) {
+            let pointer: &'a dyn ReflectPointer = self.pointer;
+            return pointer.get().map(Err).ok_or(PointerError::Shared);
+        }
+
+        self.pointer.get_mut().map(Ok)
+    }
+
+    /// Takes the target as a mutable reflected value, passing through a
+    /// target which is itself a pointer. Returns `None` if the target can not
+    /// be reflected.
+    pub fn into_nested(self) -> PointerResult<Option<&'a mut dyn Reflect>> {
+        let cast = self.cast;
+        let target = self.into_target()?;
+        if cast.is_pointer() {
+            match cast.as_pointer_mut(target) {
+                Some(pointer) => pointer.into_nested(),
+                None => Ok(None),
+            }
+        } else {
+            Ok(cast.as_reflect_mut(target))
+        }
+    }
+}
+
+impl Debug for PointerMut<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("PointerMut")
+            .field("target_type", &self.pointer.target_type_name())
+            .field("cast", &self.cast)
+            .finish()
+    }
+}
@@ -0,0 +1,40 @@
+use core::fmt::Display;
+use core::fmt::Formatter;
+use core::fmt::Result as FmtResult;
+
+/// The result of accessing the target of a pointer through reflection.
+pub type PointerResult<T> = Result<T, PointerError>;
+
+/// An error that occurred while accessing the target of a pointer.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PointerError {
+    /// The value is inside a cell or a lock, so it can only be read through a
+    /// guard.
+    Guarded,
+    /// The target of a pointer is not inside a cell or a lock, so it can only
+    /// be modified through a mutable reference to the pointer.
+    NotGuarded,
+    /// The target is shared with other pointers (or was borrowed immutably),
+    /// so it can not be modified.
+    Shared,
+    /// The value inside a `RefCell` is already borrowed in a conflicting way.
+    Borrowed,
+    /// A thread panicked while holding the lock, so the value may be in an
+    /// inconsistent state.
+    Poisoned,
+}
+
+impl Display for PointerError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            PointerError::Guarded => write!(f, "The value can only be accessed through a guard."),
+            PointerError::NotGuarded => write!(
+                f,
+                "The value is not inside a cell or a lock, so it can not be borrowed for writing."
+            ),
+            PointerError::Shared => write!(f, "The value is shared, so it can not be modified."),
+            PointerError::Borrowed => write!(f, "The value is already borrowed."),
+            PointerError::Poisoned => write!(f, "The lock of the value is poisoned."),
+        }
+    }
+}
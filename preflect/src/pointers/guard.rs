@@ -0,0 +1,225 @@
+use crate::fields::Reflect;
+use crate::pointers::PointerMut;
+use crate::pointers::PointerRef;
+use crate::pointers::TargetCast;
+use alloc::boxed::Box;
+use core::any::Any;
+use core::cell::Cell;
+use core::cell::Ref;
+use core::cell::RefMut;
+use core::fmt::Debug;
+use core::fmt::Formatter;
+use core::fmt::Result as FmtResult;
+#[cfg(feature = "std")]
+use std::sync::MutexGuard;
+
+/// Gives immutable access to the target of a pointer. The value inside a cell
+/// or lock stays borrowed (or locked) until the guard is dropped.
+pub struct PointerGuard<'a> {
+    guard: Box<dyn ReadGuard + 'a>,
+    cast: TargetCast,
+}
+
+impl<'a> PointerGuard<'a> {
+    pub(crate) fn new(guard: impl ReadGuard + 'a) -> Self {
+        Self {
+            guard: Box::new(guard),
+            cast: TargetCast::opaque(),
+        }
+    }
+
+    pub(crate) fn with_cast(self, cast: TargetCast) -> Self {
+        Self { cast, ..self }
+    }
+
+    /// The type-erased target.
+    pub fn value(&self) -> &dyn Any {
+        self.guard.value()
+    }
+
+    /// Views the target as a reflected value.
+    pub fn cast(&self) -> TargetCast {
+        self.cast
+    }
+
+    /// The target if it is of type `T`.
+    pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+        self.value().downcast_ref()
+    }
+
+    /// The target as a reflected value, if its type can be reflected.
+    pub fn as_reflect(&self) -> Option<&dyn Reflect> {
+        self.cast.as_reflect(self.value())
+    }
+
+    /// The target as a pointer, if it is itself a pointer (e.g. the `RefCell`
+    /// of an `Rc<RefCell<T>>`).
+    pub fn as_pointer(&self) -> Option<PointerRef<'_>> {
+        self.cast.as_pointer(self.value())
+    }
+}
+
+impl Debug for PointerGuard<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("PointerGuard")
+            .field("cast", &self.cast)
+            .finish()
+    }
+}
+
+/// Gives mutable access to the target of a pointer. The value inside a cell
+/// or lock stays borrowed (or locked) until the guard is dropped, and the
+/// value of a `Cell` is written back when the guard is dropped.
+pub struct PointerGuardMut<'a> {
+    guard: Box<dyn WriteGuard + 'a>,
+    cast: TargetCast,
+}
+
+impl<'a> PointerGuardMut<'a> {
+    pub(crate) fn new(guard: impl WriteGuard + 'a) -> Self {
+        Self {
+            guard: Box::new(guard),
+            cast: TargetCast::opaque(),
+        }
+    }
+
+    pub(crate) fn with_cast(self, cast: TargetCast) -> Self {
+        Self { cast, ..self }
+    }
+
+    /// The type-erased target.
+    pub fn value(&self) -> &dyn Any {
+        self.guard.value()
+    }
+
+    /// Views the target as a reflected value.
+    pub fn cast(&self) -> TargetCast {
+        self.cast
+    }
+
+    /// The mutable type-erased target.
+    pub fn value_mut(&mut self) -> &mut dyn Any {
+        self.guard.value_mut()
+    }
+
+    /// The target if it is of type `T`.
+    pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+        self.value().downcast_ref()
+    }
+
+    /// The mutable target if it is of type `T`.
+    pub fn downcast_mut<T: Any>(&mut self) -> Option<&mut T> {
+        self.value_mut().downcast_mut()
+    }
+
+    /// The target as a reflected value, if its type can be reflected.
+    pub fn as_reflect(&self) -> Option<&dyn Reflect> {
+        self.cast.as_reflect(self.value())
+    }
+
+    /// The target as a mutable reflected value, if its type can be reflected.
+    pub fn as_reflect_mut(&mut self) -> Option<&mut dyn Reflect> {
+        let cast = self.cast;
+        cast.as_reflect_mut(self.value_mut())
+    }
+
+    /// The target as a pointer, if it is itself a pointer.
+    pub fn as_pointer(&self) -> Option<PointerRef<'_>> {
+        self.cast.as_pointer(self.value())
+    }
+
+    /// The target as a mutable pointer, if it is itself a pointer.
+    pub fn as_pointer_mut(&mut self) -> Option<PointerMut<'_>> {
+        let cast = self.cast;
+        cast.as_pointer_mut(self.value_mut())
+    }
+}
+
+impl Debug for PointerGuardMut<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("PointerGuardMut")
+            .field("cast", &self.cast)
+            .finish()
+    }
+}
+
+/// A guard (or plain reference) which gives immutable access to a value.
+pub(crate) trait ReadGuard {
+    fn value(&self) -> &dyn Any;
+}
+
+/// A guard which gives mutable access to a value.
+pub(crate) trait WriteGuard: ReadGuard {
+    fn value_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<T: Any> ReadGuard for &T {
+    fn value(&self) -> &dyn Any {
+        *self
+    }
+}
+
+impl<T: Any> ReadGuard for Ref<'_, T> {
+    fn value(&self) -> &dyn Any {
+        &**self
+    }
+}
+
+impl<T: Any> ReadGuard for RefMut<'_, T> {
+    fn value(&self) -> &dyn Any {
+        &**self
+    }
+}
+
+impl<T: Any> WriteGuard for RefMut<'_, T> {
+    fn value_mut(&mut self) -> &mut dyn Any {
+        &mut **self
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Any> ReadGuard for MutexGuard<'_, T> {
+    fn value(&self) -> &dyn Any {
+        &**self
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Any> WriteGuard for MutexGuard<'_, T> {
+    fn value_mut(&mut self) -> &mut dyn Any {
+        &mut **self
+    }
+}
+
+/// A copy of the value of a `Cell`.
+pub(crate) struct CellCopy<T>(pub(crate) T);
+
+impl<T: Any> ReadGuard for CellCopy<T> {
+    fn value(&self) -> &dyn Any {
+        &self.0
+    }
+}
+
+/// A copy of the value of a `Cell` which is written back when it is dropped.
+pub(crate) struct CellWriteBack<'a, T: Copy> {
+    pub(crate) cell: &'a Cell<T>,
+    pub(crate) value: T,
+}
+
+impl<T: Any + Copy> ReadGuard for CellWriteBack<'_, T> {
+    fn value(&self) -> &dyn Any {
+        &self.value
+    }
+}
+
+impl<T: Any + Copy> WriteGuard for CellWriteBack<'_, T> {
+    fn value_mut(&mut self) -> &mut dyn Any {
+        &mut self.value
+    }
+}
+
+impl<T: Copy> Drop for CellWriteBack<'_, T> {
+    fn drop(&mut self) {
+        self.cell.set(self.value);
+    }
+}
@@ -0,0 +1,245 @@
+use crate::pointers::guard::CellCopy;
+use crate::pointers::guard::CellWriteBack;
+use crate::pointers::PointerError;
+use crate::pointers::PointerGuard;
+use crate::pointers::PointerGuardMut;
+use crate::pointers::PointerResult;
+use alloc::boxed::Box;
+use alloc::rc::Rc;
+use alloc::sync::Arc;
+use core::any::type_name;
+use core::any::Any;
+use core::cell::Cell;
+use core::cell::RefCell;
+#[cfg(feature = "std")]
+use std::sync::Mutex;
+
+/// A pointer (or a cell or lock) whose target is accessed as a type-erased
+/// value.
+///
+/// ```
+/// use preflect::pointers::ReflectPointer;
+/// use std::cell::RefCell;
+///
+/// let counter = RefCell::new(1u32);
+/// let erased: &dyn ReflectPointer = &counter;
+/// assert!(erased.get().is_none());
+///
+/// let mut guard = erased.lock_mut().unwrap();
+/// *guard.downcast_mut::<u32>().unwrap() += 1;
+/// assert!(erased.lock().is_err());
+/// drop(guard);
+///
+/// assert_eq!(2, counter.into_inner());
+/// ```
+pub trait ReflectPointer {
+    /// The name of the type of the target.
+    fn target_type_name(&self) -> &'static str;
+
+    /// Gets an immutable reference to the target of a pointer. Returns `None`
+    /// for cells and locks, whose values can only be read through
+    /// [`lock`](Self::lock).
+    fn get(&self) -> Option<&dyn Any>;
+
+    /// Gets a mutable reference to the target. This fails for an `Rc` or
+    /// `Arc` which shares its target with other pointers.
+    fn get_mut(&mut self) -> PointerResult<&mut dyn Any>;
+
+    /// Borrows (or locks) the target for reading.
+    fn lock(&self) -> PointerResult<PointerGuard<'_>>;
+
+    /// Borrows (or locks) the target for writing. This fails for pointers,
+    /// which can only modify their target through [`get_mut`](Self::get_mut).
+    fn lock_mut(&self) -> PointerResult<PointerGuardMut<'_>>;
+}
+
+/// A type which can be accessed as a reflected pointer. Fields of these types
+/// can be marked with `#[preflect(pointer)]`.
+pub trait AsPointer: ReflectPointer {
+    /// The type of the target.
+    type Target: Any;
+}
+
+impl<T: Any> ReflectPointer for Box<T> {
+    fn target_type_name(&self) -> &'static str {
+        type_name::<T>()
+    }
+
+    fn get(&self) -> Option<&dyn Any> {
+        Some(&**self)
+    }
+
+    fn get_mut(&mut self) -> PointerResult<&mut dyn Any> {
+        Ok(&mut **self)
+    }
+
+    fn lock(&self) -> PointerResult<PointerGuard<'_>> {
+        Ok(PointerGuard::new(&**self))
+    }
+
+    fn lock_mut(&self) -> PointerResult<PointerGuardMut<'_>> {
+        Err(PointerError::NotGuarded)
+    }
+}
+
+impl<T: Any> AsPointer for Box<T> {
+    type Target = T;
+}
+
+impl<T: Any> ReflectPointer for Rc<T> {
+    fn target_type_name(&self) -> &'static str {
+        type_name::<T>()
+    }
+
+    fn get(&self) -> Option<&dyn Any> {
+        Some(&**self)
+    }
+
+    fn get_mut(&mut self) -> PointerResult<&mut dyn Any> {
+        match Rc::get_mut(self) {
+            Some(target) => Ok(target),
+            None => Err(PointerError::Shared),
+        }
+    }
+
+    fn lock(&self) -> PointerResult<PointerGuard<'_>> {
+        Ok(PointerGuard::new(&**self))
+    }
+
+    fn lock_mut(&self) -> PointerResult<PointerGuardMut<'_>> {
+        Err(PointerError::NotGuarded)
+    }
+}
+
+impl<T: Any> AsPointer for Rc<T> {
+    type Target = T;
+}
+
+impl<T: Any> ReflectPointer for Arc<T> {
+    fn target_type_name(&self) -> &'static str {
+        type_name::<T>()
+    }
+
+    fn get(&self) -> Option<&dyn Any> {
+        Some(&**self)
+    }
+
+    fn get_mut(&mut self) -> PointerResult<&mut dyn Any> {
+        match Arc::get_mut(self) {
+            Some(target) => Ok(target),
+            None => Err(PointerError::Shared),
+        }
+    }
+
+    fn lock(&self) -> PointerResult<PointerGuard<'_>> {
+        Ok(PointerGuard::new(&**self))
+    }
+
+    fn lock_mut(&self) -> PointerResult<PointerGuardMut<'_>> {
+        Err(PointerError::NotGuarded)
+    }
+}
+
+impl<T: Any> AsPointer for Arc<T> {
+    type Target = T;
+}
+
+impl<T: Any> ReflectPointer for RefCell<T> {
+    fn target_type_name(&self) -> &'static str {
+        type_name::<T>()
+    }
+
+    fn get(&self) -> Option<&dyn Any> {
+        None
+    }
+
+    fn get_mut(&mut self) -> PointerResult<&mut dyn Any> {
+        Ok(RefCell::get_mut(self))
+    }
+
+    fn lock(&self) -> PointerResult<PointerGuard<'_>> {
+        match self.try_borrow() {
+            Ok(value) => Ok(PointerGuard::new(value)),
+            Err(_) => Err(PointerError::Borrowed),
+        }
+    }
+
+    fn lock_mut(&self) -> PointerResult<PointerGuardMut<'_>> {
+        match self.try_borrow_mut() {
+            Ok(value) => Ok(PointerGuardMut::new(value)),
+            Err(_) => Err(PointerError::Borrowed),
+        }
+    }
+}
+
+impl<T: Any> AsPointer for RefCell<T> {
+    type Target = T;
+}
+
+/// The guards of a `Cell` hold a copy of its value, which is written back when
+/// a mutable guard is dropped.
+impl<T: Any + Copy> ReflectPointer for Cell<T> {
+    fn target_type_name(&self) -> &'static str {
+        type_name::<T>()
+    }
+
+    fn get(&self) -> Option<&dyn Any> {
+        None
+    }
+
+    fn get_mut(&mut self) -> PointerResult<&mut dyn Any> {
+        Ok(Cell::get_mut(self))
+    }
+
+    fn lock(&self) -> PointerResult<PointerGuard<'_>> {
+        Ok(PointerGuard::new(CellCopy(Cell::get(self))))
+    }
+
+    fn lock_mut(&self) -> PointerResult<PointerGuardMut<'_>> {
+        Ok(PointerGuardMut::new(CellWriteBack {
+            cell: self,
+            value: Cell::get(self),
+        }))
+    }
+}
+
+impl<T: Any + Copy> AsPointer for Cell<T> {
+    type Target = T;
+}
+
+#[cfg(feature = "std")]
+impl<T: Any> ReflectPointer for Mutex<T> {
+    fn target_type_name(&self) -> &'static str {
+        type_name::<T>()
+    }
+
+    fn get(&self) -> Option<&dyn Any> {
+        None
+    }
+
+    fn get_mut(&mut self) -> PointerResult<&mut dyn Any> {
+        match Mutex::get_mut(self) {
+            Ok(value) => Ok(value),
+            Err(_) => Err(PointerError::Poisoned),
+        }
+    }
+
+    fn lock(&self) -> PointerResult<PointerGuard<'_>> {
+        match Mutex::lock(self) {
+            Ok(value) => Ok(PointerGuard::new(value)),
+            Err(_) => Err(PointerError::Poisoned),
+        }
+    }
+
+    fn lock_mut(&self) -> PointerResult<PointerGuardMut<'_>> {
+        match Mutex::lock(self) {
+            Ok(value) => Ok(PointerGuardMut::new(value)),
+            Err(_) => Err(PointerError::Poisoned),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Any> AsPointer for Mutex<T> {
+    type Target = T;
+}
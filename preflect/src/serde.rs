@@ -0,0 +1,292 @@
+//! Provides facilities for serializing and deserializing values through
+//! reflection, so that any value which implements [`Reflect`] can be used with
+//! serde without deriving `Serialize` or `Deserialize` for it.
+//!
+//! Values are serialized as structs containing their reflected fields. Fields
+//! marked with `#[preflect(ignore)]` are skipped and fields marked with
+//! `#[preflect(nested)]` are serialized recursively. When deserializing, the
+//! fields of an existing value are overwritten and fields can be named by any
+//! of their aliases. Unknown fields are skipped. Enums are serialized as the
+//! fields of their active variant.
+//!
+//! The values of fields which are not nested must be primitives (or `String`
+//! with the `alloc` feature).
+//!
+//! The functions in this module can also be used on the fields of a type which
+//! derives `Serialize` and `Deserialize` with
+//! `#[serde(with = "preflect::serde")]`.
+
+use crate::fields::errors::field_type_name;
+use crate::fields::Reflect;
+use crate::fmt::struct_name;
+use ::serde::de::DeserializeSeed;
+use ::serde::de::Error as DeError;
+use ::serde::de::IgnoredAny;
+use ::serde::de::MapAccess;
+use ::serde::de::SeqAccess;
+use ::serde::de::Visitor;
+use ::serde::ser::Error as SerError;
+use ::serde::ser::SerializeStruct;
+use ::serde::Deserialize;
+use ::serde::Deserializer;
+use ::serde::Serialize;
+use ::serde::Serializer;
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+use core::any::Any;
+use core::fmt::Formatter;
+use core::fmt::Result as FmtResult;
+use core::ptr;
+
+/// Evaluates `$on_match` with the value downcast to the first of the
+/// supported primitive types which it is (naming the type `$ty`), or evaluates
+/// to `$fallback` if it is none of them.
+macro_rules! match_primitive {
+    ($value:ident.$downcast:ident(), |$typed:ident: $ty:ident| $on_match:expr, $fallback:expr) => {
+        match_primitive!(@each $value, $downcast, $typed, $ty, $on_match, $fallback;
+            bool, char, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64, ()
+        )
+    };
+    (@each $value:ident, $downcast:ident, $typed:ident, $ty:ident, $on_match:expr, $fallback:expr; $($prim:ty),*) => {
+        $(
+            if let Some($typed) = $value.$downcast::<$prim>() {
+                type $ty = $prim;
+                $on_match
+            } else
+        )* {
+            match_alloc!($value, $downcast, $typed, $ty, $on_match, $fallback)
+        }
+    };
+}
+
+#[cfg(feature = "alloc")]
+macro_rules! match_alloc {
+    ($value:ident, $downcast:ident, $typed:ident, $ty:ident, $on_match:expr, $fallback:expr) => {
+        if let Some($typed) = $value.$downcast::<String>() {
+            type $ty = String;
+            $on_match
+        } else {
+            $fallback
+        }
+    };
+}
+
+#[cfg(not(feature = "alloc"))]
+macro_rules! match_alloc {
+    ($value:ident, $downcast:ident, $typed:ident, $ty:ident, $on_match:expr, $fallback:expr) => {
+        $fallback
+    };
+}
+
+impl Serialize for dyn Reflect {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let field_names = self.field_names();
+        let type_name = struct_name(self);
+        let mut state = serializer.serialize_struct(type_name, field_names.len())?;
+        for name in field_names {
+            if let Ok(nested) = self.get_nested_raw(name) {
+                state.serialize_field(name, nested)?;
+            } else if let Ok(value) = self.get_field_raw(name) {
+                let field = FieldValue {
+                    owner: self,
+                    name,
+                    value,
+                };
+                state.serialize_field(name, &field)?;
+            }
+        }
+
+        state.end()
+    }
+}
+
+/// The value of a field which is not nested.
+struct FieldValue<'a> {
+    owner: &'a dyn Reflect,
+    name: &'static str,
+    value: &'a dyn Any,
+}
+
+impl Serialize for FieldValue<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let value = self.value;
+        if let Some(value) = value.downcast_ref::<&'static str>() {
+            return value.serialize(serializer);
+        }
+
+        match_primitive!(
+            value.downcast_ref(),
+            |value: T| T::serialize(value, serializer),
+            Err(S::Error::custom(format_args!(
+                "The field '{}' of type '{}' can not be serialized.",
+                self.name,
+                field_type_name(self.owner, self.name).unwrap_or("unknown")
+            )))
+        )
+    }
+}
+
+/// Deserializes the fields of an existing value in place. This can be used
+/// with [`DeserializeSeed`] to deserialize values which do not implement
+/// `Default`, or whose fields should keep their values if they are missing
+/// from the input.
+pub struct ReflectSeed<'a> {
+    value: &'a mut dyn Reflect,
+}
+
+impl<'a> ReflectSeed<'a> {
+    /// Creates a seed which deserializes into the provided value.
+    pub fn new(value: &'a mut dyn Reflect) -> Self {
+        Self { value }
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for ReflectSeed<'_> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        let type_name = struct_name(self.value);
+        let field_names = self.value.field_names();
+        deserializer.deserialize_struct(type_name, field_names, self)
+    }
+}
+
+impl<'de> Visitor<'de> for ReflectSeed<'_> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "struct {}", struct_name(self.value))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        while let Some(name) = map.next_key_seed(FieldNameSeed {
+            owner: &*self.value,
+        })? {
+            match name {
+                Some(name) => map.next_value_seed(FieldSeed {
+                    owner: &mut *self.value,
+                    name,
+                })?,
+                None => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        let field_names = self.value.field_names();
+        for (index, name) in field_names.iter().enumerate() {
+            let seed = FieldSeed {
+                owner: &mut *self.value,
+                name,
+            };
+            if seq.next_element_seed(seed)?.is_none() {
+                return Err(A::Error::invalid_length(index, &self));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Resolves the name (or alias) of a field to the name it is declared with.
+/// Names which do not belong to a reflected field resolve to `None`.
+struct FieldNameSeed<'a> {
+    owner: &'a dyn Reflect,
+}
+
+impl<'de> DeserializeSeed<'de> for FieldNameSeed<'_> {
+    type Value = Option<&'static str>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_identifier(self)
+    }
+}
+
+impl<'de> Visitor<'de> for FieldNameSeed<'_> {
+    type Value = Option<&'static str>;
+
+    fn expecting(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str("the name of a field")
+    }
+
+    fn visit_str<E: DeError>(self, name: &str) -> Result<Self::Value, E> {
+        Ok(resolve_field_name(self.owner, name))
+    }
+
+    fn visit_u64<E: DeError>(self, index: u64) -> Result<Self::Value, E> {
+        let field_names = self.owner.field_names();
+        Ok(field_names.get(index as usize).copied())
+    }
+}
+
+/// Deserializes the value of a single field in place.
+struct FieldSeed<'a> {
+    owner: &'a mut dyn Reflect,
+    name: &'static str,
+}
+
+impl<'de> DeserializeSeed<'de> for FieldSeed<'_> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        if self.owner.get_nested_raw(self.name).is_ok() {
+            let nested = self
+                .owner
+                .get_nested_mut_raw(self.name)
+                .map_err(D::Error::custom)?;
+            return ReflectSeed::new(nested).deserialize(deserializer);
+        }
+
+        let name = self.name;
+        let type_name = field_type_name(self.owner, name).unwrap_or("unknown");
+        let value = self
+            .owner
+            .get_field_mut_raw(name)
+            .map_err(D::Error::custom)?;
+        match_primitive!(
+            value.downcast_mut(),
+            |value: T| {
+                *value = T::deserialize(deserializer)?;
+                Ok(())
+            },
+            Err(D::Error::custom(format_args!(
+                "The field '{}' of type '{}' can not be deserialized.",
+                name, type_name
+            )))
+        )
+    }
+}
+
+/// Serializes a value through reflection. This can be used with
+/// `#[serde(serialize_with = "preflect::serde::serialize")]`.
+pub fn serialize<T: Reflect, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+    value.as_reflect().serialize(serializer)
+}
+
+/// Deserializes a value through reflection, starting from its default value.
+/// This can be used with
+/// `#[serde(deserialize_with = "preflect::serde::deserialize")]`.
+pub fn deserialize<'de, T: Reflect + Default, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<T, D::Error> {
+    let mut value = T::default();
+    ReflectSeed::new(&mut value).deserialize(deserializer)?;
+    Ok(value)
+}
+
+/// Finds the name a field is declared with using its name or one of its
+/// aliases. The field which an alias refers to is found by comparing the
+/// references to the fields.
+fn resolve_field_name(owner: &dyn Reflect, name: &str) -> Option<&'static str> {
+    let field = owner.get_field_raw(name).ok()?;
+    let type_id = <dyn Any>::type_id(field);
+    owner.field_names().iter().copied().find(|candidate| {
+        owner
+            .get_field_raw(candidate)
+            .is_ok_and(|other| ptr::addr_eq(other, field) && <dyn Any>::type_id(other) == type_id)
+    })
+}
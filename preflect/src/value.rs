@@ -0,0 +1,15 @@
+//! Provides a type-erased [`Value`] tree which reflected values can be
+//! converted to and from. It can be used as a common format for tooling which
+//! inspects or modifies values without knowing their types.
+
+#![cfg(feature = "alloc")]
+
+mod convert;
+mod errors;
+mod reflect;
+mod tree;
+
+pub use self::convert::*;
+pub use self::errors::*;
+pub use self::reflect::*;
+pub use self::tree::*;
@@ -0,0 +1,230 @@
+use crate::value::Value;
+use crate::value::ValueError;
+use crate::value::ValueKind;
+use crate::value::ValueResult;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use core::any::type_name;
+use core::convert::TryFrom;
+
+/// A type whose values can be converted to a [`Value`].
+pub trait ToValue {
+    /// Converts the value to a `Value`.
+    fn to_value(&self) -> Value;
+}
+
+/// A type whose values can be created from a [`Value`].
+pub trait FromValue: Sized {
+    /// Creates a value from a `Value` of the matching shape.
+    fn from_value(value: &Value) -> ValueResult<Self>;
+}
+
+impl ToValue for Value {
+    fn to_value(&self) -> Value {
+        self.clone()
+    }
+}
+
+impl FromValue for Value {
+    fn from_value(value: &Value) -> ValueResult<Self> {
+        Ok(value.clone())
+    }
+}
+
+impl ToValue for () {
+    fn to_value(&self) -> Value {
+        Value::Null
+    }
+}
+
+impl FromValue for () {
+    fn from_value(value: &Value) -> ValueResult<Self> {
+        match value {
+            Value::Null => Ok(()),
+            other => Err(ValueError::mismatch(ValueKind::Null, other.kind())),
+        }
+    }
+}
+
+impl ToValue for bool {
+    fn to_value(&self) -> Value {
+        Value::Bool(*self)
+    }
+}
+
+impl FromValue for bool {
+    fn from_value(value: &Value) -> ValueResult<Self> {
+        match value {
+            Value::Bool(value) => Ok(*value),
+            other => Err(ValueError::mismatch(ValueKind::Bool, other.kind())),
+        }
+    }
+}
+
+/// Implements the conversions for integers, which are converted to `$variant`
+/// but can be created from any integer which is in range.
+macro_rules! impl_int_value {
+    ($variant:ident as $wide:ty: $($ty:ty),*) => {
+        $(
+            impl ToValue for $ty {
+                fn to_value(&self) -> Value {
+                    Value::$variant(*self as $wide)
+                }
+            }
+
+            impl FromValue for $ty {
+                fn from_value(value: &Value) -> ValueResult<Self> {
+                    let out_of_range = || ValueError::OutOfRange {
+                        type_name: type_name::<$ty>(),
+                    };
+
+                    match value {
+                        Value::Int(value) => <$ty>::try_from(*value).map_err(|_| out_of_range()),
+                        Value::UInt(value) => <$ty>::try_from(*value).map_err(|_| out_of_range()),
+                        other => Err(ValueError::mismatch(ValueKind::$variant, other.kind())),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_int_value!(Int as i64: i8, i16, i32, i64, isize);
+impl_int_value!(UInt as u64: u8, u16, u32, u64, usize);
+
+/// Implements the conversions for floats, which can also be created from
+/// integers.
+macro_rules! impl_float_value {
+    ($($ty:ty),*) => {
+        $(
+            impl ToValue for $ty {
+                fn to_value(&self) -> Value {
+                    Value::Float(*self as f64)
+                }
+            }
+
+            impl FromValue for $ty {
+                fn from_value(value: &Value) -> ValueResult<Self> {
+                    match value {
+                        Value::Float(value) => Ok(*value as $ty),
+                        Value::Int(value) => Ok(*value as $ty),
+                        Value::UInt(value) => Ok(*value as $ty),
+                        other => Err(ValueError::mismatch(ValueKind::Float, other.kind())),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_float_value!(f32, f64);
+
+impl ToValue for char {
+    fn to_value(&self) -> Value {
+        Value::String(self.to_string())
+    }
+}
+
+impl FromValue for char {
+    fn from_value(value: &Value) -> ValueResult<Self> {
+        match value {
+            Value::String(value) => {
+                let mut chars = value.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => Ok(c),
+                    _ => Err(ValueError::OutOfRange {
+                        type_name: type_name::<char>(),
+                    }),
+                }
+            }
+            other => Err(ValueError::mismatch(ValueKind::String, other.kind())),
+        }
+    }
+}
+
+impl ToValue for String {
+    fn to_value(&self) -> Value {
+        Value::String(self.clone())
+    }
+}
+
+impl FromValue for String {
+    fn from_value(value: &Value) -> ValueResult<Self> {
+        match value {
+            Value::String(value) => Ok(value.clone()),
+            other => Err(ValueError::mismatch(ValueKind::String, other.kind())),
+        }
+    }
+}
+
+impl ToValue for &str {
+    fn to_value(&self) -> Value {
+        Value::String((*self).into())
+    }
+}
+
+impl<T: ToValue> ToValue for Option<T> {
+    fn to_value(&self) -> Value {
+        match self {
+            Some(value) => value.to_value(),
+            None => Value::Null,
+        }
+    }
+}
+
+impl<T: FromValue> FromValue for Option<T> {
+    fn from_value(value: &Value) -> ValueResult<Self> {
+        match value {
+            Value::Null => Ok(None),
+            value => T::from_value(value).map(Some),
+        }
+    }
+}
+
+impl<T: ToValue> ToValue for Vec<T> {
+    fn to_value(&self) -> Value {
+        Value::List(self.iter().map(ToValue::to_value).collect())
+    }
+}
+
+impl<T: FromValue> FromValue for Vec<T> {
+    fn from_value(value: &Value) -> ValueResult<Self> {
+        match value {
+            Value::List(elements) => elements
+                .iter()
+                .enumerate()
+                .map(|(index, element)| {
+                    T::from_value(element).map_err(|error| error.in_element(index))
+                })
+                .collect(),
+            other => Err(ValueError::mismatch(ValueKind::List, other.kind())),
+        }
+    }
+}
+
+impl<T: ToValue> ToValue for BTreeMap<String, T> {
+    fn to_value(&self) -> Value {
+        let entries = self
+            .iter()
+            .map(|(key, value)| (key.clone(), value.to_value()));
+        Value::Map(entries.collect())
+    }
+}
+
+impl<T: FromValue> FromValue for BTreeMap<String, T> {
+    fn from_value(value: &Value) -> ValueResult<Self> {
+        match value {
+            Value::Map(entries) => entries
+                .iter()
+                .map(|(key, value)| {
+                    T::from_value(value)
+                        .map(|value| (key.clone(), value))
+                        .map_err(|error| error.in_field(key.as_str()))
+                })
+                .collect(),
+            other => Err(ValueError::mismatch(ValueKind::Map, other.kind())),
+        }
+    }
+}
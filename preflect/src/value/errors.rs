@@ -0,0 +1,158 @@
+use crate::value::ValueKind;
+use alloc::boxed::Box;
+use alloc::string::String;
+use core::fmt::Display;
+use core::fmt::Formatter;
+use core::fmt::Result as FmtResult;
+
+/// The result of converting to or from a [`Value`](crate::value::Value).
+pub type ValueResult<T> = Result<T, ValueError>;
+
+/// An error that occurred while converting to or from a
+/// [`Value`](crate::value::Value) because the shape of the value did not match
+/// the type.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ValueError {
+    /// The value was of a different shape than the type requires.
+    TypeMismatch {
+        /// The shape the type requires.
+        expected: ValueKind,
+        /// The shape of the value.
+        found: ValueKind,
+    },
+    /// The value could not be represented by the type (e.g. an integer which
+    /// is too large).
+    OutOfRange {
+        /// The name of the type.
+        type_name: &'static str,
+    },
+    /// The type has no conversion to or from a value.
+    Unsupported {
+        /// The name of the type.
+        type_name: &'static str,
+    },
+    /// The value of a struct did not contain one of the fields of the type.
+    MissingField {
+        /// The name of the field.
+        name: &'static str,
+    },
+    /// The value of a struct contained a field which the type does not have.
+    UnknownField {
+        /// The name of the field.
+        name: String,
+    },
+    /// The value of a struct contained a field more than once (e.g. under its
+    /// name and one of its aliases).
+    DuplicateField {
+        /// The name of the field.
+        name: &'static str,
+    },
+    /// The value of a field (or an entry of a map) could not be converted.
+    Field {
+        /// The name of the field or the key of the entry.
+        name: String,
+        /// The reason the value could not be converted.
+        error: Box<ValueError>,
+    },
+    /// An element of a list could not be converted.
+    Element {
+        /// The position of the element within the list.
+        index: usize,
+        /// The reason the element could not be converted.
+        error: Box<ValueError>,
+    },
+}
+
+impl ValueError {
+    /// Creates an error for a value of the wrong shape.
+    pub fn mismatch(expected: ValueKind, found: ValueKind) -> Self {
+        Self::TypeMismatch { expected, found }
+    }
+
+    /// Wraps the error of the value of a field.
+    pub fn in_field(self, name: impl Into<String>) -> Self {
+        Self::Field {
+            name: name.into(),
+            error: Box::new(self),
+        }
+    }
+
+    /// Wraps the error of an element of a list.
+    pub fn in_element(self, index: usize) -> Self {
+        Self::Element {
+            index,
+            error: Box::new(self),
+        }
+    }
+
+    /// Gets the error which caused the conversion to fail, without the fields
+    /// and elements which lead to it.
+    pub fn root_cause(&self) -> &ValueError {
+        match self {
+            ValueError::Field { error, .. } | ValueError::Element { error, .. } => {
+                error.root_cause()
+            }
+            _ => self,
+        }
+    }
+}
+
+impl Display for ValueError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        if let ValueError::Field { .. } | ValueError::Element { .. } = self {
+            f.write_str("At '")?;
+            let mut error = self;
+            let mut is_first = true;
+            loop {
+                match error {
+                    ValueError::Field { name, error: inner } => {
+                        if !is_first {
+                            f.write_str(".")?;
+                        }
+
+                        f.write_str(name)?;
+                        error = inner;
+                    }
+                    ValueError::Element {
+                        index,
+                        error: inner,
+                    } => {
+                        write!(f, "[{}]", index)?;
+                        error = inner;
+                    }
+                    _ => break,
+                }
+
+                is_first = false;
+            }
+
+            f.write_str("': ")?;
+        }
+
+        match self.root_cause() {
+            ValueError::TypeMismatch { expected, found } => {
+                write!(f, "Expected {} but found {}.", expected, found)
+            }
+            ValueError::OutOfRange { type_name } => {
+                write!(f, "The value can not be represented by '{}'.", type_name)
+            }
+            ValueError::Unsupported { type_name } => {
+                write!(
+                    f,
+                    "The type '{}' can not be converted to or from a value.",
+                    type_name
+                )
+            }
+            ValueError::MissingField { name } => {
+                write!(f, "The field '{}' is missing from the value.", name)
+            }
+            ValueError::UnknownField { name } => {
+                write!(f, "The field '{}' does not exist for the type.", name)
+            }
+            ValueError::DuplicateField { name } => {
+                write!(f, "The field '{}' is given more than once.", name)
+            }
+            ValueError::Field { .. } | ValueError::Element { .. } => Ok(()),
+        }
+    }
+}
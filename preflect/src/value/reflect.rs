@@ -0,0 +1,309 @@
+use crate::fields::errors::field_type_name;
+use crate::fields::FieldInfo;
+use crate::fields::Reflect;
+use crate::fmt::struct_name;
+use crate::value::FromValue;
+use crate::value::StructValue;
+use crate::value::ToValue;
+use crate::value::Value;
+use crate::value::ValueError;
+use crate::value::ValueKind;
+use crate::value::ValueResult;
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::any::type_name;
+use core::any::Any;
+use core::any::TypeId;
+use core::fmt::Debug;
+use core::fmt::Formatter;
+use core::fmt::Result as FmtResult;
+
+/// Converts the values of a single type to and from a [`Value`] when they are
+/// the values of reflected fields.
+#[derive(Clone, Copy)]
+pub struct ValueConverter {
+    type_id: fn() -> TypeId,
+    to_value: fn(&dyn Any) -> Value,
+    check: fn(&Value) -> ValueResult<()>,
+    from_value: fn(&Value, &mut dyn Any) -> ValueResult<()>,
+}
+
+impl ValueConverter {
+    /// Creates a converter for values of type `T`.
+    pub const fn of<T: ToValue + FromValue + 'static>() -> Self {
+        Self {
+            type_id: TypeId::of::<T>,
+            to_value: erased_to_value::<T>,
+            check: erased_check::<T>,
+            from_value: erased_from_value::<T>,
+        }
+    }
+
+    /// The [`TypeId`] of the values which are converted.
+    pub fn type_id(&self) -> TypeId {
+        (self.type_id)()
+    }
+
+    /// Converts a value of the type to a [`Value`].
+    pub fn to_value(&self, value: &dyn Any) -> Value {
+        (self.to_value)(value)
+    }
+
+    /// Ensures that a value of the type can be created from a [`Value`].
+    pub fn check(&self, value: &Value) -> ValueResult<()> {
+        (self.check)(value)
+    }
+
+    /// Overwrites a value of the type with one created from a [`Value`]. The
+    /// target is left unchanged if the conversion fails.
+    pub fn from_value(&self, value: &Value, target: &mut dyn Any) -> ValueResult<()> {
+        (self.from_value)(value, target)
+    }
+}
+
+impl Debug for ValueConverter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("ValueConverter")
+            .field("type_id", &self.type_id())
+            .finish()
+    }
+}
+
+/// The converters which are used for the values of fields. A converter is
+/// looked up by the `TypeId` of a value, starting with the registered
+/// converters and falling back to those for the primitive types, `String` and
+/// `Value`. Containers such as `Vec<T>` and `Option<T>` must be registered.
+///
+/// Fields which can themselves be reflected (those marked with
+/// `#[preflect(nested)]`) are converted to and from [`Value::Struct`]. A
+/// [`Value::Map`] (e.g. an object which was parsed from JSON) is accepted
+/// wherever a struct is expected.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Converters<'c> {
+    converters: &'c [ValueConverter],
+}
+
+impl<'c> Converters<'c> {
+    /// Registers the provided converters. Earlier converters take precedence
+    /// over later converters for the same type.
+    pub const fn new(converters: &'c [ValueConverter]) -> Self {
+        Self { converters }
+    }
+
+    /// Finds the converter for values with the provided `TypeId`.
+    pub fn find(&self, type_id: TypeId) -> Option<&'c ValueConverter> {
+        self.converters
+            .iter()
+            .chain(DEFAULT_CONVERTERS)
+            .find(|converter| ValueConverter::type_id(converter) == type_id)
+    }
+
+    /// Converts a reflected value to a [`Value::Struct`] containing its
+    /// reflected fields. Enums are converted to the fields of their active
+    /// variant.
+    pub fn to_value(&self, value: &dyn Reflect) -> ValueResult<Value> {
+        let mut struct_value = StructValue::new(struct_name(value));
+        for name in value.field_names() {
+            let field_value = if let Ok(nested) = value.get_nested_raw(name) {
+                self.to_value(nested)
+            } else {
+                let field = value.get_field_raw(name).map_err(|_| missing(name))?;
+                match self.find(<dyn Any>::type_id(field)) {
+                    Some(converter) => Ok((converter.to_value)(field)),
+                    None => Err(unsupported(value, name)),
+                }
+            };
+
+            struct_value.set(*name, field_value.map_err(|error| error.in_field(*name))?);
+        }
+
+        Ok(Value::Struct(struct_value))
+    }
+
+    /// Overwrites the reflected fields of a value with the fields of a
+    /// [`Value::Struct`]. The struct must contain every reflected field, under
+    /// its name or one of its aliases, and no other fields. Nothing is written
+    /// if the value does not match.
+    pub fn apply(&self, value: &Value, target: &mut dyn Reflect) -> ValueResult<()> {
+        self.check(value, target)?;
+        self.write(value, target)
+    }
+
+    /// Creates a reflected value from a [`Value::Struct`], starting from its
+    /// default value.
+    pub fn from_value<T: Reflect + Default>(&self, value: &Value) -> ValueResult<T> {
+        let mut target = T::default();
+        self.apply(value, &mut target)?;
+        Ok(target)
+    }
+
+    /// Ensures that the value matches the shape of the target before anything
+    /// is written to it.
+    fn check(&self, value: &Value, target: &dyn Reflect) -> ValueResult<()> {
+        let struct_value = as_struct(value)?;
+        let mut given = Vec::new();
+        for (name, _) in struct_value.fields() {
+            let field_name = match resolve_name(target, name) {
+                Some(field_name) => field_name,
+                None => return Err(ValueError::UnknownField { name: name.into() }),
+            };
+
+            if given.contains(&field_name) {
+                return Err(ValueError::DuplicateField { name: field_name });
+            }
+
+            given.push(field_name);
+        }
+
+        for name in target.field_names() {
+            let field_value = get_field_value(&struct_value, target, name)?;
+            let result = if let Ok(nested) = target.get_nested_raw(name) {
+                self.check(field_value, nested)
+            } else {
+                let field = target.get_field_raw(name).map_err(|_| missing(name))?;
+                match self.find(<dyn Any>::type_id(field)) {
+                    Some(converter) => (converter.check)(field_value),
+                    None => Err(unsupported(target, name)),
+                }
+            };
+
+            result.map_err(|error| error.in_field(*name))?;
+        }
+
+        Ok(())
+    }
+
+    fn write(&self, value: &Value, target: &mut dyn Reflect) -> ValueResult<()> {
+        let struct_value = as_struct(value)?;
+        for name in target.field_names() {
+            let field_value = get_field_value(&struct_value, target, name)?;
+            let result = if target.get_nested_raw(name).is_ok() {
+                let nested = target.get_nested_mut_raw(name).map_err(|_| missing(name))?;
+                self.write(field_value, nested)
+            } else {
+                let type_name = field_type_name(target, name).unwrap_or("unknown");
+                let field = target.get_field_mut_raw(name).map_err(|_| missing(name))?;
+                match self.find(<dyn Any>::type_id(field)) {
+                    Some(converter) => (converter.from_value)(field_value, field),
+                    None => Err(ValueError::Unsupported { type_name }),
+                }
+            };
+
+            result.map_err(|error| error.in_field(*name))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Value {
+    /// Converts a reflected value to a [`Value::Struct`] using the default
+    /// converters.
+    pub fn from_reflect(value: &dyn Reflect) -> ValueResult<Value> {
+        Converters::default().to_value(value)
+    }
+
+    /// Overwrites the reflected fields of a value using the default
+    /// converters.
+    pub fn apply_to(&self, target: &mut dyn Reflect) -> ValueResult<()> {
+        Converters::default().apply(self, target)
+    }
+
+    /// Creates a reflected value from the value using the default converters.
+    pub fn to_reflect<T: Reflect + Default>(&self) -> ValueResult<T> {
+        Converters::default().from_value(self)
+    }
+}
+
+/// The converters used for the primitive types, `String` and `Value`.
+const DEFAULT_CONVERTERS: &[ValueConverter] = &[
+    ValueConverter::of::<()>(),
+    ValueConverter::of::<bool>(),
+    ValueConverter::of::<char>(),
+    ValueConverter::of::<i8>(),
+    ValueConverter::of::<i16>(),
+    ValueConverter::of::<i32>(),
+    ValueConverter::of::<i64>(),
+    ValueConverter::of::<isize>(),
+    ValueConverter::of::<u8>(),
+    ValueConverter::of::<u16>(),
+    ValueConverter::of::<u32>(),
+    ValueConverter::of::<u64>(),
+    ValueConverter::of::<usize>(),
+    ValueConverter::of::<f32>(),
+    ValueConverter::of::<f64>(),
+    ValueConverter::of::<String>(),
+    ValueConverter::of::<Value>(),
+];
+
+fn erased_to_value<T: ToValue + 'static>(value: &dyn Any) -> Value {
+    value
+        .downcast_ref::<T>()
+        .map_or(Value::Null, ToValue::to_value)
+}
+
+fn erased_from_value<T: FromValue + 'static>(
+    value: &Value,
+    target: &mut dyn Any,
+) -> ValueResult<()> {
+    match target.downcast_mut::<T>() {
+        Some(target) => {
+            *target = T::from_value(value)?;
+            Ok(())
+        }
+        None => Err(ValueError::Unsupported {
+            type_name: type_name::<T>(),
+        }),
+    }
+}
+
+fn erased_check<T: FromValue>(value: &Value) -> ValueResult<()> {
+    T::from_value(value).map(|_| ())
+}
+
+fn as_struct(value: &Value) -> ValueResult<Cow<'_, StructValue>> {
+    match value {
+        Value::Struct(struct_value) => Ok(Cow::Borrowed(struct_value)),
+        Value::Map(entries) => {
+            let mut struct_value = StructValue::default();
+            for (name, value) in entries {
+                struct_value.set(name.as_str(), value.clone());
+            }
+
+            Ok(Cow::Owned(struct_value))
+        }
+        other => Err(ValueError::mismatch(ValueKind::Struct, other.kind())),
+    }
+}
+
+/// Finds the name of the field which `name` refers to, using the same lookup
+/// as [`BaseHasFields`](crate::fields::BaseHasFields) so that aliases are
+/// accepted.
+fn resolve_name(target: &dyn Reflect, name: &str) -> Option<&'static str> {
+    target.get_field_info(name).ok().map(FieldInfo::name)
+}
+
+/// Gets the value of a field from the value of a struct, which may contain it
+/// under its name or one of its aliases.
+fn get_field_value<'v>(
+    struct_value: &'v StructValue,
+    target: &dyn Reflect,
+    name: &'static str,
+) -> ValueResult<&'v Value> {
+    struct_value
+        .fields()
+        .find(|(field_name, _)| resolve_name(target, field_name) == Some(name))
+        .map(|(_, value)| value)
+        .ok_or_else(|| missing(name))
+}
+
+fn missing(name: &'static str) -> ValueError {
+    ValueError::MissingField { name }
+}
+
+fn unsupported(owner: &dyn Reflect, name: &str) -> ValueError {
+    ValueError::Unsupported {
+        type_name: field_type_name(owner, name).unwrap_or("unknown"),
+    }
+}
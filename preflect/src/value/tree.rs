@@ -0,0 +1,173 @@
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Display;
+use core::fmt::Formatter;
+use core::fmt::Result as FmtResult;
+
+/// A type-erased value. Reflected values are converted to [`Value::Struct`],
+/// while the values of their fields are converted to the variant which matches
+/// their shape.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    /// The absence of a value (e.g. `None` or `()`).
+    Null,
+    /// A boolean.
+    Bool(bool),
+    /// A signed integer.
+    Int(i64),
+    /// An unsigned integer.
+    UInt(u64),
+    /// A floating point number.
+    Float(f64),
+    /// A string (or a `char`).
+    String(String),
+    /// An ordered list of values.
+    List(Vec<Value>),
+    /// A map from strings to values.
+    Map(BTreeMap<String, Value>),
+    /// A struct with named fields.
+    Struct(StructValue),
+}
+
+impl Value {
+    /// The shape of the value.
+    pub fn kind(&self) -> ValueKind {
+        match self {
+            Value::Null => ValueKind::Null,
+            Value::Bool(_) => ValueKind::Bool,
+            Value::Int(_) => ValueKind::Int,
+            Value::UInt(_) => ValueKind::UInt,
+            Value::Float(_) => ValueKind::Float,
+            Value::String(_) => ValueKind::String,
+            Value::List(_) => ValueKind::List,
+            Value::Map(_) => ValueKind::Map,
+            Value::Struct(_) => ValueKind::Struct,
+        }
+    }
+}
+
+/// The shape of a [`Value`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ValueKind {
+    /// The shape of [`Value::Null`].
+    Null,
+    /// The shape of [`Value::Bool`].
+    Bool,
+    /// The shape of [`Value::Int`].
+    Int,
+    /// The shape of [`Value::UInt`].
+    UInt,
+    /// The shape of [`Value::Float`].
+    Float,
+    /// The shape of [`Value::String`].
+    String,
+    /// The shape of [`Value::List`].
+    List,
+    /// The shape of [`Value::Map`].
+    Map,
+    /// The shape of [`Value::Struct`].
+    Struct,
+}
+
+impl Display for ValueKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let name = match self {
+            ValueKind::Null => "null",
+            ValueKind::Bool => "a bool",
+            ValueKind::Int => "a signed integer",
+            ValueKind::UInt => "an unsigned integer",
+            ValueKind::Float => "a float",
+            ValueKind::String => "a string",
+            ValueKind::List => "a list",
+            ValueKind::Map => "a map",
+            ValueKind::Struct => "a struct",
+        };
+
+        f.write_str(name)
+    }
+}
+
+/// The value of a struct. Its fields are kept in the order they were added.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StructValue {
+    name: String,
+    fields: Vec<(String, Value)>,
+}
+
+impl StructValue {
+    /// Creates a struct without any fields. The name is usually the name of
+    /// the type the struct was converted from.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            fields: Vec::new(),
+        }
+    }
+
+    /// Adds a field to the struct.
+    pub fn with_field(mut self, name: impl Into<String>, value: Value) -> Self {
+        self.set(name, value);
+        self
+    }
+
+    /// The name of the struct.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Gets the value of a field.
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.fields
+            .iter()
+            .find(|(field_name, _)| field_name == name)
+            .map(|(_, value)| value)
+    }
+
+    /// Gets the mutable value of a field.
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut Value> {
+        self.fields
+            .iter_mut()
+            .find(|(field_name, _)| field_name == name)
+            .map(|(_, value)| value)
+    }
+
+    /// Sets the value of a field, adding the field if the struct does not
+    /// have it yet. The previous value of the field is returned.
+    pub fn set(&mut self, name: impl Into<String>, value: Value) -> Option<Value> {
+        let name = name.into();
+        match self.get_mut(&name) {
+            Some(field) => Some(core::mem::replace(field, value)),
+            None => {
+                self.fields.push((name, value));
+                None
+            }
+        }
+    }
+
+    /// Removes a field from the struct, returning its value.
+    pub fn remove(&mut self, name: &str) -> Option<Value> {
+        let index = self
+            .fields
+            .iter()
+            .position(|(field_name, _)| field_name == name)?;
+        Some(self.fields.remove(index).1)
+    }
+
+    /// Iterates over the names and values of the fields.
+    pub fn fields(&self) -> impl Iterator<Item = (&str, &Value)> {
+        self.fields
+            .iter()
+            .map(|(name, value)| (name.as_str(), value))
+    }
+
+    /// The number of fields of the struct.
+    pub fn len(&self) -> usize {
+        self.fields.len()
+    }
+
+    /// Determines if the struct has no fields.
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+}
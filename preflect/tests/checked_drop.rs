@@ -0,0 +1,85 @@
+#![cfg(feature = "checked-drop")]
+
+use preflect::drop::CheckedPartialDrop;
+use preflect::drop::PartialDrop;
+use preflect::fields::BaseHasFields;
+use preflect::fields::FieldAccessError;
+use preflect::fields::FieldAccessResult;
+use preflect::fields::FieldInfo;
+use std::any::Any;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
+/// Counts how many times the value has been dropped.
+struct CountDrop(&'static AtomicUsize);
+
+impl Drop for CountDrop {
+    fn drop(&mut self) {
+        self.0.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[derive(PartialDrop)]
+struct Pair {
+    a: CountDrop,
+    b: CountDrop,
+}
+
+// Reports `b` when asked for `a`, which must not let `take_field` move out a
+// field that is dropped again later.
+impl BaseHasFields for Pair {
+    fn field_names(&self) -> &'static [&'static str] {
+        &["a", "b"]
+    }
+
+    fn field_info(&self) -> &'static [FieldInfo] {
+        &[]
+    }
+
+    fn get_field_info<'n>(&self, name: &'n str) -> FieldAccessResult<'n, &'static FieldInfo> {
+        Err(FieldAccessError::missing_field(name, self.field_names()))
+    }
+
+    fn get_field_raw<'s, 'n>(&'s self, name: &'n str) -> FieldAccessResult<'n, &'s dyn Any> {
+        match name {
+            "a" | "b" => Ok(&self.b),
+            _ => Err(FieldAccessError::missing_field(name, self.field_names())),
+        }
+    }
+
+    fn get_field_mut_raw<'s, 'n>(
+        &'s mut self,
+        name: &'n str,
+    ) -> FieldAccessResult<'n, &'s mut dyn Any> {
+        match name {
+            "a" | "b" => Ok(&mut self.b),
+            _ => Err(FieldAccessError::missing_field(name, self.field_names())),
+        }
+    }
+
+    fn visit_fields_mut<'s>(&'s mut self, visit: &mut dyn FnMut(&'static str, &'s mut dyn Any)) {
+        visit("b", &mut self.b);
+    }
+}
+
+#[test]
+fn take_field_ignores_base_has_fields() {
+    static A_DROPS: AtomicUsize = AtomicUsize::new(0);
+    static B_DROPS: AtomicUsize = AtomicUsize::new(0);
+
+    let pair = Pair {
+        a: CountDrop(&A_DROPS),
+        b: CountDrop(&B_DROPS),
+    };
+    let mut pair = CheckedPartialDrop::new(pair);
+    let a = pair.take_field::<CountDrop>("a").unwrap();
+    assert!(std::ptr::eq(&A_DROPS, a.0));
+
+    drop(pair);
+    assert_eq!(0, A_DROPS.load(Ordering::SeqCst));
+    assert_eq!(1, B_DROPS.load(Ordering::SeqCst));
+
+    drop(a);
+    assert_eq!(1, A_DROPS.load(Ordering::SeqCst));
+    assert_eq!(1, B_DROPS.load(Ordering::SeqCst));
+}
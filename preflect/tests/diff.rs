@@ -0,0 +1,59 @@
+#![cfg(feature = "alloc")]
+
+use preflect::diff::diff;
+use preflect::diff::FieldChange;
+use preflect::fields::HasFields;
+
+#[derive(Clone, Copy, Debug, HasFields, PartialEq)]
+enum State {
+    Idle,
+    Running,
+    Stopped { code: u8 },
+    Failed { code: u8 },
+}
+
+#[derive(Clone, Copy, Debug, HasFields, PartialEq)]
+struct Job {
+    id: u32,
+    #[preflect(nested)]
+    state: State,
+}
+
+#[test]
+fn fieldless_variants() {
+    let changes = diff(&State::Idle, &State::Running).unwrap();
+    assert_eq!(1, changes.len());
+    assert_eq!("", changes[0].path());
+    assert_eq!(Some(&State::Idle), changes[0].downcast_old::<State>());
+    assert_eq!(Some(&State::Running), changes[0].downcast_new::<State>());
+
+    assert!(diff(&State::Running, &State::Running).unwrap().is_empty());
+}
+
+#[test]
+fn variants_with_the_same_fields() {
+    let changes = diff(&State::Stopped { code: 1 }, &State::Failed { code: 1 }).unwrap();
+    let paths: Vec<_> = changes.iter().map(FieldChange::path).collect();
+    assert_eq!(vec![""], paths);
+
+    let changes = diff(&State::Failed { code: 1 }, &State::Failed { code: 2 }).unwrap();
+    let paths: Vec<_> = changes.iter().map(FieldChange::path).collect();
+    assert_eq!(vec!["code"], paths);
+}
+
+#[test]
+fn nested_variants() {
+    let old = Job {
+        id: 1,
+        state: State::Idle,
+    };
+    let new = Job {
+        state: State::Running,
+        ..old
+    };
+
+    let changes = diff(&old, &new).unwrap();
+    let paths: Vec<_> = changes.iter().map(FieldChange::path).collect();
+    assert_eq!(vec!["state"], paths);
+    assert_eq!(Some(&State::Running), changes[0].downcast_new::<State>());
+}
@@ -0,0 +1,232 @@
+#![cfg(feature = "alloc")]
+
+use preflect::json::JsonPatch;
+use preflect::json::JsonPointer;
+use preflect::json::PatchOperation;
+use preflect::value::Value;
+use serde_json::json;
+use std::collections::BTreeMap;
+
+fn to_value(json: &serde_json::Value) -> Value {
+    match json {
+        serde_json::Value::Null => Value::Null,
+        serde_json::Value::Bool(value) => Value::Bool(*value),
+        serde_json::Value::Number(number) => match (number.as_u64(), number.as_i64()) {
+            (Some(value), _) => Value::UInt(value),
+            (_, Some(value)) => Value::Int(value),
+            _ => Value::Float(number.as_f64().unwrap()),
+        },
+        serde_json::Value::String(value) => Value::String(value.clone()),
+        serde_json::Value::Array(elements) => Value::List(elements.iter().map(to_value).collect()),
+        serde_json::Value::Object(entries) => Value::Map(
+            entries
+                .iter()
+                .map(|(key, value)| (key.clone(), to_value(value)))
+                .collect::<BTreeMap<_, _>>(),
+        ),
+    }
+}
+
+fn to_patch(json: &serde_json::Value) -> JsonPatch {
+    let pointer = |operation: &serde_json::Value, key: &str| {
+        JsonPointer::parse(operation[key].as_str().unwrap()).unwrap()
+    };
+
+    let operations = json.as_array().unwrap().iter().map(|operation| {
+        let path = pointer(operation, "path");
+        let value = to_value(&operation["value"]);
+        match operation["op"].as_str().unwrap() {
+            "add" => PatchOperation::Add { path, value },
+            "remove" => PatchOperation::Remove { path },
+            "replace" => PatchOperation::Replace { path, value },
+            "move" => PatchOperation::Move {
+                from: pointer(operation, "from"),
+                path,
+            },
+            "copy" => PatchOperation::Copy {
+                from: pointer(operation, "from"),
+                path,
+            },
+            "test" => PatchOperation::Test { path, value },
+            op => panic!("Unknown operation '{}'", op),
+        }
+    });
+
+    JsonPatch::from(operations.collect::<Vec<_>>())
+}
+
+/// The examples of RFC 6901, section 5.
+#[test]
+fn rfc_6901_examples() {
+    let document = to_value(&json!({
+        "foo": ["bar", "baz"],
+        "": 0,
+        "a/b": 1,
+        "c%d": 2,
+        "e^f": 3,
+        "g|h": 4,
+        "i\\j": 5,
+        "k\"l": 6,
+        " ": 7,
+        "m~n": 8
+    }));
+
+    let cases = [
+        (
+            "",
+            to_value(&json!({
+                "foo": ["bar", "baz"],
+                "": 0,
+                "a/b": 1,
+                "c%d": 2,
+                "e^f": 3,
+                "g|h": 4,
+                "i\\j": 5,
+                "k\"l": 6,
+                " ": 7,
+                "m~n": 8
+            })),
+        ),
+        ("/foo", to_value(&json!(["bar", "baz"]))),
+        ("/foo/0", to_value(&json!("bar"))),
+        ("/", Value::UInt(0)),
+        ("/a~1b", Value::UInt(1)),
+        ("/c%d", Value::UInt(2)),
+        ("/e^f", Value::UInt(3)),
+        ("/g|h", Value::UInt(4)),
+        ("/i\\j", Value::UInt(5)),
+        ("/k\"l", Value::UInt(6)),
+        ("/ ", Value::UInt(7)),
+        ("/m~0n", Value::UInt(8)),
+    ];
+
+    for (pointer, expected) in cases.iter() {
+        let parsed = JsonPointer::parse(pointer).unwrap();
+        assert_eq!(*pointer, parsed.to_string());
+        assert_eq!(Some(expected), document.pointer(&parsed), "{}", pointer);
+    }
+
+    assert!(JsonPointer::parse("foo").is_err());
+    assert!(JsonPointer::parse("/~2").is_err());
+    assert_eq!(
+        None,
+        document.pointer(&JsonPointer::parse("/foo/01").unwrap())
+    );
+}
+
+/// The examples of RFC 6902, appendix A. The example of an invalid JSON
+/// document (A.13) is omitted since it is rejected by the JSON parser.
+#[test]
+fn rfc_6902_examples() {
+    let cases = [
+        (
+            "A.1",
+            json!({ "foo": "bar" }),
+            json!([{ "op": "add", "path": "/baz", "value": "qux" }]),
+            Some(json!({ "baz": "qux", "foo": "bar" })),
+        ),
+        (
+            "A.2",
+            json!({ "foo": ["bar", "baz"] }),
+            json!([{ "op": "add", "path": "/foo/1", "value": "qux" }]),
+            Some(json!({ "foo": ["bar", "qux", "baz"] })),
+        ),
+        (
+            "A.3",
+            json!({ "baz": "qux", "foo": "bar" }),
+            json!([{ "op": "remove", "path": "/baz" }]),
+            Some(json!({ "foo": "bar" })),
+        ),
+        (
+            "A.4",
+            json!({ "foo": ["bar", "qux", "baz"] }),
+            json!([{ "op": "remove", "path": "/foo/1" }]),
+            Some(json!({ "foo": ["bar", "baz"] })),
+        ),
+        (
+            "A.5",
+            json!({ "baz": "qux", "foo": "bar" }),
+            json!([{ "op": "replace", "path": "/baz", "value": "boo" }]),
+            Some(json!({ "baz": "boo", "foo": "bar" })),
+        ),
+        (
+            "A.6",
+            json!({ "foo": { "bar": "baz", "waldo": "fred" }, "qux": { "corge": "grault" } }),
+            json!([{ "op": "move", "from": "/foo/waldo", "path": "/qux/thud" }]),
+            Some(json!({ "foo": { "bar": "baz" }, "qux": { "corge": "grault", "thud": "fred" } })),
+        ),
+        (
+            "A.7",
+            json!({ "foo": ["all", "grass", "cows", "eat"] }),
+            json!([{ "op": "move", "from": "/foo/1", "path": "/foo/3" }]),
+            Some(json!({ "foo": ["all", "cows", "eat", "grass"] })),
+        ),
+        (
+            "A.8",
+            json!({ "baz": "qux", "foo": ["a", 2, "c"] }),
+            json!([
+                { "op": "test", "path": "/baz", "value": "qux" },
+                { "op": "test", "path": "/foo/1", "value": 2 }
+            ]),
+            Some(json!({ "baz": "qux", "foo": ["a", 2, "c"] })),
+        ),
+        (
+            "A.9",
+            json!({ "baz": "qux" }),
+            json!([{ "op": "test", "path": "/baz", "value": "bar" }]),
+            None,
+        ),
+        (
+            "A.10",
+            json!({ "foo": "bar" }),
+            json!([{ "op": "add", "path": "/child", "value": { "grandchild": {} } }]),
+            Some(json!({ "foo": "bar", "child": { "grandchild": {} } })),
+        ),
+        (
+            "A.11",
+            json!({ "foo": "bar" }),
+            json!([{ "op": "add", "path": "/baz", "value": "qux", "xyz": 123 }]),
+            Some(json!({ "foo": "bar", "baz": "qux" })),
+        ),
+        (
+            "A.12",
+            json!({ "foo": "bar" }),
+            json!([{ "op": "add", "path": "/baz/bat", "value": "qux" }]),
+            None,
+        ),
+        (
+            "A.14",
+            json!({ "/": 9, "~1": 10 }),
+            json!([{ "op": "test", "path": "/~01", "value": 10 }]),
+            Some(json!({ "/": 9, "~1": 10 })),
+        ),
+        (
+            "A.15",
+            json!({ "/": 9, "~1": 10 }),
+            json!([{ "op": "test", "path": "/~01", "value": "10" }]),
+            None,
+        ),
+        (
+            "A.16",
+            json!({ "foo": ["bar"] }),
+            json!([{ "op": "add", "path": "/foo/-", "value": ["abc", "def"] }]),
+            Some(json!({ "foo": ["bar", ["abc", "def"]] })),
+        ),
+    ];
+
+    for (name, document, patch, expected) in cases.iter() {
+        let mut document = to_value(document);
+        let original = document.clone();
+        let result = to_patch(patch).apply_to_value(&mut document);
+        match expected {
+            Some(expected) => {
+                assert_eq!(Ok(()), result, "{}", name);
+                assert_eq!(to_value(expected), document, "{}", name);
+            }
+            None => {
+                assert!(result.is_err(), "{}", name);
+                assert_eq!(original, document, "{}", name);
+            }
+        }
+    }
+}